@@ -0,0 +1,34 @@
+use criterion::{black_box, Criterion, criterion_group, criterion_main};
+
+use aoc_2024::days;
+
+/// Benchmarks parsing and both parts of every registered day that has a cached input.
+pub fn bench_all_days(criterion: &mut Criterion) {
+    for registration in days::all() {
+        let day = registration.day;
+        let Some(input) = days::cached_input(days::YEAR, day).unwrap() else {
+            eprintln!("{day}: skipped, no cached input");
+            continue;
+        };
+        let parsed = (registration.parse)(&input).unwrap();
+
+        let mut group = criterion.benchmark_group(day.to_string());
+        group.bench_function("parsing official input", |b| {
+            b.iter(|| (registration.parse)(black_box(&input)));
+        });
+        group.bench_function("part 1 official input", |b| {
+            b.iter(|| parsed.part1());
+        });
+        group.bench_function("part 2 official input", |b| {
+            b.iter(|| parsed.part2());
+        });
+        group.finish();
+    }
+}
+
+criterion_group!(name = benches;
+    config = Criterion::default().with_plots();
+    targets =
+    bench_all_days,
+);
+criterion_main!(benches);
@@ -1,10 +1,12 @@
-use std::sync::Arc;
+use std::sync::{Arc, LazyLock};
 use criterion::{black_box, Criterion, criterion_group, criterion_main};
 
+use aoc_2024::days;
 use aoc_2024::days::day10 as day;
 use day::DAY;
 
-const OFFICIAL_INPUT: &str = include_str!("../input/day10.in");
+static OFFICIAL_INPUT: LazyLock<String> = LazyLock::new(|| days::cached_input(days::YEAR, DAY).unwrap()
+    .expect("Run the day once to cache its input before benchmarking it"));
 
 pub fn bench_parsing_official(criterion: &mut Criterion) {
     criterion.bench_function(&format!("{DAY} parsing official input"), |b| {
@@ -1,9 +1,13 @@
+use std::sync::LazyLock;
+
 use criterion::{black_box, Criterion, criterion_group, criterion_main};
 
+use aoc_2024::days;
 use aoc_2024::days::day13 as day;
 use day::DAY;
 
-const OFFICIAL_INPUT: &str = include_str!("../input/day13.in");
+static OFFICIAL_INPUT: LazyLock<String> = LazyLock::new(|| days::cached_input(days::YEAR, DAY).unwrap()
+    .expect("Run the day once to cache its input before benchmarking it"));
 
 pub fn bench_parsing_official(criterion: &mut Criterion) {
     criterion.bench_function(&format!("{DAY} parsing official input"), |b| {
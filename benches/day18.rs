@@ -1,9 +1,15 @@
+use std::sync::LazyLock;
+
 use criterion::{black_box, Criterion, criterion_group, criterion_main};
 
+use aoc_2024::days;
 use aoc_2024::days::day18 as day;
+use aoc_2024::days::util::ParsedGrid;
+use aoc_2024::days::util::search::{first_breaking_change, Strategy};
 use day::DAY;
 
-const OFFICIAL_INPUT: &str = include_str!("../input/day18.in");
+static OFFICIAL_INPUT: LazyLock<String> = LazyLock::new(|| days::cached_input(days::YEAR, DAY).unwrap()
+    .expect("Run the day once to cache its input before benchmarking it"));
 
 pub fn bench_parsing_official(criterion: &mut Criterion) {
     criterion.bench_function(&format!("{DAY} parsing official input"), |b| {
@@ -45,9 +51,24 @@ pub fn bench_part2_official_with_parsing(criterion: &mut Criterion) {
     });
 }
 
+pub fn bench_search_strategies_official(criterion: &mut Criterion) {
+    let mut group = criterion.benchmark_group(format!("{DAY} search strategies official input"));
+    let input: day::Input = black_box(OFFICIAL_INPUT.parse().unwrap());
+    let grid = ParsedGrid::<bool>::new(71, 71);
+    let corruptions = input.byte_locations().iter()
+        .map(|(x, y)| y * 71 + x)
+        .collect::<Vec<_>>();
+    for (name, strategy) in [("incremental", Strategy::Incremental), ("union-find", Strategy::ReverseUnionFind), ("binary-search", Strategy::BinarySearch)] {
+        group.bench_function(name, |b| {
+            b.iter(|| first_breaking_change(&grid, black_box(&corruptions), 0, 71 * 71 - 1, strategy));
+        });
+    }
+    group.finish();
+}
+
 criterion_group!(name = benches;
     config = Criterion::default().with_plots();
     targets =
-    bench_parsing_official, bench_part1_official, bench_part2_official, bench_part1_official_with_parsing, bench_part2_official_with_parsing,
+    bench_parsing_official, bench_part1_official, bench_part2_official, bench_part1_official_with_parsing, bench_part2_official_with_parsing, bench_search_strategies_official,
 );
 criterion_main!(benches);
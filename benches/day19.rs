@@ -1,9 +1,13 @@
+use std::sync::LazyLock;
+
 use criterion::{black_box, Criterion, criterion_group, criterion_main};
 
+use aoc_2024::days;
 use aoc_2024::days::day19 as day;
 use day::DAY;
 
-const OFFICIAL_INPUT: &str = include_str!("../input/day19.in");
+static OFFICIAL_INPUT: LazyLock<String> = LazyLock::new(|| days::cached_input(days::YEAR, DAY).unwrap()
+    .expect("Run the day once to cache its input before benchmarking it"));
 
 pub fn bench_parsing_official(criterion: &mut Criterion) {
     criterion.bench_function(&format!("{DAY} parsing official input"), |b| {
@@ -45,9 +49,21 @@ pub fn bench_part2_official_with_parsing(criterion: &mut Criterion) {
     });
 }
 
+pub fn bench_approaches_official(criterion: &mut Criterion) {
+    let mut group = criterion.benchmark_group(format!("{DAY} approaches official input"));
+    let input = black_box(OFFICIAL_INPUT.parse().unwrap());
+    group.bench_function("sequential", |b| {
+        b.iter(|| day::combinations_sequential(black_box(&input)));
+    });
+    group.bench_function("parallel", |b| {
+        b.iter(|| day::combinations_parallel(black_box(&input)));
+    });
+    group.finish();
+}
+
 criterion_group!(name = benches;
     config = Criterion::default().with_plots();
     targets =
-    bench_parsing_official, bench_part1_official, bench_part2_official, bench_part1_official_with_parsing, bench_part2_official_with_parsing,
+    bench_parsing_official, bench_part1_official, bench_part2_official, bench_part1_official_with_parsing, bench_part2_official_with_parsing, bench_approaches_official,
 );
 criterion_main!(benches);
@@ -1,9 +1,13 @@
+use std::sync::LazyLock;
+
 use criterion::{black_box, Criterion, criterion_group, criterion_main};
 
+use aoc_2024::days;
 use aoc_2024::days::day20 as day;
 use day::DAY;
 
-const OFFICIAL_INPUT: &str = include_str!("../input/day20.in");
+static OFFICIAL_INPUT: LazyLock<String> = LazyLock::new(|| days::cached_input(days::YEAR, DAY).unwrap()
+    .expect("Run the day once to cache its input before benchmarking it"));
 
 pub fn bench_parsing_official(criterion: &mut Criterion) {
     criterion.bench_function(&format!("{DAY} parsing official input"), |b| {
@@ -16,14 +20,14 @@ pub fn bench_parsing_official(criterion: &mut Criterion) {
 pub fn bench_part1_official(criterion: &mut Criterion) {
     criterion.bench_function(&format!("{DAY} part 1 official input"), |b| {
         let input = black_box(OFFICIAL_INPUT.parse().unwrap());
-        b.iter(|| day::process_part1(black_box(&input)));
+        b.iter(|| day::process_part1::<100>(black_box(&input)));
     });
 }
 
 pub fn bench_part2_official(criterion: &mut Criterion) {
     criterion.bench_function(&format!("{DAY}, part 2 official input"), |b| {
         let input = black_box(OFFICIAL_INPUT.parse().unwrap());
-        b.iter(|| day::process_part1(black_box(&input)));
+        b.iter(|| day::process_part1::<100>(black_box(&input)));
     });
 }
 
@@ -31,7 +35,7 @@ pub fn bench_part1_official_with_parsing(criterion: &mut Criterion) {
     criterion.bench_function(&format!("{DAY} part 1 official input with parsing"), |b| {
         b.iter(|| {
             let input = black_box(OFFICIAL_INPUT.parse().unwrap());
-            day::process_part1(black_box(&input))
+            day::process_part1::<100>(black_box(&input))
         });
     });
 }
@@ -40,7 +44,7 @@ pub fn bench_part2_official_with_parsing(criterion: &mut Criterion) {
     criterion.bench_function(&format!("{DAY} part 2 official input with parsing"), |b| {
         b.iter(|| {
             let input = black_box(OFFICIAL_INPUT.parse().unwrap());
-            day::process_part1(black_box(&input))
+            day::process_part1::<100>(black_box(&input))
         });
     });
 }
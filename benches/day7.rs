@@ -1,9 +1,13 @@
+use std::sync::LazyLock;
+
 use criterion::{black_box, Criterion, criterion_group, criterion_main};
 
+use aoc_2024::days;
 use aoc_2024::days::day7 as day;
 use day::DAY;
 
-const OFFICIAL_INPUT: &str = include_str!("../input/day7.in");
+static OFFICIAL_INPUT: LazyLock<String> = LazyLock::new(|| days::cached_input(days::YEAR, DAY).unwrap()
+    .expect("Run the day once to cache its input before benchmarking it"));
 
 pub fn bench_parsing_official(criterion: &mut Criterion) {
     criterion.bench_function(&format!("{DAY} parsing official input"), |b| {
@@ -23,39 +27,43 @@ pub fn bench_part1_official(criterion: &mut Criterion) {
 pub fn bench_part2_official(criterion: &mut Criterion) {
     criterion.bench_function(&format!("{DAY}, part 2 official input"), |b| {
         let input = black_box(OFFICIAL_INPUT.parse().unwrap());
-        b.iter(|| day::process_part1(black_box(&input)));
+        b.iter(|| day::process_part2(black_box(&input)));
     });
 }
 
 pub fn bench_part1_official_with_parsing(criterion: &mut Criterion) {
     criterion.bench_function(&format!("{DAY} part 1 official input with parsing"), |b| {
-        let runtime = tokio::runtime::Builder::new_multi_thread()
-            .build().unwrap();
-        b.to_async(runtime).iter(|| {
-            async {
-                let input = black_box(OFFICIAL_INPUT.parse().unwrap());
-                let _ = day::process_part1(black_box(&input)).await;
-            }
+        b.iter(|| {
+            let input = black_box(OFFICIAL_INPUT.parse().unwrap());
+            day::process_part1(black_box(&input))
         });
     });
 }
 
 pub fn bench_part2_official_with_parsing(criterion: &mut Criterion) {
     criterion.bench_function(&format!("{DAY} part 2 official input with parsing"), |b| {
-        let runtime = tokio::runtime::Builder::new_multi_thread()
-            .build().unwrap();
-        b.to_async(runtime).iter(|| {
-            async {
-                let input = black_box(OFFICIAL_INPUT.parse().unwrap());
-                let _ = day::process_part1(black_box(&input)).await;
-            }
+        b.iter(|| {
+            let input = black_box(OFFICIAL_INPUT.parse().unwrap());
+            day::process_part2(black_box(&input))
         });
     });
 }
 
+pub fn bench_solvers_official(criterion: &mut Criterion) {
+    let mut group = criterion.benchmark_group(format!("{DAY} solvers official input"));
+    let input: day::Input = black_box(OFFICIAL_INPUT.parse().unwrap());
+    group.bench_function("forward", |b| {
+        b.iter(|| day::calibration_result_forward(black_box(&input), &day::Operator::ALL2));
+    });
+    group.bench_function("reverse", |b| {
+        b.iter(|| day::process_part2(black_box(&input)));
+    });
+    group.finish();
+}
+
 criterion_group!(name = benches;
     config = Criterion::default().with_plots();
     targets =
-    bench_parsing_official, bench_part1_official, bench_part2_official, bench_part1_official_with_parsing, bench_part2_official_with_parsing,
+    bench_parsing_official, bench_part1_official, bench_part2_official, bench_part1_official_with_parsing, bench_part2_official_with_parsing, bench_solvers_official,
 );
 criterion_main!(benches);
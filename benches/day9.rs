@@ -1,8 +1,12 @@
+use std::sync::LazyLock;
+
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use aoc_2024::days;
 use aoc_2024::days::day9 as day;
 use day::DAY;
 
-const OFFICIAL_INPUT: &str = include_str!("../input/day9.in");
+static OFFICIAL_INPUT: LazyLock<String> = LazyLock::new(|| days::cached_input(days::YEAR, DAY).unwrap()
+    .expect("Run the day once to cache its input before benchmarking it"));
 
 pub fn bench_parsing_official(criterion: &mut Criterion) {
     criterion.bench_function(&format!("{DAY} parsing official input"), |b| {
@@ -128,11 +132,26 @@ pub fn bench_part2_evil2_with_parsing(criterion: &mut Criterion) {
     });
 }
 
+pub fn bench_approaches_evil(criterion: &mut Criterion) {
+    let mut group = criterion.benchmark_group(format!("{DAY} part 2 approaches evil inputs"));
+    for (name, raw_input) in [("evil input 1", EVIL1), ("evil input 2", EVIL2)] {
+        let input: day::Input = raw_input.parse().unwrap();
+        group.bench_function(format!("free-space {name}"), |b| {
+            b.iter(|| day::compact_free_space(black_box(&input)));
+        });
+        group.bench_function(format!("filler-buckets {name}"), |b| {
+            b.iter(|| day::compact_filler_heaps(black_box(&input)));
+        });
+    }
+    group.finish();
+}
+
 criterion_group!(name = benches;
     config = Criterion::default().with_plots();
     targets =
     bench_parsing_official, bench_part1_official, bench_part2_official, bench_part1_official_with_parsing, bench_part2_official_with_parsing,
     bench_parsing_evil1, bench_part1_evil1, bench_part2_evil1, bench_part1_evil1_with_parsing, bench_part2_evil1_with_parsing,
     bench_parsing_evil2, bench_part1_evil2, bench_part2_evil2, bench_part1_evil2_with_parsing, bench_part2_evil2_with_parsing,
+    bench_approaches_evil,
 );
 criterion_main!(benches);
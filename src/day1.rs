@@ -5,5 +5,6 @@ mod util;
 pub async fn main() -> eyre::Result<()> {
     util::setup()?;
 
-    days::day1::run().await
+    println!("{}", days::day1::run().await?);
+    Ok(())
 }
@@ -5,5 +5,6 @@ mod util;
 pub async fn main() -> eyre::Result<()> {
     util::setup()?;
 
-    days::day10::run().await
+    println!("{}", days::day10::run().await?);
+    Ok(())
 }
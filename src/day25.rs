@@ -0,0 +1,10 @@
+use aoc_2024::days;
+mod util;
+
+#[tokio::main]
+pub async fn main() -> eyre::Result<()> {
+    util::setup()?;
+
+    println!("{}", days::day25::run().await?);
+    Ok(())
+}
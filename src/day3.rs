@@ -5,5 +5,6 @@ mod util;
 pub async fn main() -> eyre::Result<()> {
     util::setup()?;
 
-    days::day3::run().await
+    println!("{}", days::day3::run().await?);
+    Ok(())
 }
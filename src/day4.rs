@@ -5,5 +5,6 @@ mod util;
 pub async fn main() -> eyre::Result<()> {
     util::setup()?;
 
-    days::day4::run().await
+    println!("{}", days::day4::run().await?);
+    Ok(())
 }
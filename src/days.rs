@@ -1,54 +1,265 @@
 use std::fmt::{Display, Formatter};
+use std::future::Future;
+use std::io::Write;
 use std::ops::Deref;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
 use std::str::FromStr;
-use std::sync::{Arc, LazyLock};
+use std::sync::{Arc, OnceLock};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use eyre::{eyre, WrapErr};
 
-use reqwest::header::ACCEPT;
-use reqwest::{Client, StatusCode, Url};
+use reqwest::header::{ACCEPT, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED};
+use reqwest::{Client, RequestBuilder, Response, StatusCode, Url};
 use reqwest::cookie::Jar;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tracing::warn;
 
+#[cfg(feature = "day1")]
 pub mod day1;
+#[cfg(feature = "day2")]
 pub mod day2;
+#[cfg(feature = "day3")]
 pub mod day3;
+#[cfg(feature = "day4")]
 pub mod day4;
+#[cfg(feature = "day5")]
 pub mod day5;
+#[cfg(feature = "day6")]
 pub mod day6;
+#[cfg(feature = "day7")]
 pub mod day7;
+#[cfg(feature = "day8")]
 pub mod day8;
+#[cfg(feature = "day9")]
 pub mod day9;
+#[cfg(feature = "day10")]
 pub mod day10;
+#[cfg(feature = "day11")]
 pub mod day11;
+#[cfg(feature = "day12")]
 pub mod day12;
+#[cfg(feature = "day13")]
 pub mod day13;
+#[cfg(feature = "day14")]
 pub mod day14;
+#[cfg(feature = "day15")]
 pub mod day15;
+#[cfg(feature = "day16")]
 pub mod day16;
+#[cfg(feature = "day17")]
 pub mod day17;
+#[cfg(feature = "day18")]
 pub mod day18;
+#[cfg(feature = "day19")]
 pub mod day19;
+#[cfg(feature = "day20")]
 pub mod day20;
+#[cfg(feature = "day21")]
 pub mod day21;
-mod util;
+#[cfg(feature = "day22")]
+pub mod day22;
+#[cfg(feature = "day25")]
+pub mod day25;
+pub mod answer_cache;
+pub mod approach;
+pub mod config;
+pub mod encryption;
+pub mod harness;
+pub mod leaderboard;
+pub mod manifest;
+pub mod puzzle;
+pub mod report;
+pub mod solver;
+pub mod submit;
+pub mod update;
+pub mod util;
 
-pub const CLIENT: LazyLock<Client> = LazyLock::new(|| {
+static CLIENT: OnceLock<Client> = OnceLock::new();
+
+/// The client authenticated with the [`config::session`], which is built once and shared by all
+/// requests so they reuse its connections and cookie jar.
+pub fn client() -> eyre::Result<&'static Client> {
+    client_with_session(config::session)
+}
+
+/// Like [`client`], but asks `session` for the session if the client isn't built yet, so tests
+/// don't have to set `AOC_SESSION`.
+fn client_with_session(session: impl FnOnce() -> eyre::Result<String>) -> eyre::Result<&'static Client> {
+    if let Some(client) = CLIENT.get() {
+        return Ok(client);
+    }
+
+    let client = build_client(&session()?);
+    Ok(CLIENT.get_or_init(|| client))
+}
+
+static PUBLIC_CLIENT: OnceLock<Client> = OnceLock::new();
+
+/// A client without the session cookie for requests to other sites than AoC.
+pub fn public_client() -> &'static Client {
+    PUBLIC_CLIENT.get_or_init(|| Client::builder()
+        .user_agent(user_agent())
+        .build().unwrap())
+}
+
+/// The `User-Agent` sent with every request, so the AoC maintainers know who to contact about
+/// automated traffic. `AOC_USER_AGENT` replaces it entirely, `AOC_CONTACT` appends contact details.
+pub fn user_agent() -> String {
+    if let Ok(user_agent) = std::env::var("AOC_USER_AGENT") {
+        return user_agent;
+    }
+
+    match std::env::var("AOC_CONTACT") {
+        Ok(contact) => format!("{} by {contact}", env!("CARGO_PKG_REPOSITORY")),
+        Err(_) => env!("CARGO_PKG_REPOSITORY").to_string(),
+    }
+}
+
+fn build_client(session: &str) -> Client {
     let jar = Arc::new(Jar::default());
-    jar.add_cookie_str(&format!("session={}", std::env::var("AOC_SESSION").unwrap()), &Url::from_str("https://adventofcode.com/").unwrap());
+    jar.add_cookie_str(&format!("session={session}"), &Url::from_str("https://adventofcode.com/").unwrap());
     Client::builder()
         .cookie_store(true)
         .cookie_provider(jar)
+        .user_agent(user_agent())
         .build().unwrap()
-});
+}
+
+/// How far along the solution of a day is.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Status {
+    Implemented,
+    /// Added from the template, but not solved yet
+    Stub,
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Implemented => write!(f, "implemented"),
+            Self::Stub => write!(f, "not implemented"),
+        }
+    }
+}
+
+/// A day module, submitted with [`inventory::submit!`] next to its solution so [`all`] picks it up
+/// without being listed anywhere else.
+pub struct Registration {
+    pub day: Day,
+    pub status: Status,
+    /// Set for days that have been solved in more than one way
+    pub approach_history: Option<approach::ApproachHistory>,
+    /// Fetches the input and solves both parts
+    pub run: fn() -> Pin<Box<dyn Future<Output = eyre::Result<harness::DayResult>> + Send>>,
+    /// Parses an input to solve its parts separately, e.g. to time them
+    pub parse: fn(&str) -> eyre::Result<Box<dyn solver::ParsedInput>>,
+    pub solve: fn(&str) -> eyre::Result<(solver::Answer, solver::Answer)>,
+    /// The hashed answers for the author's input, see [`solver::answer_hash`]
+    pub my_answers: Option<solver::MyAnswers>,
+}
+
+inventory::collect!(Registration);
+
+/// Every registered day, ordered by day.
+pub fn all() -> Vec<&'static Registration> {
+    let mut days = inventory::iter::<Registration>.into_iter().collect::<Vec<_>>();
+    days.sort_by_key(|registration| registration.day);
+    days
+}
+
+/// The registration of `day`, if it has a module.
+pub fn registration(day: Day) -> Option<&'static Registration> {
+    inventory::iter::<Registration>.into_iter()
+        .find(|registration| registration.day == day)
+}
+
+/// The [`Status`] of a day, where days without a module are stubs as well.
+pub fn status(day: Day) -> Status {
+    registration(day)
+        .map_or(Status::Stub, |registration| registration.status)
+}
+
+/// The [`ApproachHistory`](approach::ApproachHistory) of days that have been solved in more than one way.
+pub fn approach_history(day: Day) -> Option<approach::ApproachHistory> {
+    registration(day)?.approach_history
+}
+
+/// The event these solutions are for.
+pub const YEAR: u16 = 2024;
+
+/// The page of the puzzle of `day` in the event of `year`, which the input and answer URLs are
+/// relative to.
+pub fn day_url(year: u16, day: Day) -> String {
+    format!("https://adventofcode.com/{year}/day/{}", day.0)
+}
+
+/// The number of days from 1970-01-01 to the given date.
+fn days_since_epoch(year: u16, month: u64, day: u64) -> u64 {
+    // shift the year to start in March, so the leap day is the last day of the year
+    let year = if month <= 2 { year as u64 - 1 } else { year as u64 };
+    let era = year / 400;
+    let year_of_era = year % 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
 
 #[derive(Debug, Copy, Clone, Ord, PartialOrd, PartialEq, Eq)]
 #[repr(transparent)]
 pub struct Day(usize);
 
 impl Day {
+    /// Every day of the advent calendar.
+    pub const ALL: [Day; 25] = {
+        let mut days = [Day(1); 25];
+        let mut index = 0;
+        while index < days.len() {
+            days[index] = Day(index + 1);
+            index += 1;
+        }
+        days
+    };
+
+    /// The days from `first` to `last`, both included.
+    pub fn range(first: Day, last: Day) -> impl Iterator<Item = Day> {
+        (first.0..=last.0).map(Day)
+    }
+
+    /// The day after this one, or [`None`] on the last day of the calendar.
+    pub fn next(&self) -> Option<Day> {
+        Day::try_from(self.0 + 1).ok()
+    }
+
     pub fn filename(&self) -> String {
         format!("day{}.in", self.0)
     }
+
+    /// When the puzzle unlocks, at midnight EST (05:00 UTC) on the day of December of `year`.
+    pub fn unlock_time(&self, year: u16) -> SystemTime {
+        let days = days_since_epoch(year, 12, self.0 as u64);
+        UNIX_EPOCH + Duration::from_secs((days * 24 + 5) * 60 * 60)
+    }
+
+    /// Whether the puzzle of `year` has unlocked at `now`.
+    pub fn is_unlocked(&self, now: SystemTime, year: u16) -> bool {
+        now >= self.unlock_time(year)
+    }
+
+    /// The days of `year` that have unlocked at `now`.
+    pub fn unlocked(now: SystemTime, year: u16) -> impl Iterator<Item = Day> {
+        Self::ALL.into_iter()
+            .take_while(move |day| day.is_unlocked(now, year))
+    }
+
+    /// The day of December in EST at `now`, or [`None`] outside the advent calendar of `year`.
+    pub fn today(now: SystemTime, year: u16) -> Option<Day> {
+        let today = Self::unlocked(now, year).last()?;
+        let tomorrow = today.unlock_time(year) + Duration::from_secs(24 * 60 * 60);
+        (now < tomorrow).then_some(today)
+    }
 }
 
 impl Display for Day {
@@ -69,8 +280,8 @@ impl TryFrom<usize> for Day {
     type Error = eyre::Error;
 
     fn try_from(value: usize) -> Result<Self, Self::Error> {
-        if value == 0 || value > 24 {
-            Err(eyre!("The day must be between 1 and 24"))
+        if value == 0 || value > Self::ALL.len() {
+            Err(eyre!("The day must be between 1 and {}", Self::ALL.len()))
         } else {
             Ok(Day(value))
         }
@@ -85,38 +296,460 @@ impl Deref for Day {
     }
 }
 
-pub async fn get_input(day: Day) -> eyre::Result<String> {
-    let input_dir = Path::new("input");
-    if !input_dir.exists() {
-        std::fs::create_dir(input_dir)
-            .wrap_err("Failed to create directory for inputs")?;
-    } else if !input_dir.is_dir() {
-        return Err(eyre!("./input is not a directory"))
+pub(crate) fn cache_dir(path: &Path) -> eyre::Result<&Path> {
+    if !path.exists() {
+        std::fs::create_dir_all(path)
+            .wrap_err(format!("Failed to create directory {}", path.display()))?;
+    } else if !path.is_dir() {
+        return Err(eyre!("{} is not a directory", path.display()))
     }
 
-    let input_file = input_dir.join(day.filename());
-    let input = std::fs::read_to_string(&input_file);
+    Ok(path)
+}
 
-    if let Ok(input) = input {
-        Ok(input)
+async fn send(request: RequestBuilder, what: &str) -> eyre::Result<Response> {
+    let response = request
+        .send().await
+        .context(format!("Failed to request {what}"))?;
+    let error_message = if response.status() == StatusCode::BAD_REQUEST {
+        format!("Failed to request {what}. Your AoC session has probably expired")
     } else {
-        let response = CLIENT.get(format!("https://adventofcode.com/2024/day/{}/input", *day))
-            .header(ACCEPT, "text/plain")
-            .send().await
-            .context(format!("Failed to request {day} input file"))?;
-        let error_message = if response.status() == StatusCode::BAD_REQUEST {
-            format!("Failed to request {day} input file. You probably haven't set the AOC_SESSION variable to your session cookie")
-        } else {
-            format!("Failed to request {day} input file")
-        };
-        let response = response
-            .error_for_status()
-            .context(error_message)?;
-        let input = response.text().await
-            .context(format!("Failed to request {day} input file"))?;
-        std::fs::write(&input_file, &input)
-            .context(format!("Failed to write input to {}", input_file.display()))?;
-
-        Ok(input)
+        format!("Failed to request {what}")
+    };
+    response
+        .error_for_status()
+        .context(error_message)
+}
+
+pub(crate) async fn fetch(url: &str, accept: &str, what: &str) -> eyre::Result<String> {
+    send(client()?.get(url).header(ACCEPT, accept), what).await?
+        .text().await
+        .context(format!("Failed to request {what}"))
+}
+
+/// The validators of a cached response, which are sent along when requesting it again so the
+/// server can answer with `304 Not Modified` instead of the whole response.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Serialize, Deserialize)]
+struct Validators {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    etag: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    last_modified: Option<String>,
+}
+
+impl Validators {
+    /// The validators are stored next to the cached response in `<file>.validators`.
+    fn file(cached_file: &Path) -> PathBuf {
+        let mut file = cached_file.as_os_str().to_owned();
+        file.push(".validators");
+        PathBuf::from(file)
+    }
+
+    fn from_response(response: &Response) -> Self {
+        let header = |name| response.headers().get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(ToOwned::to_owned);
+        Self {
+            etag: header(ETAG),
+            last_modified: header(LAST_MODIFIED),
+        }
+    }
+}
+
+/// Like [`fetch`], but revalidates the response cached in `file` instead of downloading it again
+/// if it hasn't changed, and updates `file` otherwise.
+pub(crate) async fn fetch_cached(url: &str, accept: &str, what: &str, file: &Path) -> eyre::Result<String> {
+    let validators_file = Validators::file(file);
+    let cached = std::fs::read_to_string(file).ok();
+    let validators = cached.as_ref()
+        .and_then(|_| std::fs::read_to_string(&validators_file).ok())
+        .and_then(|validators| serde_json::from_str::<Validators>(&validators).ok())
+        .unwrap_or_default();
+
+    let mut request = client()?.get(url)
+        .header(ACCEPT, accept);
+    if let Some(etag) = &validators.etag {
+        request = request.header(IF_NONE_MATCH, etag);
+    }
+    if let Some(last_modified) = &validators.last_modified {
+        request = request.header(IF_MODIFIED_SINCE, last_modified);
+    }
+    let response = send(request, what).await?;
+
+    if let (StatusCode::NOT_MODIFIED, Some(cached)) = (response.status(), cached) {
+        return Ok(cached);
+    }
+
+    let validators = Validators::from_response(&response);
+    let body = response.text().await
+        .context(format!("Failed to request {what}"))?;
+    std::fs::write(file, &body)
+        .context(format!("Failed to write {what} to {}", file.display()))?;
+    std::fs::write(&validators_file, serde_json::to_string(&validators)?)
+        .context(format!("Failed to write {}", validators_file.display()))?;
+
+    Ok(body)
+}
+
+static USE_EXAMPLES: AtomicBool = AtomicBool::new(false);
+
+/// Makes [`get_input`] return the example from the puzzle description instead of the personal input.
+pub fn use_examples(enabled: bool) {
+    USE_EXAMPLES.store(enabled, Ordering::Relaxed);
+}
+
+static REFRESH_INPUTS: AtomicBool = AtomicBool::new(false);
+
+/// Makes [`get_input`] download the inputs again even if they are already cached.
+pub fn refresh_inputs(enabled: bool) {
+    REFRESH_INPUTS.store(enabled, Ordering::Relaxed);
+}
+
+static WAIT_FOR_UNLOCK: AtomicBool = AtomicBool::new(false);
+
+/// Makes [`get_input`] wait for puzzles that are still locked instead of failing.
+pub fn wait_for_unlock(enabled: bool) {
+    WAIT_FOR_UNLOCK.store(enabled, Ordering::Relaxed);
+}
+
+/// Waits until `day` unlocks, showing a countdown on stderr, or fails if it is still locked and
+/// waiting isn't enabled.
+async fn await_unlock(year: u16, day: Day) -> eyre::Result<()> {
+    let unlock_time = day.unlock_time(year);
+    let remaining = |now: SystemTime| unlock_time.duration_since(now).ok()
+        .filter(|remaining| !remaining.is_zero());
+    let format = |remaining: Duration| {
+        let seconds = remaining.as_secs() + (remaining.subsec_nanos() > 0) as u64;
+        format!("{:02}:{:02}:{:02}", seconds / 3600, seconds / 60 % 60, seconds % 60)
+    };
+
+    let Some(remaining_time) = remaining(SystemTime::now()) else {
+        return Ok(());
+    };
+    if !WAIT_FOR_UNLOCK.load(Ordering::Relaxed) {
+        return Err(eyre!("{day} unlocks in {}, pass --wait to wait for it", format(remaining_time)));
+    }
+
+    let mut stderr = std::io::stderr();
+    while let Some(remaining_time) = remaining(SystemTime::now()) {
+        write!(stderr, "\r{day} unlocks in {}", format(remaining_time))?;
+        stderr.flush()?;
+        // wake up on the full second, so the countdown ticks evenly and ends right at the unlock
+        let tick = Duration::from_nanos(remaining_time.subsec_nanos() as u64);
+        tokio::time::sleep(if tick.is_zero() { Duration::from_secs(1) } else { tick }).await;
+    }
+    writeln!(stderr, "\r{day} unlocked          ")?;
+
+    Ok(())
+}
+
+/// Checks that `input` looks like a puzzle input rather than an error page. A missing final newline
+/// only warns, as inputs saved by hand or trimmed by an editor are fine without one.
+fn validate_input(input: &str) -> eyre::Result<()> {
+    if input.trim().is_empty() {
+        Err(eyre!("The input is empty"))
+    } else if input.contains("Please don't repeatedly request") {
+        Err(eyre!("The input was requested before it unlocked"))
+    } else if input.trim_start().starts_with('<') || input.contains("<html") {
+        Err(eyre!("The input is an HTML page"))
+    } else {
+        if !input.ends_with('\n') {
+            warn!("The input doesn't end with a newline, it may be truncated");
+        }
+        Ok(())
+    }
+}
+
+/// Reads the cached input of `day` in the event of `year` without fetching it. Inputs that are
+/// missing or corrupted are [`None`].
+pub fn cached_input(year: u16, day: Day) -> eyre::Result<Option<String>> {
+    let input_file = input_file(year, day)?;
+    let input = match encryption::passphrase()? {
+        Some(passphrase) => std::fs::read(&input_file).ok()
+            .map(|data| encryption::decrypt(&data, &passphrase)
+                .wrap_err(format!("Failed to decrypt {}", input_file.display())))
+            .transpose()?,
+        None => std::fs::read_to_string(&input_file).ok(),
+    };
+
+    Ok(input.filter(|input| match validate_input(input) {
+        Ok(()) => true,
+        Err(error) => {
+            warn!("Ignoring corrupted {day} input {}: {error}", input_file.display());
+            false
+        }
+    }))
+}
+
+/// The inputs are cached as `<year>/day<N>.in` in the [`config::input_dir`].
+fn input_file(year: u16, day: Day) -> eyre::Result<PathBuf> {
+    let input_dir = config::input_dir()?;
+    let year_dir = input_dir.join(year.to_string());
+    let year_dir = cache_dir(&year_dir)?;
+
+    // encrypted inputs may be committed, so they are kept apart from plain text ones
+    let filename = match encryption::passphrase()? {
+        Some(_) => format!("{}.enc", day.filename()),
+        None => day.filename(),
+    };
+    let input_file = year_dir.join(&filename);
+    if year == YEAR {
+        migrate_flat_input(&input_dir.join(&filename), &input_file)?;
+    }
+
+    Ok(input_file)
+}
+
+/// Moves an input cached directly in the input directory, from before the inputs were cached by
+/// year when all of them were from 2024, to where it is cached now.
+fn migrate_flat_input(flat_file: &Path, input_file: &Path) -> eyre::Result<()> {
+    if flat_file.is_file() && !input_file.exists() {
+        std::fs::rename(flat_file, input_file)
+            .wrap_err(format!("Failed to move {} to {}", flat_file.display(), input_file.display()))?;
+    }
+
+    Ok(())
+}
+
+/// Where an [`Input`] came from.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Source {
+    Cache,
+    Network,
+    /// The example from the puzzle description
+    Example,
+}
+
+impl Display for Source {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Cache => write!(f, "cached input"),
+            Self::Network => write!(f, "downloaded input"),
+            Self::Example => write!(f, "example input"),
+        }
+    }
+}
+
+/// A puzzle input together with where it came from, so runs can be traced back to the exact input.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Input {
+    pub body: String,
+    /// When the input was downloaded, for cached inputs when their file was last written
+    pub fetched_at: SystemTime,
+    pub byte_len: usize,
+    /// The hex encoded SHA-256 hash of the body
+    pub sha256: String,
+    pub source: Source,
+    /// The answers the puzzle description gives, for example inputs
+    pub expected: Option<ExpectedAnswers>,
+}
+
+/// The answers the puzzle description gives for its example, `None` for parts it doesn't state one for.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash)]
+pub struct ExpectedAnswers {
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+/// The hex encoded SHA-256 hash of an input.
+pub fn sha256(body: &str) -> String {
+    format!("{:x}", Sha256::digest(body.as_bytes()))
+}
+
+impl Input {
+    pub fn new(body: String, fetched_at: SystemTime, source: Source) -> Self {
+        Self {
+            byte_len: body.len(),
+            sha256: sha256(&body),
+            body,
+            fetched_at,
+            source,
+            expected: None,
+        }
+    }
+}
+
+pub async fn get_input(year: u16, day: Day) -> eyre::Result<Input> {
+    if USE_EXAMPLES.load(Ordering::Relaxed) {
+        let example = puzzle::get_example(day).await?;
+        let mut input = Input::new(example.input, SystemTime::now(), Source::Example);
+        input.expected = Some(ExpectedAnswers { part1: example.part1, part2: example.part2 });
+        return Ok(input);
+    }
+
+    let input_file = input_file(year, day)?;
+    if !REFRESH_INPUTS.load(Ordering::Relaxed) {
+        if let Some(input) = cached_input(year, day)? {
+            let fetched_at = std::fs::metadata(&input_file)
+                .and_then(|metadata| metadata.modified())
+                .unwrap_or_else(|_| SystemTime::now());
+            return Ok(Input::new(input, fetched_at, Source::Cache));
+        }
+    }
+
+    await_unlock(year, day).await?;
+    let input = fetch(&format!("{}/input", day_url(year, day)), "text/plain", &format!("{day} input file")).await?;
+    validate_input(&input)
+        .wrap_err(format!("Received an invalid {day} input"))?;
+
+    let data = match encryption::passphrase()? {
+        Some(passphrase) => encryption::encrypt(&input, &passphrase)?,
+        None => input.clone().into_bytes(),
+    };
+    std::fs::write(&input_file, data)
+        .context(format!("Failed to write input to {}", input_file.display()))?;
+
+    Ok(Input::new(input, SystemTime::now(), Source::Network))
+}
+
+#[cfg(test)]
+mod test {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+    use super::*;
+
+    #[test]
+    pub fn test_registrations() {
+        let days = all().into_iter()
+            .map(|registration| registration.day)
+            .collect::<Vec<_>>();
+        assert!(days.windows(2).all(|pair| pair[0] < pair[1]), "{days:?}");
+        assert_eq!(cfg!(feature = "day22"), days.contains(&Day(22)));
+        assert_eq!(Status::Stub, status(Day(24)));
+        assert_eq!(cfg!(feature = "day6"), approach_history(Day(6)).is_some());
+        assert!(approach_history(Day(1)).is_none());
+    }
+
+    #[tokio::test]
+    pub async fn test_user_agent_header() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+
+        let server = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut request = Vec::new();
+            let mut buffer = [0; 1024];
+            while !request.ends_with(b"\r\n\r\n") {
+                let read = stream.read(&mut buffer).await.unwrap();
+                request.extend_from_slice(&buffer[..read]);
+            }
+            stream.write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\n\r\n").await.unwrap();
+            String::from_utf8(request).unwrap()
+        });
+
+        build_client("test").get(format!("http://{address}/")).send().await.unwrap();
+        let request = server.await.unwrap();
+
+        assert!(request.contains(&format!("user-agent: {}\r\n", user_agent())));
+    }
+
+    #[tokio::test]
+    pub async fn test_fetch_cached() {
+        client_with_session(|| Ok("test".to_string())).unwrap();
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+
+        let server = tokio::spawn(async move {
+            for _ in 0..2 {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let mut request = Vec::new();
+                let mut buffer = [0; 1024];
+                while !request.ends_with(b"\r\n\r\n") {
+                    let read = stream.read(&mut buffer).await.unwrap();
+                    request.extend_from_slice(&buffer[..read]);
+                }
+                let response: &[u8] = if String::from_utf8(request).unwrap().contains("if-none-match: \"v1\"\r\n") {
+                    b"HTTP/1.1 304 Not Modified\r\nconnection: close\r\n\r\n"
+                } else {
+                    b"HTTP/1.1 200 OK\r\netag: \"v1\"\r\ncontent-length: 4\r\nconnection: close\r\n\r\npage"
+                };
+                stream.write_all(response).await.unwrap();
+            }
+        });
+
+        let dir = std::env::temp_dir().join(format!("aoc_fetch_cached_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("page.html");
+        let url = format!("http://{address}/");
+
+        assert_eq!("page", fetch_cached(&url, "text/html", "page", &file).await.unwrap());
+        assert_eq!("page", std::fs::read_to_string(&file).unwrap());
+        assert_eq!("page", fetch_cached(&url, "text/html", "page", &file).await.unwrap());
+        server.await.unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    pub fn test_client_is_shared() {
+        let client = client_with_session(|| Ok("test".to_string())).unwrap();
+
+        assert!(std::ptr::eq(client, super::client().unwrap()));
+    }
+
+    #[test]
+    pub fn test_unlock_time() {
+        // 2024-12-01T05:00:00Z
+        assert_eq!(UNIX_EPOCH + Duration::from_secs(1_733_029_200), Day(1).unlock_time(2024));
+        // 2024-12-25T05:00:00Z
+        assert_eq!(UNIX_EPOCH + Duration::from_secs(1_735_102_800), Day(25).unlock_time(2024));
+        // 2015-12-01T05:00:00Z
+        assert_eq!(UNIX_EPOCH + Duration::from_secs(1_448_946_000), Day(1).unlock_time(2015));
+    }
+
+    #[test]
+    pub fn test_days() {
+        assert_eq!(Day(25), Day::try_from(25).unwrap());
+        assert!(Day::try_from(26).is_err());
+        assert!(Day::try_from(0).is_err());
+        assert_eq!(vec![Day(23), Day(24), Day(25)], Day::range(Day(23), Day(25)).collect::<Vec<_>>());
+        assert_eq!(Some(Day(2)), Day(1).next());
+        assert_eq!(None, Day(25).next());
+
+        let now = Day(3).unlock_time(2024) + Duration::from_secs(60);
+        assert!(Day(3).is_unlocked(now, 2024));
+        assert!(!Day(4).is_unlocked(now, 2024));
+        assert_eq!(vec![Day(1), Day(2), Day(3)], Day::unlocked(now, 2024).collect::<Vec<_>>());
+        assert_eq!(25, Day::unlocked(now, 2023).count());
+
+        assert_eq!(Some(Day(3)), Day::today(now, 2024));
+        assert_eq!(None, Day::today(now - Duration::from_secs(3 * 24 * 60 * 60), 2024));
+        assert_eq!(Some(Day(25)), Day::today(Day(25).unlock_time(2024) + Duration::from_secs(60), 2024));
+        assert_eq!(None, Day::today(Day(25).unlock_time(2024) + Duration::from_secs(24 * 60 * 60), 2024));
+        assert_eq!(None, Day::today(now, 2023));
+    }
+
+    #[test]
+    pub fn test_migrate_flat_input() {
+        let dir = std::env::temp_dir().join(format!("aoc_migrate_flat_input_{}", std::process::id()));
+        let year_dir = dir.join("2024");
+        std::fs::create_dir_all(&year_dir).unwrap();
+        std::fs::write(dir.join("day1.in"), "1\n").unwrap();
+
+        migrate_flat_input(&dir.join("day1.in"), &year_dir.join("day1.in")).unwrap();
+        assert!(!dir.join("day1.in").exists());
+        assert_eq!("1\n", std::fs::read_to_string(year_dir.join("day1.in")).unwrap());
+
+        std::fs::write(dir.join("day1.in"), "2\n").unwrap();
+        migrate_flat_input(&dir.join("day1.in"), &year_dir.join("day1.in")).unwrap();
+        assert_eq!("1\n", std::fs::read_to_string(year_dir.join("day1.in")).unwrap());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    pub fn test_input_metadata() {
+        let input = Input::new("abc".to_string(), UNIX_EPOCH, Source::Cache);
+
+        assert_eq!(3, input.byte_len);
+        assert_eq!("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad", input.sha256);
+    }
+
+    #[test]
+    pub fn test_validate_input() {
+        assert!(validate_input("3   4\n4   3\n").is_ok());
+        assert!(validate_input("").is_err());
+        assert!(validate_input("\n").is_err());
+        assert!(validate_input("3   4\n4   3").is_ok());
+        assert!(validate_input("<!DOCTYPE html>\n<html lang=\"en-us\">\n</html>\n").is_err());
+        assert!(validate_input("Please don't repeatedly request this endpoint before it unlocks! The calendar countdown is synchronized with the server time; the link will be enabled on the calendar the instant this puzzle becomes available.\n").is_err());
     }
 }
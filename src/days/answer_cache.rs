@@ -0,0 +1,158 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use eyre::WrapErr;
+use serde::{Deserialize, Serialize};
+
+use super::{Day, Input, Source};
+use super::harness::DayResult;
+use super::solver::Answer;
+
+/// The answers of earlier runs, stored in `.aoc-cache/answers.json` in the crate root. An entry is
+/// only used while both the input and the crate version it was computed with are unchanged.
+///
+/// ```json
+/// {
+///   "2024": {
+///     "day1": {
+///       "sha256": "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad",
+///       "version": "0.1.0",
+///       "part1": "1530215",
+///       "part2": "26800609"
+///     }
+///   }
+/// }
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct AnswerCache {
+    years: BTreeMap<String, BTreeMap<String, CachedAnswers>>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct CachedAnswers {
+    /// The hex encoded SHA-256 hash of the input the answers were computed from
+    pub sha256: String,
+    /// The crate version the answers were computed with
+    pub version: String,
+    pub part1: String,
+    pub part2: String,
+}
+
+impl AnswerCache {
+    pub fn file() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join(".aoc-cache").join("answers.json")
+    }
+
+    pub fn load() -> eyre::Result<Self> {
+        let file = Self::file();
+        match std::fs::read_to_string(&file) {
+            Ok(cache) => serde_json::from_str(&cache)
+                .wrap_err(format!("Failed to parse {}", file.display())),
+            Err(_) => Ok(Self::default()),
+        }
+    }
+
+    pub fn save(&self) -> eyre::Result<()> {
+        let file = Self::file();
+        if let Some(dir) = file.parent() {
+            super::cache_dir(dir)?;
+        }
+        std::fs::write(&file, serde_json::to_string_pretty(self)?)
+            .wrap_err(format!("Failed to write {}", file.display()))
+    }
+
+    /// The answers for `input`, if they were computed from the same input by this version.
+    pub fn get(&self, year: u16, day: Day, input: &Input) -> Option<&CachedAnswers> {
+        self.years.get(&year.to_string())?
+            .get(&format!("day{}", *day))
+            .filter(|answers| answers.sha256 == input.sha256 && answers.version == env!("CARGO_PKG_VERSION"))
+    }
+
+    pub fn record(&mut self, year: u16, day: Day, input: &Input, part1: &Answer, part2: &Answer) {
+        self.years.entry(year.to_string()).or_default()
+            .insert(format!("day{}", *day), CachedAnswers {
+                sha256: input.sha256.clone(),
+                version: env!("CARGO_PKG_VERSION").to_string(),
+                part1: part1.to_string(),
+                part2: part2.to_string(),
+            });
+    }
+}
+
+static CACHE: OnceLock<Mutex<AnswerCache>> = OnceLock::new();
+static FORCE: AtomicBool = AtomicBool::new(false);
+
+/// Makes the runs of the days answer from and record into `cache`, see [`save`].
+pub fn use_cache(cache: AnswerCache) {
+    CACHE.set(Mutex::new(cache))
+        .expect("The answer cache can only be loaded once");
+}
+
+/// Makes the runs solve every day again, still recording their answers in the cache in use.
+pub fn force(enabled: bool) {
+    FORCE.store(enabled, Ordering::Relaxed);
+}
+
+/// The cached result for a run of `day` on `input`, if the cache is in use and up to date.
+pub fn cached_result(day: Day, input: &Input) -> Option<DayResult> {
+    if input.source == Source::Example || FORCE.load(Ordering::Relaxed) {
+        return None;
+    }
+    let cache = CACHE.get()?.lock().unwrap();
+    let answers = cache.get(super::YEAR, day, input)?;
+    Some(DayResult {
+        day,
+        source: input.source,
+        parse: Duration::ZERO,
+        part1: (Answer::Text(answers.part1.clone()), Duration::ZERO),
+        part2: (Answer::Text(answers.part2.clone()), Duration::ZERO),
+        cached: true,
+        expected: None,
+    })
+}
+
+/// Records the answers of a run on `input`, if the cache is in use.
+pub fn record(input: &Input, result: &DayResult) {
+    if input.source == Source::Example {
+        return;
+    }
+    if let Some(cache) = CACHE.get() {
+        cache.lock().unwrap()
+            .record(super::YEAR, result.day, input, &result.part1.0, &result.part2.0);
+    }
+}
+
+/// Writes the cache in use back to its file.
+pub fn save() -> eyre::Result<()> {
+    match CACHE.get() {
+        Some(cache) => cache.lock().unwrap().save(),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::SystemTime;
+
+    use super::*;
+
+    #[test]
+    pub fn test_get() {
+        let input = Input::new("1 2\n".to_string(), SystemTime::now(), Source::Cache);
+        let changed = Input::new("1 3\n".to_string(), SystemTime::now(), Source::Cache);
+        let mut cache = AnswerCache::default();
+        cache.record(2024, Day(1), &input, &Answer::Unsigned(1), &Answer::Unsigned(2));
+
+        let answers = cache.get(2024, Day(1), &input).unwrap();
+        assert_eq!(("1", "2"), (answers.part1.as_str(), answers.part2.as_str()));
+        assert_eq!(None, cache.get(2024, Day(1), &changed));
+        assert_eq!(None, cache.get(2024, Day(2), &input));
+
+        cache.years.get_mut("2024").unwrap().get_mut("day1").unwrap().version = "0.0.0".to_string();
+        assert_eq!(None, cache.get(2024, Day(1), &input));
+    }
+}
@@ -0,0 +1,144 @@
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+use crate::days::Day;
+
+/// A named strategy to solve a day with, e.g. `bfs-per-end` or `dp-single-pass`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Approach {
+    pub name: &'static str,
+    pub description: &'static str,
+}
+
+/// The approaches a day was solved with over time, oldest first. All of them are compiled in, the
+/// `active` one is used unless another one is selected with [`use_strategy`] or was found to be
+/// faster by [`ApproachHistory::calibrate`].
+#[derive(Debug, Copy, Clone)]
+pub struct ApproachHistory {
+    pub day: Day,
+    pub approaches: &'static [Approach],
+    pub active: &'static str,
+    /// Parses the input and solves the parts that differ between the approaches.
+    pub solve: fn(&str) -> eyre::Result<()>,
+}
+
+static STRATEGY: OnceLock<String> = OnceLock::new();
+static CALIBRATION: OnceLock<HashMap<String, String>> = OnceLock::new();
+
+thread_local! {
+    static OVERRIDE: Cell<Option<&'static str>> = const { Cell::new(None) };
+}
+
+/// Selects the approach named `strategy` for every day that has one.
+pub fn use_strategy(strategy: String) {
+    STRATEGY.set(strategy)
+        .expect("The strategy can only be selected once");
+}
+
+/// Uses the fastest approaches recorded by `calibrate`, keyed by day number, where no strategy is
+/// selected explicitly.
+pub fn use_calibration(calibration: HashMap<String, String>) {
+    CALIBRATION.set(calibration)
+        .expect("The calibration can only be loaded once");
+}
+
+/// How long solving with an approach took during calibration.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Timing {
+    pub approach: &'static Approach,
+    pub duration: Duration,
+}
+
+impl ApproachHistory {
+    pub fn get(&self, name: &str) -> Option<&'static Approach> {
+        self.approaches.iter()
+            .find(|approach| approach.name == name)
+    }
+
+    /// The name of the approach to solve the day with.
+    pub fn selected(&self) -> &'static str {
+        OVERRIDE.get()
+            .or_else(|| STRATEGY.get().map(String::as_str))
+            .or_else(|| CALIBRATION.get()?.get(&self.day.0.to_string()).map(String::as_str))
+            .and_then(|strategy| self.get(strategy))
+            .map(|approach| approach.name)
+            .unwrap_or(self.active)
+    }
+
+    /// Solves `raw_input` with every approach, taking the best of `runs` runs each, fastest first.
+    pub fn calibrate(&self, raw_input: &str, runs: usize) -> eyre::Result<Vec<Timing>> {
+        let mut timings = self.approaches.iter()
+            .map(|approach| {
+                let previous = OVERRIDE.replace(Some(approach.name));
+                let duration = (0..runs.max(1))
+                    .map(|_| {
+                        let start = Instant::now();
+                        (self.solve)(raw_input)?;
+                        Ok(start.elapsed())
+                    })
+                    .collect::<eyre::Result<Vec<_>>>();
+                OVERRIDE.set(previous);
+
+                Ok(Timing {
+                    approach,
+                    duration: duration?.into_iter().min().unwrap_or_default(),
+                })
+            })
+            .collect::<eyre::Result<Vec<_>>>()?;
+        timings.sort_by_key(|timing| timing.duration);
+        Ok(timings)
+    }
+}
+
+impl Display for ApproachHistory {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let selected = self.selected();
+        for (index, approach) in self.approaches.iter().enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+            let marker = if approach.name == selected { '*' } else { ' ' };
+            write!(f, "{marker} {}: {}", approach.name, approach.description)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const HISTORY: ApproachHistory = ApproachHistory {
+        day: Day(1),
+        approaches: &[
+            Approach { name: "brute-force", description: "Try everything" },
+            Approach { name: "dp", description: "Remember everything" },
+        ],
+        active: "dp",
+        solve,
+    };
+
+    fn solve(_: &str) -> eyre::Result<()> {
+        if HISTORY.selected() == "brute-force" {
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        Ok(())
+    }
+
+    #[test]
+    pub fn test_display() {
+        assert_eq!("  brute-force: Try everything\n* dp: Remember everything", HISTORY.to_string());
+    }
+
+    #[test]
+    pub fn test_calibrate() {
+        let timings = HISTORY.calibrate("", 2).unwrap();
+
+        assert_eq!(vec!["dp", "brute-force"], timings.iter().map(|timing| timing.approach.name).collect::<Vec<_>>());
+        assert!(timings[1].duration >= Duration::from_millis(10));
+        assert_eq!("dp", HISTORY.selected());
+    }
+}
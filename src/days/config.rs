@@ -0,0 +1,157 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use eyre::{eyre, WrapErr};
+use serde::{Deserialize, Serialize};
+
+/// The directory holding the user's AoC configuration, `$XDG_CONFIG_HOME/aoc` or `~/.config/aoc`.
+pub fn config_dir() -> Option<PathBuf> {
+    std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+        .map(|config| config.join("aoc"))
+}
+
+/// The contents of `config.toml` in the [`config_dir`].
+///
+/// ```toml
+/// # Encrypt the cached inputs with the session token unless AOC_INPUT_KEY is set
+/// encrypt_inputs = true
+/// # Where to cache the inputs unless AOC_INPUT_DIR is set, `input/` in the crate root by default
+/// input_dir = "/home/me/aoc/input"
+///
+/// [sessions.personal]
+/// session = "53616c746564..."
+///
+/// [sessions.work]
+/// session = "53616c746564..."
+///
+/// # The fastest approaches by day, recorded by `calibrate`
+/// [strategies]
+/// 14 = "no-overlaps"
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Config {
+    #[serde(default)]
+    pub encrypt_inputs: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input_dir: Option<PathBuf>,
+    #[serde(default)]
+    pub sessions: HashMap<String, Profile>,
+    #[serde(default)]
+    pub strategies: HashMap<String, String>,
+}
+
+/// A named AoC account.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Profile {
+    pub session: String,
+}
+
+fn config_file() -> Option<PathBuf> {
+    config_dir().map(|config| config.join("config.toml"))
+}
+
+impl Config {
+    pub fn load() -> eyre::Result<Self> {
+        let Some(config_file) = config_file() else {
+            return Ok(Self::default());
+        };
+        if !config_file.exists() {
+            return Ok(Self::default());
+        }
+
+        let config = std::fs::read_to_string(&config_file)
+            .wrap_err(format!("Failed to read {}", config_file.display()))?;
+        toml::from_str(&config)
+            .wrap_err(format!("Failed to parse {}", config_file.display()))
+    }
+
+    pub fn save(&self) -> eyre::Result<()> {
+        let config_file = config_file()
+            .ok_or(eyre!("Neither XDG_CONFIG_HOME nor HOME is set"))?;
+        if let Some(config_dir) = config_file.parent() {
+            super::cache_dir(config_dir)?;
+        }
+
+        std::fs::write(&config_file, toml::to_string(self)?)
+            .wrap_err(format!("Failed to write {}", config_file.display()))
+    }
+}
+
+static PROFILE: OnceLock<String> = OnceLock::new();
+
+/// Selects the session profile used for all requests. Without one `AOC_PROFILE` is used if set.
+pub fn use_profile(profile: String) {
+    PROFILE.set(profile)
+        .expect("The profile can only be selected once");
+}
+
+pub fn profile() -> Option<String> {
+    PROFILE.get().cloned()
+        .or_else(|| dotenvy::var("AOC_PROFILE").ok())
+}
+
+/// Where inputs are cached: `AOC_INPUT_DIR`, the `input_dir` of the [`Config`] or `input/` in the
+/// crate root. Every profile gets its own subdirectory as each account has its own inputs.
+pub fn input_dir() -> eyre::Result<PathBuf> {
+    let input_dir = match dotenvy::var("AOC_INPUT_DIR") {
+        Ok(input_dir) => PathBuf::from(input_dir),
+        Err(_) => Config::load()?.input_dir
+            .unwrap_or_else(|| Path::new(env!("CARGO_MANIFEST_DIR")).join("input")),
+    };
+
+    Ok(match profile() {
+        Some(profile) => input_dir.join(profile),
+        None => input_dir,
+    })
+}
+
+/// Looks up the session cookie of the selected [`profile`] in the [`Config`]. Without a profile the
+/// `AOC_SESSION` variable (which may also be set in a `.env` file) is used, falling back to the
+/// `session` file in the [`config_dir`].
+pub fn session() -> eyre::Result<String> {
+    if let Some(profile) = profile() {
+        return Config::load()?.sessions.remove(&profile)
+            .map(|profile| profile.session.trim().to_string())
+            .ok_or(eyre!("No session configured for profile {profile}. Add it to the [sessions.{profile}] table in the config.toml of your AoC config directory"));
+    }
+
+    if let Ok(session) = dotenvy::var("AOC_SESSION") {
+        return Ok(session.trim().to_string());
+    }
+
+    let session_file = config_dir().map(|config| config.join("session"));
+    if let Some(session) = session_file.as_ref().and_then(|file| std::fs::read_to_string(file).ok()) {
+        return Ok(session.trim().to_string());
+    }
+
+    let session_file = session_file
+        .map(|file| file.display().to_string())
+        .unwrap_or("~/.config/aoc/session".to_string());
+    Err(eyre!("No AoC session found. Set AOC_SESSION to your session cookie (in the environment or a .env file) or create {session_file} containing it"))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    pub fn test_parse_config() {
+        let config: Config = toml::from_str(r#"
+            [sessions.personal]
+            session = "abc"
+
+            [sessions.work]
+            session = "def"
+
+            [strategies]
+            14 = "no-overlaps"
+        "#).unwrap();
+
+        assert_eq!("abc", config.sessions["personal"].session);
+        assert_eq!("def", config.sessions["work"].session);
+        assert_eq!("no-overlaps", config.strategies["14"]);
+    }
+}
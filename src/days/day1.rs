@@ -1,10 +1,6 @@
 use std::str::FromStr;
-use std::time::SystemTime;
 use eyre::eyre;
-use tracing::{debug, info, Instrument, Level, span, trace};
-use crate::days::Day;
-
-pub const DAY: Day = Day(1);
+use crate::days::solver::{register_day, Answer, Solver};
 
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub struct Input {
@@ -38,7 +34,7 @@ impl FromStr for Input {
     }
 }
 
-pub fn process_part1(input: &Input) -> eyre::Result<String> {
+pub fn process_part1(input: &Input) -> eyre::Result<Answer> {
     let mut column1 = input.column1.clone();
     let mut column2 = input.column2.clone();
 
@@ -50,44 +46,41 @@ pub fn process_part1(input: &Input) -> eyre::Result<String> {
         .map(|(column1, column2)| column1.abs_diff(*column2))
         .sum();
 
-    Ok(result.to_string())
+    Ok(result.into())
 }
 
-pub fn process_part2(input: &Input) -> eyre::Result<String> {
+pub fn process_part2(input: &Input) -> eyre::Result<Answer> {
     let result: usize = input.column1.iter()
         .map(|&needle| input.column2.iter()
             .filter(|&&val| needle == val)
             .sum::<usize>())
         .sum();
 
-
-    Ok(result.to_string())
+    Ok(result.into())
 }
 
-pub async fn run() -> eyre::Result<()> {
-    let day_span = span!(Level::ERROR, "", "{}", DAY);
-    async {
-        info!("Running {DAY}");
-
-        let raw_input = super::get_input(DAY).await?;
-        trace!(raw_input);
-
-        let input = raw_input.parse()?;
-        debug!(?input);
-
-        let start1 = SystemTime::now();
-        let result1 = process_part1(&input)?;
-        let end1 = SystemTime::now();
-        let start2 = SystemTime::now();
-        let result2 = process_part2(&input)?;
-        let end2 = SystemTime::now();
-        println!("{DAY} result:");
-        println!("  part 1: {result1} in {:?}", end1.duration_since(start1).unwrap());
-        println!("  part 2: {result2} in {:?}", end2.duration_since(start2).unwrap());
-        Ok(())
+pub struct Solution;
+
+impl Solver for Solution {
+    type Parsed = Input;
+
+    fn parse(input: &str) -> eyre::Result<Self::Parsed> {
+        input.parse()
+    }
+
+    fn part1(input: &Self::Parsed) -> eyre::Result<Answer> {
+        process_part1(input)
+    }
+
+    fn part2(input: &Self::Parsed) -> eyre::Result<Answer> {
+        process_part2(input)
     }
-        .instrument(day_span.or_current())
-        .await
+}
+
+register_day! {
+    day: 1,
+    status: Implemented,
+    solver: Solution,
 }
 
 #[cfg(test)]
@@ -105,9 +98,9 @@ mod test {
 "#.parse().unwrap();
 
         let result1 = process_part1(&input).unwrap();
-        assert_eq!("11", result1);
+        assert_eq!(Answer::Unsigned(11), result1);
 
         let result2 = process_part2(&input).unwrap();
-        assert_eq!("31", result2);
+        assert_eq!(Answer::Unsigned(31), result2);
     }
 }
@@ -8,11 +8,11 @@ use std::time::SystemTime;
 use eyre::anyhow;
 use owo_colors::{CssColors, DynColor, OwoColorize};
 use tracing::{debug, info, Instrument, Level, span, trace};
-use crate::days::Day;
+use crate::days::answer_cache;
+use crate::days::harness::DayResult;
+use crate::days::solver::{register_day, Answer, Solver};
 use crate::days::util::{Coordinate, ParsedGrid};
 
-pub const DAY: Day = Day(10);
-
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub struct MapPosition<'map> {
     map: &'map ParsedGrid<Height>,
@@ -32,9 +32,9 @@ impl MapPosition<'_> {
         *self.height() == Height::MAX
     }
 
-    pub fn offset(&self, offset: Coordinate) -> Result<Self, ()> {
+    pub fn offset(&self, offset: Coordinate) -> Option<Self> {
         let position = self.map.offset_index(self.position, offset)?;
-        Ok(Self {
+        Some(Self {
             map: self.map,
             position,
         })
@@ -177,20 +177,16 @@ impl Input {
     }
 
     pub fn trail_ends(&self) -> impl Iterator<Item = MapPosition> {
-        self.map.as_slice().iter()
-            .enumerate()
-            .filter(|(_, height)| ***height == 9)
-            .map(|(position, _)| MapPosition {
+        self.map.positions(|height| *height == Height::MAX)
+            .map(|position| MapPosition {
                 map: &self.map,
                 position,
             })
     }
 
     pub fn trail_heads(&self) -> impl Iterator<Item = MapPosition> {
-        self.map.as_slice().iter()
-            .enumerate()
-            .filter(|(_, height)| ***height == 0)
-            .map(|(position, _)| MapPosition {
+        self.map.positions(|height| *height == Height::MIN)
+            .map(|position| MapPosition {
                 map: &self.map,
                 position,
             })
@@ -204,37 +200,52 @@ impl Input {
     }
 }
 
-pub async fn process_part1(input: Arc<Input>) -> eyre::Result<usize> {
-    let scores = Arc::new((0..input.map.as_slice().len())
+/// Adds one to the score of every position the trail down from `trail_end` passes, counting each
+/// position only once if `distinct`, or once per path reaching it otherwise.
+fn walk_down(trail_end: MapPosition, scores: &[AtomicUsize], distinct: bool) {
+    let mut positions = VecDeque::from_iter(std::iter::once(trail_end));
+    let mut seen_position = HashSet::new();
+    while let Some(current_position) = positions.pop_front() {
+        if distinct && !seen_position.insert(current_position.clone()) {
+            continue;
+        }
+        scores[current_position.position].fetch_add(1, Ordering::Relaxed);
+        if *current_position.height() == Height::MIN {
+            continue;
+        }
+        let new_height = current_position.height().0 - 1;
+
+        for direction in Coordinate::CARDINALITIES {
+            if let Some(new_position) = current_position.offset(direction) {
+                if new_position.height().0 == new_height {
+                    positions.push_back(new_position);
+                }
+            }
+        }
+    }
+}
+
+fn empty_scores(input: &Input) -> Vec<AtomicUsize> {
+    (0..input.map.as_slice().len())
         .map(|_| AtomicUsize::new(0))
-        .collect::<Vec<_>>());
+        .collect()
+}
+
+fn trailhead_scores(input: &Input, scores: &[AtomicUsize]) -> usize {
+    input.trail_heads()
+        .map(|trail_head| scores[trail_head.position].load(Ordering::Relaxed))
+        .sum()
+}
+
+async fn process(input: Arc<Input>, distinct: bool) -> eyre::Result<usize> {
+    let scores = Arc::new(empty_scores(&input));
 
     let handles = input.trail_ends().map(|trail_end| {
         let input = input.clone();
         let scores = scores.clone();
         let position = trail_end.position;
         tokio::spawn(async move {
-            let trail_end = input.position(position);
-            let mut positions = VecDeque::from_iter(std::iter::once(trail_end));
-            let mut seen_position = HashSet::new();
-            while let Some(current_position) = positions.pop_front() {
-                if !seen_position.insert(current_position.clone()) {
-                    continue;
-                }
-                scores[current_position.position].fetch_add(1, Ordering::Relaxed);
-                if *current_position.height() == Height::MIN {
-                    continue;
-                }
-                let new_height = current_position.height().0 - 1;
-
-                for direction in Coordinate::CARDINALITIES {
-                    if let Ok(new_position) = current_position.offset(direction) {
-                        if new_position.height().0 == new_height {
-                            positions.push_back(new_position);
-                        }
-                    }
-                }
-            }
+            walk_down(input.position(position), &scores, distinct);
         })
     }).collect::<Vec<_>>();
 
@@ -242,84 +253,95 @@ pub async fn process_part1(input: Arc<Input>) -> eyre::Result<usize> {
         handle.await?;
     }
 
-    let scores = scores.iter()
-        .map(|score| score.load(Ordering::Relaxed))
-        .collect::<Vec<_>>();
+    Ok(trailhead_scores(&input, &scores))
+}
 
-    let result = input.trail_heads()
-        .map(|trail_head| scores[trail_head.position]).
-        sum();
+pub async fn process_part1(input: Arc<Input>) -> eyre::Result<Answer> {
+    process(input, true).await.map(Answer::from)
+}
 
-    Ok(result)
+pub async fn process_part2(input: &Input) -> eyre::Result<Answer> {
+    process(Arc::new(input.clone()), false).await.map(Answer::from)
 }
 
-pub async fn process_part2(input: &Input) -> eyre::Result<usize> {
-    let scores = Arc::new((0..input.map.as_slice().len())
-        .map(|_| AtomicUsize::new(0))
-        .collect::<Vec<_>>());
+/// Walks down from the trail ends one after another, unlike [`process_part1`] and
+/// [`process_part2`], which spawn a task per trail end.
+pub struct Solution;
 
-    let handles = input.trail_ends().map(|trail_end| {
-        let input = input.clone();
-        let scores = scores.clone();
-        let position = trail_end.position;
-        tokio::spawn(async move {
-            let trail_end = input.position(position);
-            let mut positions = VecDeque::from_iter(std::iter::once(trail_end));
-            while let Some(current_position) = positions.pop_front() {
-                scores[current_position.position].fetch_add(1, Ordering::Relaxed);
-                if *current_position.height() == Height::MIN {
-                    continue;
-                }
-                let new_height = current_position.height().0 - 1;
-
-                for direction in Coordinate::CARDINALITIES {
-                    if let Ok(new_position) = current_position.offset(direction) {
-                        if new_position.height().0 == new_height {
-                            positions.push_back(new_position);
-                        }
-                    }
-                }
-            }
-        })
-    }).collect::<Vec<_>>();
+impl Solution {
+    fn score(input: &Input, distinct: bool) -> usize {
+        let scores = empty_scores(input);
+        for trail_end in input.trail_ends() {
+            walk_down(trail_end, &scores, distinct);
+        }
+        trailhead_scores(input, &scores)
+    }
+}
 
-    for handle in handles {
-        handle.await?;
+impl Solver for Solution {
+    type Parsed = Input;
+
+    fn parse(input: &str) -> eyre::Result<Self::Parsed> {
+        input.parse()
     }
 
-    let scores = scores.iter()
-        .map(|score| score.load(Ordering::Relaxed))
-        .collect::<Vec<_>>();
+    fn part1(input: &Self::Parsed) -> eyre::Result<Answer> {
+        Ok(Self::score(input, true).into())
+    }
 
-    let result = input.trail_heads()
-        .map(|trail_head| scores[trail_head.position]).
-        sum();
+    fn part2(input: &Self::Parsed) -> eyre::Result<Answer> {
+        Ok(Self::score(input, false).into())
+    }
+}
 
-    Ok(result)
+register_day! {
+    day: 10,
+    status: Implemented,
+    solver: Solution,
+    run: custom,
+    example: test::EXAMPLE => ("36", "81"),
 }
 
-pub async fn run() -> eyre::Result<()> {
-    let day_span = span!(Level::ERROR, "", "{}", DAY);
+pub async fn run() -> eyre::Result<DayResult> {
+    let day_span = span!(Level::ERROR, "day", day = *DAY);
     async {
         info!("Running {DAY}");
 
-        let raw_input = super::get_input(DAY).await?;
+        let puzzle_input = super::get_input(super::YEAR, DAY).await?;
+        let raw_input = &puzzle_input.body;
         trace!(raw_input);
+        if let Some(result) = answer_cache::cached_result(DAY, &puzzle_input) {
+            return Ok(result);
+        }
 
-        let input: Input = raw_input.parse()?;
+        let start_parse = SystemTime::now();
+        let input: Input = span!(Level::ERROR, "parse", day = *DAY, phase = "parse")
+            .in_scope(|| raw_input.parse())?;
+        let end_parse = SystemTime::now();
         debug!(?input);
         let input = Arc::new(input);
 
         let start1 = SystemTime::now();
-        let result1 = process_part1(input.clone()).await?;
+        let result1 = process_part1(input.clone())
+            .instrument(span!(Level::ERROR, "solve", day = *DAY, part = 1, phase = "solve"))
+            .await?;
         let end1 = SystemTime::now();
         let start2 = SystemTime::now();
-        let result2 = process_part2(&input).await?;
+        let result2 = process_part2(&input)
+            .instrument(span!(Level::ERROR, "solve", day = *DAY, part = 2, phase = "solve"))
+            .await?;
         let end2 = SystemTime::now();
-        println!("{DAY} result:");
-        println!("  part 1: {result1} in {:?}", end1.duration_since(start1).unwrap());
-        println!("  part 2: {result2} in {:?}", end2.duration_since(start2).unwrap());
-        Ok(())
+        let result = DayResult {
+            day: DAY,
+            source: puzzle_input.source,
+            parse: end_parse.duration_since(start_parse).unwrap(),
+            part1: (result1, end1.duration_since(start1).unwrap()),
+            part2: (result2, end2.duration_since(start2).unwrap()),
+            cached: false,
+            expected: puzzle_input.expected.clone(),
+        };
+        answer_cache::record(&puzzle_input, &result);
+        Ok(result)
     }
         .instrument(day_span.or_current())
         .await
@@ -329,8 +351,7 @@ pub async fn run() -> eyre::Result<()> {
 mod test {
     use super::*;
 
-    fn example_input() -> Input {
-        r"89010123
+    pub const EXAMPLE: &str = r"89010123
           78121874
           87430965
           96549874
@@ -338,7 +359,10 @@ mod test {
           32019012
           01329801
           10456732
-          ".parse().unwrap()
+          ";
+
+    fn example_input() -> Input {
+        EXAMPLE.parse().unwrap()
     }
 
     #[tokio::test]
@@ -347,7 +371,7 @@ mod test {
         // println!("{input:?}");
 
         let result = process_part1(Arc::new(input)).await.unwrap();
-        assert_eq!(36, result);
+        assert_eq!(Answer::Unsigned(36), result);
     }
 
     #[tokio::test]
@@ -355,6 +379,6 @@ mod test {
         let input = example_input();
 
         let result = process_part2(&input).await.unwrap();
-        assert_eq!(81, result);
+        assert_eq!(Answer::Unsigned(81), result);
     }
 }
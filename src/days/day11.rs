@@ -1,12 +1,7 @@
-use std::collections::HashMap;
 use std::str::FromStr;
-use std::time::SystemTime;
 use eyre::eyre;
-use itertools::Itertools;
-use tracing::{debug, info, Instrument, Level, span, trace};
-use crate::days::Day;
-
-pub const DAY: Day = Day(11);
+use crate::days::solver::{register_day, Answer, Solver};
+use crate::days::util::counter::Counter;
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
 pub struct Stone {
@@ -70,7 +65,7 @@ impl FromStr for Input {
     }
 }
 
-pub fn process_part1(input: &Input) -> eyre::Result<usize> {
+pub fn process_part1(input: &Input) -> eyre::Result<Answer> {
     let mut stones = input.stones.clone();
 
     let mut acc = Vec::new();
@@ -84,59 +79,51 @@ pub fn process_part1(input: &Input) -> eyre::Result<usize> {
         stones.append(&mut acc);
     }
 
-    Ok(stones.len())
+    Ok(stones.len().into())
 }
 
-pub fn process_part2(input: &Input) -> eyre::Result<usize> {
-    let mut stones: HashMap<Stone, usize> = input.stones.iter().cloned().counts();
-    let mut new_stones: HashMap<Stone, usize> = HashMap::with_capacity(stones.len());
+pub fn process_part2(input: &Input) -> eyre::Result<Answer> {
+    let mut stones: Counter<Stone> = input.stones.iter().copied().collect();
+    let mut new_stones = Counter::with_capacity(stones.len());
 
     for _ in 0..75 {
-        for (stone, &count) in &stones {
-            let mut stone = *stone;
+        for (&stone, count) in stones.iter() {
+            let mut stone = stone;
             if let Some(new_stone) = stone.evolve() {
-                insert_stone_count(&mut new_stones, count, new_stone);
+                new_stones.add(new_stone, count);
             }
-            insert_stone_count(&mut new_stones, count, stone);
+            new_stones.add(stone, count);
         }
 
         std::mem::swap(&mut stones, &mut new_stones);
         new_stones.clear();
     }
 
-    Ok(stones.values().sum::<usize>())
+    Ok(stones.total().into())
 }
 
-fn insert_stone_count(new_stones: &mut HashMap<Stone, usize>, count: usize, stone: Stone) {
-    new_stones.entry(stone)
-        .and_modify(|saved_count| *saved_count += count)
-        .or_insert(count);
-}
+pub struct Solution;
+
+impl Solver for Solution {
+    type Parsed = Input;
+
+    fn parse(input: &str) -> eyre::Result<Self::Parsed> {
+        input.parse()
+    }
 
-pub async fn run() -> eyre::Result<()> {
-    let day_span = span!(Level::ERROR, "", "{}", DAY);
-    async {
-        info!("Running {DAY}");
-
-        let raw_input = super::get_input(DAY).await?;
-        trace!(raw_input);
-
-        let input = raw_input.parse()?;
-        debug!(?input);
-
-        let start1 = SystemTime::now();
-        let result1 = process_part1(&input)?;
-        let end1 = SystemTime::now();
-        let start2 = SystemTime::now();
-        let result2 = process_part2(&input)?;
-        let end2 = SystemTime::now();
-        println!("{DAY} result:");
-        println!("  part 1: {result1} in {:?}", end1.duration_since(start1).unwrap());
-        println!("  part 2: {result2} in {:?}", end2.duration_since(start2).unwrap());
-        Ok(())
+    fn part1(input: &Self::Parsed) -> eyre::Result<Answer> {
+        process_part1(input)
     }
-        .instrument(day_span.or_current())
-        .await
+
+    fn part2(input: &Self::Parsed) -> eyre::Result<Answer> {
+        process_part2(input)
+    }
+}
+
+register_day! {
+    day: 11,
+    status: Implemented,
+    solver: Solution,
 }
 
 #[cfg(test)]
@@ -152,7 +139,7 @@ mod test {
         let input = example_input();
 
         let result = process_part1(&input).unwrap();
-        assert_eq!(55312, result);
+        assert_eq!(Answer::Unsigned(55312), result);
     }
 
     #[test]
@@ -160,6 +147,6 @@ mod test {
         let input = example_input();
 
         let result = process_part2(&input).unwrap();
-        assert_eq!(65601038650482, result);
+        assert_eq!(Answer::Unsigned(65601038650482), result);
     }
 }
@@ -1,156 +1,144 @@
-use std::collections::VecDeque;
 use std::str::FromStr;
-use std::time::SystemTime;
 use itertools::Itertools;
-use tracing::{debug, info, Instrument, Level, span, trace};
-use crate::days::Day;
+use crate::days::solver::{register_day, Answer, Solver};
 use crate::days::util::{Coordinate, Grid};
 
-pub const DAY: Day = Day(12);
-
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub struct Input {
     grid: Grid,
 }
 
+/// Surrounds the garden, so every plot has all its neighbours
+const BORDER: char = '.';
+
 impl FromStr for Input {
     type Err = eyre::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let grid = s.parse()?;
+        let grid: Grid = s.parse()?;
 
         Ok(Self {
-            grid,
+            grid: grid.with_border(BORDER),
         })
     }
 }
 
-pub fn process_part1(input: &Input) -> eyre::Result<usize> {
-    let mut visited = vec![false; input.grid.as_slice().len()];
-    let mut search_positions = VecDeque::new();
-    let mut cost = 0;
+/// The plots of every region, a group of touching plots growing the same plant.
+fn regions(grid: &Grid) -> impl Iterator<Item = Vec<usize>> + '_ {
+    grid.regions().into_iter()
+        .filter(|region| grid.as_slice()[region[0]] != BORDER)
+}
 
-    for (position, plot) in input.grid.as_slice().iter().enumerate() {
-        if visited[position] {
-            continue;
-        }
-        // for row in visited.chunks(input.grid.width()) {
-        //     println!("{}", row.iter().map(|visited| if *visited { 'X' } else { '0' }).collect::<String>());
-        // }
-
-        let mut area = 0;
-        let mut perimeter = 0;
-        search_positions.push_back(position);
-        visited[position] = true;
-        while let Some(search_position) = search_positions.pop_front() {
-            // println!("adding {search_position} to area");
-            area += 1;
-
-            for direction in Coordinate::CARDINALITIES {
-                // print!("searching {search_position} in {direction:?}: ");
-                if let Ok(new_position) = input.grid.offset_index(search_position, direction) {
-                    if visited[new_position] || input.grid.as_slice()[new_position] != *plot {
-                        if input.grid.as_slice()[new_position] != *plot {
-                            perimeter += 1;
-                            // println!("different plant type");
-                        } else {
-                            // println!("already visited");
-                        }
-                        continue;
-                    }
-
-                    visited[new_position] = true;
-                    search_positions.push_back(new_position);
-                    // println!("new plot");
-                } else {
-                    perimeter += 1;
-                    // println!("outside");
-                }
-            }
-        }
-        // println!("area: {area}, perimeter: {perimeter}");
-        cost += area * perimeter;
-    }
+/// How many sides of the plot at `position` need a fence, as they don't border the same plant.
+fn fences(grid: &Grid, position: usize) -> usize {
+    let plot = grid.as_slice()[position];
+    let same_plant = grid.neighbors4(position)
+        .filter(|&(neighbour, _)| grid.as_slice()[neighbour] == plot)
+        .count();
+    4 - same_plant
+}
 
-    Ok(cost)
+/// How many corners of its region the plot at `position` lies on. A region has as many sides as
+/// corners.
+fn corners(grid: &Grid, position: usize) -> usize {
+    let coordinate = grid.index_to_coordinate(position);
+    let plot = grid[coordinate];
+    Coordinate::CARDINALITIES.iter()
+        .chain(std::iter::once(&Coordinate::CARDINALITIES[0]))
+        .tuple_windows()
+        .filter(|&(&direction1, &direction2)| {
+            let direction1_inside = grid[coordinate + direction1] == plot;
+            let direction2_inside = grid[coordinate + direction2] == plot;
+            let direction3_inside = grid[coordinate + direction1 + direction2] == plot;
+
+            // an outer corner, or an inner one with the diagonal belonging to another region
+            !direction1_inside && !direction2_inside || direction1_inside && direction2_inside && !direction3_inside
+        })
+        .count()
 }
 
-pub fn process_part2(input: &Input) -> eyre::Result<usize> {
-    let mut visited = vec![false; input.grid.as_slice().len()];
-    let mut search_positions = VecDeque::new();
-    let mut cost = 0;
+/// The length of the fence around `region`.
+fn perimeter(grid: &Grid, region: &[usize]) -> usize {
+    region.iter().map(|&position| fences(grid, position)).sum()
+}
 
-    for (position, plot) in input.grid.as_slice().iter().enumerate() {
-        if visited[position] {
-            continue;
-        }
+/// The number of straight sides of the fence around `region`.
+fn sides(grid: &Grid, region: &[usize]) -> usize {
+    region.iter().map(|&position| corners(grid, position)).sum()
+}
 
-        let mut area = 0;
-        let mut perimeter = 0;
-        search_positions.push_back(position);
-        while let Some(search_position) = search_positions.pop_front() {
-            if visited[search_position] {
-                continue;
-            }
-            area += 1;
-            visited[search_position] = true;
-
-            let grid = input.grid.as_slice();
-            for (&direction1, &direction2) in Coordinate::CARDINALITIES.iter().chain(std::iter::once(&Coordinate::CARDINALITIES[0])).tuple_windows() {
-                let direction1_inside = input.grid.offset_index(search_position, direction1).is_ok_and(|position| grid[position] == *plot);
-                let direction2_inside = input.grid.offset_index(search_position, direction2).is_ok_and(|position| grid[position] == *plot);
-                let direction3_inside = input.grid.offset_index(search_position, direction1 + direction2).is_ok_and(|position| grid[position] == *plot);
-
-                if !direction1_inside && !direction2_inside {
-                    perimeter += 1;
-                } else if direction1_inside && direction2_inside && !direction3_inside {
-                    perimeter += 1;
-                } else {
-                }
-
-                if let Ok(direction1) = input.grid.offset_index(search_position, direction1) {
-                    if input.grid.as_slice()[direction1] == *plot {
-                        search_positions.push_back(direction1);
-                    }
-                }
-            }
-        }
-        cost += area * perimeter;
-    }
+pub fn process_part1(input: &Input) -> eyre::Result<Answer> {
+    let cost = regions(&input.grid)
+        .map(|region| region.len() * perimeter(&input.grid, &region))
+        .sum::<usize>();
 
-    Ok(cost)
+    Ok(cost.into())
 }
 
-pub async fn run() -> eyre::Result<()> {
-    let day_span = span!(Level::ERROR, "", "{}", DAY);
-    async {
-        info!("Running {DAY}");
-
-        let raw_input = super::get_input(DAY).await?;
-        trace!(raw_input);
-
-        let input = raw_input.parse()?;
-        debug!(?input);
-
-        let start1 = SystemTime::now();
-        let result1 = process_part1(&input)?;
-        let end1 = SystemTime::now();
-        let start2 = SystemTime::now();
-        let result2 = process_part2(&input)?;
-        let end2 = SystemTime::now();
-        println!("{DAY} result:");
-        println!("  part 1: {result1} in {:?}", end1.duration_since(start1).unwrap());
-        println!("  part 2: {result2} in {:?}", end2.duration_since(start2).unwrap());
-        Ok(())
+pub fn process_part2(input: &Input) -> eyre::Result<Answer> {
+    let cost = regions(&input.grid)
+        .map(|region| region.len() * sides(&input.grid, &region))
+        .sum::<usize>();
+
+    Ok(cost.into())
+}
+
+pub struct Solution;
+
+impl Solver for Solution {
+    type Parsed = Input;
+
+    fn parse(input: &str) -> eyre::Result<Self::Parsed> {
+        input.parse()
+    }
+
+    fn part1(input: &Self::Parsed) -> eyre::Result<Answer> {
+        process_part1(input)
+    }
+
+    fn part2(input: &Self::Parsed) -> eyre::Result<Answer> {
+        process_part2(input)
     }
-        .instrument(day_span.or_current())
-        .await
+}
+
+register_day! {
+    day: 12,
+    status: Implemented,
+    solver: Solution,
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
 
+    #[test]
+    pub fn test_region_geometry() {
+        let input: Input = r"AAAAAA
+                            AAABBA
+                            AAABBA
+                            ABBAAA
+                            ABBAAA
+                            AAAAAA
+                            ".parse().unwrap();
+        let grid = &input.grid;
+
+        let (a, b) = regions(grid)
+            .partition::<Vec<_>, _>(|region| grid.as_slice()[region[0]] == 'A');
+        assert_eq!(1, a.len());
+        assert_eq!(2, b.len());
+        assert_eq!(12, sides(grid, &a[0]));
+        assert_eq!(6 * 4 + 2 * 8, perimeter(grid, &a[0]));
+        for region in &b {
+            assert_eq!(4, region.len());
+            assert_eq!(4, sides(grid, region));
+            assert_eq!(8, perimeter(grid, region));
+        }
+        // the plot where both B regions touch diagonally only lies on the corner facing them, the
+        // border moves it from (3, 3) to (4, 4)
+        let between = grid.coordinate_to_index(Coordinate(4, 4)).unwrap();
+        assert_eq!(1, corners(grid, between));
+    }
+
     #[test]
     pub fn test_example1_part1() {
         let input = r"AAAA
@@ -160,7 +148,7 @@ mod test {
                            ".parse().unwrap();
 
         let result = process_part1(&input).unwrap();
-        assert_eq!(140, result);
+        assert_eq!(Answer::Unsigned(140), result);
     }
 
     #[test]
@@ -173,7 +161,7 @@ mod test {
                             ".parse().unwrap();
 
         let result = process_part1(&input).unwrap();
-        assert_eq!(772, result);
+        assert_eq!(Answer::Unsigned(772), result);
     }
 
     #[test]
@@ -191,7 +179,7 @@ mod test {
                             ".parse().unwrap();
 
         let result = process_part1(&input).unwrap();
-        assert_eq!(1930, result);
+        assert_eq!(Answer::Unsigned(1930), result);
     }
 
     #[test]
@@ -203,7 +191,7 @@ mod test {
                            ".parse().unwrap();
 
         let result = process_part2(&input).unwrap();
-        assert_eq!(80, result);
+        assert_eq!(Answer::Unsigned(80), result);
     }
 
     #[test]
@@ -216,7 +204,7 @@ mod test {
                             ".parse().unwrap();
 
         let result = process_part2(&input).unwrap();
-        assert_eq!(236, result);
+        assert_eq!(Answer::Unsigned(236), result);
     }
 
     #[test]
@@ -230,7 +218,7 @@ mod test {
                             ".parse().unwrap();
 
         let result = process_part2(&input).unwrap();
-        assert_eq!(368, result);
+        assert_eq!(Answer::Unsigned(368), result);
     }
 
     #[test]
@@ -248,6 +236,6 @@ mod test {
                             ".parse().unwrap();
 
         let result = process_part2(&input).unwrap();
-        assert_eq!(1206, result);
+        assert_eq!(Answer::Unsigned(1206), result);
     }
 }
@@ -1,12 +1,9 @@
 use std::convert::identity;
 use std::marker::PhantomData;
 use std::str::FromStr;
-use std::time::SystemTime;
 use eyre::eyre;
-use tracing::{debug, info, Instrument, Level, span, trace};
-use crate::days::Day;
-
-pub const DAY: Day = Day(13);
+use crate::days::solver::{register_day, Answer, Solver};
+use crate::days::util::parse::{ints_fixed, labelled, pair};
 
 pub trait ButtonType {
     const COST: usize;
@@ -50,22 +47,12 @@ impl<Type: ButtonType> FromStr for Button<Type> {
     type Err = eyre::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (button_type, distance) = s.strip_prefix("Button ")
-            .ok_or(eyre!("Expected button line to start with \"Button [type]:\""))?
-            .split_once(": ")
-            .ok_or(eyre!("Expected button line to start with \"Button [type]:\""))?;
+        let (button_type, distance) = pair(labelled(s, "Button ")?, ": ")?;
 
         if button_type != Type::LABEL {
             return Err(eyre!("Button type {button_type} should be {}", Type::LABEL))
         }
-        let (x, y) = distance.split_once(", ")
-            .ok_or(eyre!("Failed to split button distance"))?;
-        let x = x.strip_prefix("X+")
-            .ok_or(eyre!("A buttons x distance should be given with \"X+\""))?
-            .parse()?;
-        let y = y.strip_prefix("Y+")
-            .ok_or(eyre!("A buttons y distance should be given with \"Y+\""))?
-            .parse()?;
+        let [x, y] = ints_fixed(distance)?;
 
         Ok(Self { x, y, button_type: PhantomData })
     }
@@ -121,17 +108,7 @@ impl FromStr for ClawMachine {
         let prize = lines.next()
             .ok_or(eyre!("Failed to parse the price"))?;
 
-
-        let location =  prize.strip_prefix("Prize: ")
-            .ok_or(eyre!("Expected \"Prize: \" label in the third row"))?;
-        let (x, y) = location.split_once(", ")
-            .ok_or(eyre!("Failed to split price x and y location"))?;
-        let x = x.strip_prefix("X=")
-            .ok_or(eyre!("price x location should be given with \"X=\""))?
-            .parse()?;
-        let y = y.strip_prefix("Y=")
-            .ok_or(eyre!("price y location should be given with \"Y=\""))?
-            .parse()?;
+        let [x, y] = ints_fixed(labelled(prize, "Prize: ")?)?;
 
         Ok(Self {
             button_a,
@@ -159,16 +136,16 @@ impl FromStr for Input {
     }
 }
 
-pub fn process_part1(input: &Input) -> eyre::Result<usize> {
+pub fn process_part1(input: &Input) -> eyre::Result<Answer> {
     let result = input.claw_machines.iter()
         .map(ClawMachine::cost)
         .filter_map(identity)
         .sum();
 
-    Ok(result)
+    Ok(result.into())
 }
 
-pub fn process_part2(input: &Input) -> eyre::Result<usize> {
+pub fn process_part2(input: &Input) -> eyre::Result<Answer> {
     let claw_machines = input.claw_machines.iter()
         .map(|claw_machine| {
             let mut claw_machine = claw_machine.clone();
@@ -182,33 +159,31 @@ pub fn process_part2(input: &Input) -> eyre::Result<usize> {
         .filter_map(identity)
         .sum();
 
-    Ok(result)
+    Ok(result.into())
 }
 
-pub async fn run() -> eyre::Result<()> {
-    let day_span = span!(Level::ERROR, "", "{}", DAY);
-    async {
-        info!("Running {DAY}");
+pub struct Solution;
 
-        let raw_input = super::get_input(DAY).await?;
-        trace!(raw_input);
+impl Solver for Solution {
+    type Parsed = Input;
 
-        let input = raw_input.parse()?;
-        debug!(?input);
+    fn parse(input: &str) -> eyre::Result<Self::Parsed> {
+        input.parse()
+    }
+
+    fn part1(input: &Self::Parsed) -> eyre::Result<Answer> {
+        process_part1(input)
+    }
 
-        let start1 = SystemTime::now();
-        let result1 = process_part1(&input)?;
-        let end1 = SystemTime::now();
-        let start2 = SystemTime::now();
-        let result2 = process_part2(&input)?;
-        let end2 = SystemTime::now();
-        println!("{DAY} result:");
-        println!("  part 1: {result1} in {:?}", end1.duration_since(start1).unwrap());
-        println!("  part 2: {result2} in {:?}", end2.duration_since(start2).unwrap());
-        Ok(())
+    fn part2(input: &Self::Parsed) -> eyre::Result<Answer> {
+        process_part2(input)
     }
-        .instrument(day_span.or_current())
-        .await
+}
+
+register_day! {
+    day: 13,
+    status: Implemented,
+    solver: Solution,
 }
 
 #[cfg(test)]
@@ -238,7 +213,7 @@ mod test {
         let input = example_input();
 
         let result = process_part1(&input).unwrap();
-        assert_eq!(480, result);
+        assert_eq!(Answer::Unsigned(480), result);
     }
 
     #[test]
@@ -246,6 +221,6 @@ mod test {
         let input = example_input();
 
         let result = process_part2(&input).unwrap();
-        assert_eq!(875318608908, result);
+        assert_eq!(Answer::Unsigned(875318608908), result);
     }
 }
@@ -1,38 +1,52 @@
-use std::ops::Mul;
 use std::str::FromStr;
-use std::time::SystemTime;
 use eyre::eyre;
 use itertools::Itertools;
-use tracing::{debug, info, Instrument, Level, span, trace};
-use crate::days::Day;
-
-pub const DAY: Day = Day(14);
+use crate::days::solver::{register_day, Answer, Solver};
+use crate::days::approach::{Approach, ApproachHistory};
+use crate::days::util::{Bounds, Coordinate, WrappingCoordinate};
+use crate::days::util::counter::Counter;
+use crate::days::util::math::crt;
+use crate::days::util::parse::{ints_fixed, labelled, pair};
+
+pub const APPROACH_HISTORY: ApproachHistory = ApproachHistory {
+    day: DAY,
+    approaches: &[
+        Approach { name: "std-deviation", description: "Wait until the robots cluster, i.e. the standard deviation of their positions drops" },
+        Approach { name: "no-overlaps", description: "Wait until no two robots share a position" },
+        Approach { name: "chinese-remainder", description: "Find when the robots cluster on each axis within its period and combine the two seconds" },
+    ],
+    active: "std-deviation",
+    solve: |raw_input| {
+        let input: Input<101, 103> = raw_input.parse()?;
+        process_part2(&input)?;
+        Ok(())
+    },
+};
 
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub struct Robot<const WIDTH: usize, const HEIGHT: usize> {
-    position: (usize, usize),
-    velocity: (isize, isize),
+    position: WrappingCoordinate<WIDTH, HEIGHT>,
+    velocity: Coordinate,
 }
 
 impl<const WIDTH: usize, const HEIGHT: usize> Robot<WIDTH, HEIGHT> {
     pub fn patrol(&mut self, seconds: usize) {
-        self.position.0 = ((self.position.0 + WIDTH) as isize + ((self.velocity.0 * seconds as isize) % WIDTH as isize)) as usize % WIDTH;
-        self.position.1 = ((self.position.1 + HEIGHT) as isize + ((self.velocity.1 * seconds as isize) % HEIGHT as isize)) as usize % HEIGHT;
+        self.position += self.velocity * seconds as isize;
     }
 
     pub fn patrol_once(&mut self) {
-        self.position.0 = ((self.position.0 + WIDTH) as isize + self.velocity.0) as usize % WIDTH;
-        self.position.1 = ((self.position.1 + HEIGHT) as isize + self.velocity.1) as usize % HEIGHT;
+        self.position += self.velocity;
     }
 
     pub fn quadrant(&self) -> Option<u8> {
-        match (self.position.0 < WIDTH / 2, self.position.0 > WIDTH / 2, self.position.1 < HEIGHT / 2, self.position.1 > HEIGHT / 2) {
-            (false, false, _, _) => None,
-            (_, _, false, false) => None,
-            (_, right, _, bottom) => {
-                Some(if right { 1 } else { 0 } + if bottom { 2 } else { 0 })
-            }
-        }
+        let center = Coordinate((WIDTH / 2) as isize, (HEIGHT / 2) as isize);
+        // the robots on the middle row or column are in no quadrant, and as the sizes are odd the
+        // bottom quadrants are the top ones turned around the center
+        let top_left = Bounds::new(Coordinate(0, 0), center - Coordinate(1, 1));
+        let top_right = Bounds::new(Coordinate(center.0 + 1, 0), Coordinate(WIDTH as isize - 1, center.1 - 1));
+        let half_turn = |bounds: Bounds| Bounds::new(bounds.max.rotate_around(center, 2), bounds.min.rotate_around(center, 2));
+        let quadrants = [top_left, top_right, half_turn(top_right), half_turn(top_left)];
+        (0..4).find(|&quadrant| quadrants[quadrant as usize].contains(self.position.coordinate()))
     }
 }
 
@@ -40,21 +54,11 @@ impl<const WIDTH: usize, const HEIGHT: usize> FromStr for Robot<WIDTH, HEIGHT> {
     type Err = eyre::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (coordinate, velocity) = s.split_once(" ")
-            .ok_or(eyre!("Failed to split robot into position and velocity"))?;
-
-        let coordinate = coordinate.strip_prefix("p=")
-            .ok_or(eyre!("position should be given as p=x,y"))?;
-        let (x, y) = coordinate.split_once(",")
-            .ok_or(eyre!("position should be given as p=x,y"))?;
-        let position = (x.parse()?, y.parse()?);
-
-        let velocity = velocity.strip_prefix("v=")
-            .ok_or(eyre!("velocity should be given as v=x,y"))?;
-        let (dx, dy) = velocity.split_once(",")
-            .ok_or(eyre!("velocity should be given as v=x,y"))?;
-        let (dx, dy) = (dx.parse::<isize>()?, dy.parse::<isize>()?);
-        let velocity = (dx % WIDTH as isize, dy % HEIGHT as isize);
+        let (position, velocity) = pair(s, " ")?;
+        let [x, y] = ints_fixed(labelled(position, "p=")?)?;
+        let position = WrappingCoordinate::new(Coordinate(x, y));
+        let [dx, dy] = ints_fixed(labelled(velocity, "v=")?)?;
+        let velocity = Coordinate(dx, dy);
 
         Ok(Self {
             position,
@@ -63,6 +67,15 @@ impl<const WIDTH: usize, const HEIGHT: usize> FromStr for Robot<WIDTH, HEIGHT> {
     }
 }
 
+/// How the robots pile up on each other at a given second.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct Overlaps {
+    /// The number of positions occupied by more than one robot.
+    pub positions: usize,
+    /// The most robots standing on a single position.
+    pub max_stack: usize,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub struct Input<const WIDTH: usize, const HEIGHT: usize> {
     robots: Vec<Robot<WIDTH, HEIGHT>>,
@@ -83,19 +96,69 @@ impl<const WIDTH: usize, const HEIGHT: usize> FromStr for Input<WIDTH, HEIGHT> {
     }
 }
 
-pub fn process_part1<const WIDTH: usize, const HEIGHT: usize>(input: &Input<WIDTH, HEIGHT>) -> eyre::Result<usize> {
+impl<const WIDTH: usize, const HEIGHT: usize> Input<WIDTH, HEIGHT> {
+    pub fn positions_at(&self, seconds: usize) -> Vec<WrappingCoordinate<WIDTH, HEIGHT>> {
+        self.robots.iter()
+            .map(|robot| {
+                let mut robot = robot.clone();
+                robot.patrol(seconds);
+                robot.position
+            })
+            .collect()
+    }
+
+    pub fn overlaps_at(&self, seconds: usize) -> Overlaps {
+        self.positions_at(seconds).into_iter()
+            .collect::<Counter<_>>()
+            .into_iter()
+            .map(|(_, count)| count)
+            .filter(|&count| count > 1)
+            .fold(Overlaps::default(), |overlaps, count| Overlaps {
+                positions: overlaps.positions + 1,
+                max_stack: overlaps.max_stack.max(count),
+            })
+    }
+
+    /// The first second at which the robots cluster on both axes. The columns of the robots repeat
+    /// every `WIDTH` seconds and their rows every `HEIGHT` seconds, so the second the columns are
+    /// the least spread out within their period only has to meet the one of the rows.
+    pub fn first_time_clustered(&self) -> Option<usize> {
+        let most_clustered = |period: usize, axis: fn(&WrappingCoordinate<WIDTH, HEIGHT>) -> usize| (0..period)
+            .map(|seconds| {
+                let values = self.positions_at(seconds).iter().map(axis).collect::<Vec<_>>();
+                (seconds, std_deviation(&values))
+            })
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(seconds, _)| seconds as i128);
+
+        let column_seconds = most_clustered(WIDTH, |position| position.x())?;
+        let row_seconds = most_clustered(HEIGHT, |position| position.y())?;
+        let (seconds, _) = crt(&[(column_seconds, WIDTH as i128), (row_seconds, HEIGHT as i128)])?;
+        Some(seconds as usize)
+    }
+
+    /// The first second at which no two robots share a position, which tends to be when they form
+    /// the christmas tree. As the robots move in a cycle of `WIDTH * HEIGHT` seconds this is
+    /// [`None`] if they never spread out.
+    pub fn first_time_no_overlaps(&self) -> Option<usize> {
+        (0..WIDTH * HEIGHT)
+            .find(|&seconds| self.positions_at(seconds).into_iter().all_unique())
+    }
+}
+
+pub fn process_part1<const WIDTH: usize, const HEIGHT: usize>(input: &Input<WIDTH, HEIGHT>) -> eyre::Result<Answer> {
     let mut input = input.robots
         .iter().cloned()
         .collect::<Vec<_>>();
 
-    let quadrant_counts = input.iter_mut()
+    let quadrant_counts: Counter<u8> = input.iter_mut()
         .filter_map(|robot| {
             robot.patrol(100);
             robot.quadrant()
         })
-        .counts();
+        .collect();
 
-    Ok(quadrant_counts.values().fold(1, usize::mul))
+    Ok(quadrant_counts.iter().map(|(_, count)| count).product::<usize>().into())
 }
 
 fn std_deviation(data: &[usize]) -> f32 {
@@ -119,7 +182,7 @@ fn find_image<const WIDTH: usize, const HEIGHT: usize>(robots: &mut [Robot<WIDTH
         seconds += 1;
 
         let (xs, ys): (Vec<usize>, Vec<usize>) = robots.iter()
-            .map(|robot| robot.position)
+            .map(|robot| (robot.position.x(), robot.position.y()))
             .unzip();
 
         let x_score = std_deviation(&xs);
@@ -131,7 +194,18 @@ fn find_image<const WIDTH: usize, const HEIGHT: usize>(robots: &mut [Robot<WIDTH
     }
 }
 
-pub fn process_part2<const WIDTH: usize, const HEIGHT: usize>(input: &Input<WIDTH, HEIGHT>) -> eyre::Result<usize> {
+pub fn process_part2<const WIDTH: usize, const HEIGHT: usize>(input: &Input<WIDTH, HEIGHT>) -> eyre::Result<Answer> {
+    if APPROACH_HISTORY.selected() == "no-overlaps" {
+        return input.first_time_no_overlaps()
+            .map(Answer::from)
+            .ok_or(eyre!("The robots never spread out"));
+    }
+    if APPROACH_HISTORY.selected() == "chinese-remainder" {
+        return input.first_time_clustered()
+            .map(Answer::from)
+            .ok_or(eyre!("The robots never cluster"));
+    }
+
     let mut robots = input.robots
         .iter().cloned()
         .collect::<Vec<_>>();
@@ -141,36 +215,35 @@ pub fn process_part2<const WIDTH: usize, const HEIGHT: usize>(input: &Input<WIDT
     let seconds2 = find_image(&mut robots);
 
     if seconds1 == seconds2 {
-        Ok(seconds)
+        Ok(seconds.into())
     } else {
         Err(eyre!("Failed to determine"))
     }
 }
 
-pub async fn run() -> eyre::Result<()> {
-    let day_span = span!(Level::ERROR, "", "{}", DAY);
-    async {
-        info!("Running {DAY}");
+pub struct Solution;
 
-        let raw_input = super::get_input(DAY).await?;
-        trace!(raw_input);
+impl Solver for Solution {
+    type Parsed = Input<101, 103>;
 
-        let input: Input<101, 103> = raw_input.parse()?;
-        debug!(?input);
-
-        let start1 = SystemTime::now();
-        let result1 = process_part1(&input)?;
-        let end1 = SystemTime::now();
-        let start2 = SystemTime::now();
-        let result2 = process_part2(&input)?;
-        let end2 = SystemTime::now();
-        println!("{DAY} result:");
-        println!("  part 1: {result1} in {:?}", end1.duration_since(start1).unwrap());
-        println!("  part 2: {result2} in {:?}", end2.duration_since(start2).unwrap());
-        Ok(())
+    fn parse(input: &str) -> eyre::Result<Self::Parsed> {
+        input.parse()
+    }
+
+    fn part1(input: &Self::Parsed) -> eyre::Result<Answer> {
+        process_part1(input)
+    }
+
+    fn part2(input: &Self::Parsed) -> eyre::Result<Answer> {
+        process_part2(input)
     }
-        .instrument(day_span.or_current())
-        .await
+}
+
+register_day! {
+    day: 14,
+    status: Implemented,
+    solver: Solution,
+    approach_history: APPROACH_HISTORY,
 }
 
 #[cfg(test)]
@@ -196,12 +269,12 @@ mod test {
     #[test]
     pub fn test_patrol() {
         let mut robot: Robot<11, 7> = Robot {
-            position: (2, 4),
-            velocity: (2, -3),
+            position: WrappingCoordinate::new(Coordinate(2, 4)),
+            velocity: Coordinate(2, -3),
         };
         assert_eq!(-3 % 7, -3);
         robot.patrol(1);
-        assert_eq!(robot.position, (4, 1));
+        assert_eq!(robot.position, WrappingCoordinate::new(Coordinate(4, 1)));
     }
 
     #[test]
@@ -209,6 +282,41 @@ mod test {
         let input = example_input();
 
         let result = process_part1(&input).unwrap();
-        assert_eq!(12, result);
+        assert_eq!(Answer::Unsigned(12), result);
+    }
+
+    #[test]
+    pub fn test_overlaps() {
+        let input = example_input();
+
+        assert_eq!(Overlaps { positions: 1, max_stack: 2 }, input.overlaps_at(0));
+        let seconds = input.first_time_no_overlaps().unwrap();
+        assert_eq!(Overlaps::default(), input.overlaps_at(seconds));
+        assert!((0..seconds).all(|seconds| input.overlaps_at(seconds).positions > 0));
+    }
+
+    #[test]
+    pub fn test_clustered() {
+        let input = example_input();
+
+        // the robots repeat their positions after the least common multiple of the periods
+        let seconds = input.first_time_clustered().unwrap();
+        assert!(seconds < 11 * 7);
+    }
+
+    #[test]
+    pub fn test_period() {
+        use crate::days::util::cycle::{self, Cycle};
+
+        let input = example_input();
+
+        // the searches for the christmas tree stop after `WIDTH * HEIGHT` seconds, as the robots
+        // have been everywhere they'll ever be by then
+        let cycle = cycle::detect(input.robots.clone(), |robots| {
+            let mut robots = robots.clone();
+            robots.iter_mut().for_each(Robot::patrol_once);
+            Some(robots)
+        });
+        assert_eq!(Some(Cycle { start: 0, length: 11 * 7 }), cycle);
     }
 }
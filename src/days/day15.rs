@@ -1,108 +1,13 @@
 use std::collections::{HashSet, VecDeque};
 use std::fmt::{Display, Formatter};
 use std::str::FromStr;
-use std::time::SystemTime;
-// use ansi_control_codes::control_sequences::CUP;
-// use ansi_escape_codes::EscapeSequence::EraseScreenSequence;
 use eyre::eyre;
 use itertools::Itertools;
 use owo_colors::OwoColorize;
-use tracing::{debug, info, Instrument, Level, span, trace};
-use crate::days::Day;
-use crate::days::util::{Coordinate, ParsedGrid};
-
-pub const DAY: Day = Day(15);
-
-#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
-pub enum Direction {
-    North,
-    East,
-    South,
-    West,
-}
-
-impl Display for Direction {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.symbol())
-    }
-}
-
-impl TryFrom<char> for Direction {
-    type Error = eyre::Error;
-
-    fn try_from(value: char) -> Result<Self, Self::Error> {
-        match value {
-            '^' => Ok(Self::North),
-            '>' => Ok(Self::East),
-            'v' => Ok(Self::South),
-            '<' => Ok(Self::West),
-            _ => Err(eyre!("Invalid direction '{value}'")),
-        }
-    }
-}
-
-impl Direction {
-    pub const fn symbol(&self) -> char {
-        match self {
-            Direction::North => '^',
-            Direction::East => '>',
-            Direction::South => 'v',
-            Direction::West => '<',
-        }
-    }
-
-    pub const fn rotate90(&self) -> Self {
-        match self {
-            Direction::North => Direction::East,
-            Direction::East => Direction::South,
-            Direction::South => Direction::West,
-            Direction::West => Direction::North,
-        }
-    }
-
-    pub const fn rotate180(&self) -> Self {
-        match self {
-            Direction::North => Direction::South,
-            Direction::East => Direction::West,
-            Direction::South => Direction::North,
-            Direction::West => Direction::East,
-        }
-    }
-
-    pub const fn rotate270(&self) -> Self {
-        match self {
-            Direction::North => Direction::West,
-            Direction::East => Direction::North,
-            Direction::South => Direction::East,
-            Direction::West => Direction::South,
-        }
-    }
-
-    pub const fn vertical(&self) -> bool {
-        match self {
-            Direction::North | Direction::South => true,
-            Direction::East | Direction::West => false,
-        }
-    }
-
-    pub const fn horizontal(&self) -> bool {
-        match self {
-            Direction::North | Direction::South => false,
-            Direction::East | Direction::West => true,
-        }
-    }
-}
-
-impl Into<Coordinate> for Direction {
-    fn into(self) -> Coordinate {
-        match self {
-            Direction::North => Coordinate(0, -1),
-            Direction::East => Coordinate(1, 0),
-            Direction::South => Coordinate(0, 1),
-            Direction::West => Coordinate(-1, 0),
-        }
-    }
-}
+use tracing::{trace, Level};
+use crate::days::solver::{register_day, Answer, Solver};
+use crate::days::util::{Coordinate, Direction, Marker, ParsedGrid};
+use crate::days::util::display::diff;
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
 pub enum Tile {
@@ -138,9 +43,9 @@ impl TryFrom<char> for Tile {
     }
 }
 
-impl Into<char> for Tile {
-    fn into(self) -> char {
-        self.symbol()
+impl From<Tile> for char {
+    fn from(tile: Tile) -> Self {
+        tile.symbol()
     }
 }
 
@@ -226,15 +131,15 @@ impl<const X_SCALE: u8> FromStr for Warehouse<X_SCALE> {
     type Err = eyre::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let map = s.lines()
-            .map(str::trim)
-            .filter(|line| !line.is_empty())
-            .join("\n");
-        let mut map = map.parse::<ParsedGrid<Tile>>()?;
-        let robot_position = map.as_slice().iter()
-            .position(|tile| *tile == Tile::Robot)
-            .ok_or(eyre!("Failed to find robot"))?;
-        map.as_mut_slice()[robot_position] = Tile::Empty;
+        let mut robot = Marker::new(Tile::Robot.symbol());
+        let map = ParsedGrid::from_str_with(s, |tile, coordinate| {
+            if robot.check(tile, coordinate) {
+                Ok(Tile::Empty)
+            } else {
+                Tile::try_from(tile)
+            }
+        })?;
+        let robot_position = robot.index(&map)?;
         let map_size = map.as_slice().len();
 
         Ok(Self{
@@ -246,41 +151,39 @@ impl<const X_SCALE: u8> FromStr for Warehouse<X_SCALE> {
 }
 
 impl Warehouse<1> {
-    pub fn move_robot(&mut self, direction: Direction) -> Result<(), ()> {
+    pub fn move_robot(&mut self, direction: Direction) -> Option<()> {
         let move_to = self.map.offset_index(self.robot_position.0, direction.into())?;
         let mut position = self.robot_position.0;
 
-        while let Ok(new_position) = self.map.offset_index(position, direction.into()) {
+        while let Some(new_position) = self.map.offset_index(position, direction.into()) {
             position = new_position;
             let tile = self.map.as_slice()[position];
 
             if tile == Tile::Wall {
-                return Err(());
+                return None;
             }
 
             if tile == Tile::Empty {
                 self.map.swap(move_to, position);
                 self.map.swap(self.robot_position.0, move_to);
                 self.robot_position = (move_to, 0);
-                return Ok(());
+                return Some(());
             }
         }
 
-        Err(())
+        None
     }
 
     pub fn box_positions(&self) -> Vec<usize> {
-        self.map.as_slice().iter()
-            .enumerate()
-            .filter(|(_, tile)| **tile == Tile::Box)
-            .map(|(index, _)| self.map.index_to_coordinate(index))
-            .map(|coordinate| coordinate.0 as usize + coordinate.1 as usize * 100)
+        self.map.iter_coords()
+            .filter(|&(_, tile)| *tile == Tile::Box)
+            .map(|(Coordinate(x, y), _)| x as usize + y as usize * 100)
             .collect()
     }
 }
 
 impl Warehouse<2> {
-    pub fn offset_position(&self, (position, offset): (usize, u8), direction: Direction) -> Result<(usize, u8), ()> {
+    pub fn offset_position(&self, (position, offset): (usize, u8), direction: Direction) -> Option<(usize, u8)> {
         match direction {
             Direction::North | Direction::South => {
                 self.map.offset_index(position, direction.into())
@@ -288,7 +191,7 @@ impl Warehouse<2> {
             }
             Direction::East => {
                 if offset == 0 {
-                    Ok((position, offset + 1))
+                    Some((position, offset + 1))
                 } else {
                     self.map.offset_index(position, direction.into())
                         .map(|position| (position, 0))
@@ -296,7 +199,7 @@ impl Warehouse<2> {
             }
             Direction::West => {
                 if offset == 1 {
-                    Ok((position, offset - 1))
+                    Some((position, offset - 1))
                 } else {
                     self.map.offset_index(position, direction.into())
                         .map(|position| (position, 1))
@@ -305,14 +208,14 @@ impl Warehouse<2> {
         }
     }
 
-    pub fn look(&self, position: (usize, u8), direction: Direction) -> Result<Vec<(usize, u8)>, ()> {
+    pub fn look(&self, position: (usize, u8), direction: Direction) -> Option<Vec<(usize, u8)>> {
         match direction {
-            Direction::East | Direction::West => Ok(vec![
+            Direction::East | Direction::West => Some(vec![
                 self.offset_position(self.offset_position(position, direction)?, direction)?
             ]),
             Direction::North | Direction::South => {
                 let offset = self.offset_position(position, direction)?;
-                Ok(vec![
+                Some(vec![
                     offset,
                     self.offset_position(offset, direction.rotate270())?,
                     self.offset_position(offset, direction.rotate90())?,
@@ -321,32 +224,32 @@ impl Warehouse<2> {
         }
     }
 
-    pub fn look_robot(&self, direction: Direction) -> Result<Vec<(usize, u8)>, ()> {
+    pub fn look_robot(&self, direction: Direction) -> Option<Vec<(usize, u8)>> {
         let offset = self.offset_position(self.robot_position, direction)?;
         match direction {
             Direction::North => {
-                Ok(vec![
+                Some(vec![
                     offset,
                     self.offset_position(offset, direction.rotate270())?,
                 ])
             },
-            Direction::East => Ok(vec![
+            Direction::East => Some(vec![
                 offset,
             ]),
             Direction::South => {
                 let offset = self.offset_position(self.robot_position, direction)?;
-                Ok(vec![
+                Some(vec![
                     offset,
                     self.offset_position(offset, direction.rotate90())?,
                 ])
             },
-            Direction::West => Ok(vec![
+            Direction::West => Some(vec![
                 self.offset_position(offset, direction)?
             ]),
         }
     }
 
-    pub fn move_robot(&mut self, direction: Direction) -> Result<(), ()> {
+    pub fn move_robot(&mut self, direction: Direction) -> Option<()> {
         let mut visited = HashSet::new();
         let mut to_move = Vec::new();
         let mut checking = VecDeque::from_iter(self.look_robot(direction)?);
@@ -357,7 +260,7 @@ impl Warehouse<2> {
             }
 
             if tile == Tile::Wall {
-                return Err(());
+                return None;
             }
 
             visited.insert(check);
@@ -393,16 +296,14 @@ impl Warehouse<2> {
         }
 
         self.robot_position = self.offset_position(self.robot_position, direction)?;
-        Ok(())
+        Some(())
     }
 
     pub fn box_positions(&self) -> Vec<usize> {
-        self.map.as_slice().iter()
-            .zip(self.horizontal_offset.iter())
-            .enumerate()
-            .filter(|(_, (tile, _))| **tile == Tile::Box)
-            .map(|(index, (_, offset))| (offset, self.map.index_to_coordinate(index)))
-            .map(|(&offset, coordinate)| (coordinate.0 as usize * 2 + offset as usize) + coordinate.1 as usize * 100)
+        self.map.iter_coords()
+            .zip(&self.horizontal_offset)
+            .filter(|&((_, tile), _)| *tile == Tile::Box)
+            .map(|((Coordinate(x, y), _), &offset)| (x as usize * 2 + offset as usize) + y as usize * 100)
             .collect()
     }
 }
@@ -435,66 +336,62 @@ impl<const X_SCALE: u8> FromStr for Input<X_SCALE> {
     }
 }
 
-pub fn process_part1(input: &Input<1>) -> eyre::Result<usize> {
+pub fn process_part1(input: &Input<1>) -> eyre::Result<Answer> {
     let mut warehouse = input.warehouse.clone();
 
-    // println!("{warehouse}\n");
     for direction in &input.moves {
+        let before = tracing::enabled!(Level::TRACE).then(|| warehouse.map.clone());
         let _ = warehouse.move_robot(*direction);
-        // println!("move: {direction}");
-        // println!("{warehouse}\n");
+        if let Some(before) = before {
+            trace!("move: {direction}\n{}", diff(&before, &warehouse.map));
+        }
     }
 
-    Ok(warehouse.box_positions().iter().sum())
+    Ok(warehouse.box_positions().iter().sum::<usize>().into())
 }
 
-pub fn process_part2(input: &Input<2>) -> eyre::Result<usize> {
+pub fn process_part2(input: &Input<2>) -> eyre::Result<Answer> {
     let mut warehouse = input.warehouse.clone();
 
-    // println!("{warehouse}{}", EraseScreenSequence.to_string());
     for direction in &input.moves {
+        let before = tracing::enabled!(Level::TRACE).then(|| warehouse.map.clone());
         let _ = warehouse.move_robot(*direction);
-        // println!("{}{warehouse}", CUP(Some(0), Some(0)));
+        if let Some(before) = before {
+            trace!("move: {direction}\n{}", diff(&before, &warehouse.map));
+        }
     }
 
-    Ok(warehouse.box_positions().iter().sum())
+    Ok(warehouse.box_positions().iter().sum::<usize>().into())
 }
 
-pub async fn run() -> eyre::Result<()> {
-    let day_span = span!(Level::ERROR, "", "{}", DAY);
-    async {
-        info!("Running {DAY}");
+pub struct Solution;
 
-        let raw_input = super::get_input(DAY).await?;
-        trace!(raw_input);
+impl Solver for Solution {
+    type Parsed = (Input<1>, Input<2>);
 
-        let input = raw_input.parse()?;
-        debug!(?input);
-
-        let start1 = SystemTime::now();
-        let result1 = process_part1(&input)?;
-        let end1 = SystemTime::now();
-
-        let raw_input = super::get_input(DAY).await?;
-        trace!(raw_input);
+    fn parse(input: &str) -> eyre::Result<Self::Parsed> {
+        Ok((input.parse()?, input.parse()?))
+    }
 
-        let input = raw_input.parse()?;
-        debug!(?input);
+    fn part1(input: &Self::Parsed) -> eyre::Result<Answer> {
+        process_part1(&input.0)
+    }
 
-        let start2 = SystemTime::now();
-        let result2 = process_part2(&input)?;
-        let end2 = SystemTime::now();
-        println!("{DAY} result:");
-        println!("  part 1: {result1} in {:?}", end1.duration_since(start1).unwrap());
-        println!("  part 2: {result2} in {:?}", end2.duration_since(start2).unwrap());
-        Ok(())
+    fn part2(input: &Self::Parsed) -> eyre::Result<Answer> {
+        process_part2(&input.1)
     }
-        .instrument(day_span.or_current())
-        .await
+}
+
+register_day! {
+    day: 15,
+    status: Implemented,
+    solver: Solution,
 }
 
 #[cfg(test)]
 mod test {
+    use rand::{Rng, SeedableRng};
+    use rand::rngs::StdRng;
     use super::*;
 
     fn example_1_input() -> Input<1> {
@@ -554,7 +451,7 @@ mod test {
         let input = example_1_input();
 
         let result = process_part1(&input).unwrap();
-        assert_eq!(2028, result);
+        assert_eq!(Answer::Unsigned(2028), result);
     }
 
     #[test]
@@ -562,7 +459,7 @@ mod test {
         let input = example_2_input();
 
         let result = process_part1(&input).unwrap();
-        assert_eq!(10092, result);
+        assert_eq!(Answer::Unsigned(10092), result);
     }
 
     #[test]
@@ -570,7 +467,7 @@ mod test {
         let input = example_3_input();
 
         let result = process_part2(&input).unwrap();
-        assert_eq!(100 * 1 + 5 + 100 * 2 + 7 + 100 * 3 + 6, result);
+        assert_eq!(Answer::Unsigned(100 * 1 + 5 + 100 * 2 + 7 + 100 * 3 + 6), result);
     }
 
     #[test]
@@ -578,6 +475,86 @@ mod test {
         let input = example_2_input();
 
         let result = process_part2(&input).unwrap();
-        assert_eq!(9021, result);
+        assert_eq!(Answer::Unsigned(9021), result);
+    }
+
+    fn random_input(rng: &mut StdRng, directions: &[char]) -> String {
+        let (width, height) = (rng.gen_range(4..12), rng.gen_range(4..12));
+        let mut map = (0..height)
+            .map(|y| (0..width)
+                .map(|x| if x == 0 || y == 0 || x == width - 1 || y == height - 1 || rng.gen_bool(0.1) {
+                    '#'
+                } else if rng.gen_bool(0.3) {
+                    'O'
+                } else {
+                    '.'
+                })
+                .collect::<String>())
+            .collect::<Vec<_>>();
+        let (x, y) = (rng.gen_range(1..width - 1), rng.gen_range(1..height - 1));
+        map[y].replace_range(x..x + 1, "@");
+        let moves = (0..50)
+            .map(|_| directions[rng.gen_range(0..directions.len())])
+            .collect::<String>();
+
+        format!("{}\n\n{moves}\n", map.join("\n"))
+    }
+
+    /// Checks that no two boxes, walls or the robot overlap in the widened warehouse.
+    fn assert_no_overlaps(warehouse: &Warehouse<2>, raw_input: &str) {
+        let mut occupied = vec![false; warehouse.map.as_slice().len() * 2];
+        occupied[warehouse.robot_position.0 * 2 + warehouse.robot_position.1 as usize] = true;
+        for (position, tile) in warehouse.map.as_slice().iter().enumerate() {
+            if !matches!(tile, Tile::Box | Tile::Wall) {
+                continue;
+            }
+            let start = position * 2 + warehouse.horizontal_offset[position] as usize;
+            for half in start..start + 2 {
+                assert!(!occupied[half], "{raw_input}\n{warehouse}");
+                occupied[half] = true;
+            }
+        }
+    }
+
+    #[test]
+    pub fn test_scales_agree_on_vertical_moves() {
+        let mut rng = StdRng::seed_from_u64(15);
+        for _ in 0..200 {
+            let raw_input = random_input(&mut rng, &['^', 'v']);
+            let input1: Input<1> = raw_input.parse().unwrap();
+            let input2: Input<2> = raw_input.parse().unwrap();
+            let (mut warehouse1, mut warehouse2) = (input1.warehouse, input2.warehouse);
+
+            // boxes only ever move in whole columns, so the wide boxes stay aligned to the narrow ones
+            for direction in input1.moves {
+                assert_eq!(warehouse1.move_robot(direction), warehouse2.move_robot(direction), "{raw_input}");
+                let widened = warehouse1.box_positions().into_iter()
+                    .map(|position| position % 100 * 2 + position / 100 * 100)
+                    .sorted()
+                    .collect::<Vec<_>>();
+                assert_eq!(widened, warehouse2.box_positions().into_iter().sorted().collect::<Vec<_>>(), "{raw_input}");
+                assert_eq!((warehouse1.robot_position.0, 0), warehouse2.robot_position, "{raw_input}");
+            }
+        }
+    }
+
+    #[test]
+    pub fn test_scales_keep_boxes() {
+        let mut rng = StdRng::seed_from_u64(15);
+        for _ in 0..200 {
+            let raw_input = random_input(&mut rng, &['^', '>', 'v', '<']);
+            let input1: Input<1> = raw_input.parse().unwrap();
+            let input2: Input<2> = raw_input.parse().unwrap();
+            let (mut warehouse1, mut warehouse2) = (input1.warehouse, input2.warehouse);
+            let boxes = warehouse1.box_positions().len();
+
+            for direction in input1.moves {
+                let _ = warehouse1.move_robot(direction);
+                let _ = warehouse2.move_robot(direction);
+                assert_eq!(boxes, warehouse1.box_positions().len(), "{raw_input}");
+                assert_eq!(boxes, warehouse2.box_positions().len(), "{raw_input}");
+                assert_no_overlaps(&warehouse2, &raw_input);
+            }
+        }
     }
 }
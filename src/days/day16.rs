@@ -1,35 +1,14 @@
-use std::cmp::Ordering;
-use std::collections::{BinaryHeap, VecDeque};
 use std::str::FromStr;
-use std::time::SystemTime;
-// use anes::{ClearBuffer, HideCursor, MoveCursorTo, ShowCursor};
-use eyre::eyre;
-// use itertools::Itertools;
-// use owo_colors::OwoColorize;
-use tracing::{debug, info, Instrument, Level, span, trace};
-use crate::days::Day;
-use crate::days::util::{Direction, Grid};
-
-pub const DAY: Day = Day(16);
-
-#[derive(Clone, Debug, Eq, PartialEq, Hash)]
-pub struct State {
-    pub position: usize,
-    pub score: usize,
-    pub facing: Direction,
-}
-
-impl Ord for State {
-    fn cmp(&self, other: &Self) -> Ordering {
-        other.score.cmp(&self.score)
-            .then_with(|| self.position.cmp(&other.position))
-    }
-}
-
-impl PartialOrd for State {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
+use crate::days::solver::{register_day, Answer, Solver};
+use crate::days::util::{Direction, Grid, Marker};
+use crate::days::util::bit_grid::BitGrid;
+use crate::days::util::graph::{Graph, NodeId};
+use crate::days::util::search::astar;
+
+/// The id of the state of the reindeer standing on `position` facing `direction` in
+/// [`Input::state_graph`].
+fn state(position: usize, direction: Direction) -> NodeId {
+    (position * 4 + direction.index()) as NodeId
 }
 
 #[derive(Clone, Debug)]
@@ -41,153 +20,64 @@ pub struct Input {
 
 impl Input {
     pub fn find_score(&self) -> Option<usize> {
-        let mut scores = vec![usize::MAX; self.map.as_slice().len()];
-        let mut heap = BinaryHeap::new();
-
-        scores[self.start] = 0;
-        heap.push(State { position: self.start, score: 0, facing: Direction::East });
+        let neighbours = |&(position, facing): &(usize, Direction)| self.map.neighbors4(position)
+            .filter(move |&(next, direction)| direction != facing.rotate180() && self.map.as_slice()[next] != '#')
+            .map(move |(next, direction)| ((next, direction), if direction == facing { 1 } else { 1001 }));
 
-        while let Some(State { position, score, facing }) = heap.pop() {
-            if position == self.end {
-                return Some(score);
-            }
+        // every step costs at least 1, so the manhattan distance never overestimates the score
+        let end = self.map.index_to_coordinate(self.end);
+        let heuristic = |&(position, _): &(usize, Direction)| self.map.index_to_coordinate(position).manhattan(end);
 
-            if score > scores[position] { continue; }
+        astar((self.start, Direction::East), neighbours, heuristic, |&(position, _)| position == self.end)
+            .map(|(score, _)| score)
+    }
 
+    /// Every state of the reindeer, a tile and the direction it faces, with the moves between them:
+    /// stepping forward costs 1 and turning 1000.
+    pub fn state_graph(&self) -> Graph<(usize, Direction)> {
+        let mut graph = Graph::new();
+        for position in 0..self.map.as_slice().len() {
             for direction in Direction::ALL {
-                if direction == facing.rotate180() {
-                    continue;
-                }
-                let score = if direction == facing {
-                    score + 1
-                } else {
-                    score + 1001
-                };
-                let position = if let Ok(position) = self.map.offset_index(position, direction.into()) {
-                    position
-                } else {
-                    continue;
-                };
-                if self.map.as_slice()[position] == '#' {
-                    continue;
-                }
-                let next = State { score, position, facing: direction };
+                graph.add_node((position, direction));
+            }
+        }
 
-                if next.score < scores[next.position] {
-                    scores[next.position] = next.score;
-                    heap.push(next);
+        for position in self.map.positions(|&tile| tile != '#') {
+            for direction in Direction::ALL {
+                graph.add_edge(state(position, direction), state(position, direction.rotate90()), 1000);
+                graph.add_edge(state(position, direction), state(position, direction.rotate270()), 1000);
+                let next = self.map.offset_index(position, direction.into())
+                    .filter(|&next| self.map.as_slice()[next] != '#');
+                if let Some(next) = next {
+                    graph.add_edge(state(position, direction), state(next, direction), 1);
                 }
             }
         }
 
-        None
+        graph
     }
 
     pub fn count_best_paths(&self) -> usize {
-        let mut scores = vec![(usize::MAX, 0u8); self.map.as_slice().len()];
-        let mut heap = BinaryHeap::new();
-
-        scores[self.start].0 = 0;
-        heap.push(State { position: self.start, score: 0, facing: Direction::East });
-
-        // let mut count = 0;
-        // print!("{}{}", ClearBuffer::All, HideCursor);
-        while let Some(State { position, score, facing }) = heap.pop() {
-            // if (count & ((1 << 4) - 1)) == 0 {
-            //     println!("{}{}", MoveCursorTo(0, 0), scores.iter()
-            //         .map(|&(_, directions)| if directions != 0 {
-            //             display_directions(directions).dimmed().bright_white().to_string()
-            //         } else {
-            //             display_directions(directions).dimmed().white().to_string()
-            //         })
-            //         .chunks(self.map.width()).into_iter()
-            //         .map(|chunk| chunk.collect::<String>())
-            //         .join("\n")
-            //     );
-            // }
-            if score > scores[self.end].0 {
-                break;
-            }
-
-            if score > scores[position].0 { continue; }
-
-            let previous_direction = facing.rotate180();
-            for direction in Direction::ALL {
-                if direction == previous_direction {
-                    continue;
-                }
-                let score = if direction == facing {
-                    score + 1
-                } else {
-                    score + 1001
-                };
-                let position = if let Ok(position) = self.map.offset_index(position, direction.into()) {
-                    position
-                } else {
-                    continue;
-                };
-                if self.map.as_slice()[position] == '#' {
-                    continue;
-                }
-                let next = State { score, position, facing: direction };
-
-                scores[position].1 |= direction.rotate180().mask();
-                if next.score < scores[next.position].0 {
-                    scores[next.position].0 = next.score;
-                    heap.push(next);
-                }
+        let graph = self.state_graph();
+        let (scores, predecessors) = graph.dijkstra_predecessors(state(self.start, Direction::East));
+        let end_states = Direction::ALL.map(|direction| state(self.end, direction));
+        let best = end_states.iter().map(|&end| scores[end as usize]).min().unwrap();
+
+        // every state on a best path is reached from its predecessors on one, back to the start
+        let mut on_best_path = vec![false; graph.node_count()];
+        let mut to_visit = end_states.into_iter()
+            .filter(|&end| scores[end as usize] == best)
+            .collect::<Vec<_>>();
+        let mut best_tiles = BitGrid::new(self.map.width(), self.map.height());
+        while let Some(current) = to_visit.pop() {
+            if std::mem::replace(&mut on_best_path[current as usize], true) {
+                continue;
             }
-            // count += 1;
+            best_tiles.set(graph.node(current).0, true);
+            to_visit.extend(&predecessors[current as usize]);
         }
-        let mut shortest_path = VecDeque::from([(self.end, None)]);
-        let mut shortest_map = vec![false; self.map.as_slice().len()];
-        // count = 0;
-        while let Some((shortest_element, previous)) = shortest_path.pop_front() {
-            shortest_map[shortest_element] = true;
-            let mask = scores[shortest_element].1;
-            for direction in Direction::from_mask(mask) {
-                let position = self.map.offset_index(shortest_element, direction.into()).unwrap();
-                let tolerance = if previous.is_some_and(|previous| direction == previous) {
-                    1000usize
-                } else {
-                    0
-                };
-                if scores[position].0  < scores[shortest_element].0 + tolerance {
-                    shortest_path.push_back((position, Some(direction)));
-                }
-            }
-            // if (count & ((1 << 6) - 1)) == 0 {
-            //     println!("{}{}", MoveCursorTo(0, 0), scores.iter()
-            //         .zip(shortest_map.iter())
-            //         .map(|(&(_, directions), &shortest_route)| if shortest_route {
-            //             display_directions(directions).bold().bright_green().to_string()
-            //         } else if directions != 0 {
-            //             display_directions(directions).dimmed().bright_white().to_string()
-            //         } else {
-            //             display_directions(directions).dimmed().white().to_string()
-            //         })
-            //         .chunks(self.map.width()).into_iter()
-            //         .map(|chunk| chunk.collect::<String>())
-            //         .join("\n")
-            //     );
-            // }
-            // count += 1;
-        }
-        // println!("{}{}", MoveCursorTo(0, 0), scores.iter()
-        //     .zip(shortest_map.iter())
-        //     .map(|(&(_, directions), &shortest_route)| if shortest_route {
-        //         display_directions(directions).bold().bright_green().to_string()
-        //     } else if directions != 0 {
-        //         display_directions(directions).dimmed().bright_white().to_string()
-        //     } else {
-        //         display_directions(directions).dimmed().white().to_string()
-        //     })
-        //     .chunks(self.map.width()).into_iter()
-        //     .map(|chunk| chunk.collect::<String>())
-        //     .join("\n")
-        // );
-        // print!("{}", ShowCursor);
-        shortest_map.iter().filter(|shortest| **shortest).count()
+
+        best_tiles.count_ones()
     }
 }
 
@@ -195,13 +85,12 @@ impl FromStr for Input {
     type Err = eyre::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let map = s.parse::<Grid>()?;
-        let start = map.as_slice().iter()
-            .position(|character| *character == 'S')
-            .ok_or(eyre!("Failed to find 'S' marking the start position"))?;
-        let end = map.as_slice().iter()
-            .position(|character| *character == 'E')
-            .ok_or(eyre!("Failed to find 'E' marking the start position"))?;
+        let (mut start, mut end) = (Marker::new('S'), Marker::new('E'));
+        let map = Grid::from_str_with(s, |tile, coordinate| {
+            Ok::<_, eyre::Error>(if start.check(tile, coordinate) || end.check(tile, coordinate) { '.' } else { tile })
+        })?;
+        let start = start.index(&map)?;
+        let end = end.index(&map)?;
 
         Ok(Self {
             map,
@@ -211,42 +100,40 @@ impl FromStr for Input {
     }
 }
 
-pub fn process_part1(input: &Input) -> eyre::Result<usize> {
+pub fn process_part1(input: &Input) -> eyre::Result<Answer> {
     let result = input.find_score().unwrap();
 
-    Ok(result)
+    Ok(result.into())
 }
 
-pub fn process_part2(input: &Input) -> eyre::Result<usize> {
+pub fn process_part2(input: &Input) -> eyre::Result<Answer> {
     let result = input.count_best_paths();
 
-    Ok(result)
+    Ok(result.into())
 }
 
-pub async fn run() -> eyre::Result<()> {
-    let day_span = span!(Level::ERROR, "", "{}", DAY);
-    async {
-        info!("Running {DAY}");
-
-        let raw_input = super::get_input(DAY).await?;
-        trace!(raw_input);
-
-        let input = raw_input.parse()?;
-        debug!(?input);
-
-        let start1 = SystemTime::now();
-        let result1 = process_part1(&input)?;
-        let end1 = SystemTime::now();
-        let start2 = SystemTime::now();
-        let result2 = process_part2(&input)?;
-        let end2 = SystemTime::now();
-        println!("{DAY} result:");
-        println!("  part 1: {result1} in {:?}", end1.duration_since(start1).unwrap());
-        println!("  part 2: {result2} in {:?}", end2.duration_since(start2).unwrap());
-        Ok(())
+pub struct Solution;
+
+impl Solver for Solution {
+    type Parsed = Input;
+
+    fn parse(input: &str) -> eyre::Result<Self::Parsed> {
+        input.parse()
+    }
+
+    fn part1(input: &Self::Parsed) -> eyre::Result<Answer> {
+        process_part1(input)
     }
-        .instrument(day_span.or_current())
-        .await
+
+    fn part2(input: &Self::Parsed) -> eyre::Result<Answer> {
+        process_part2(input)
+    }
+}
+
+register_day! {
+    day: 16,
+    status: Implemented,
+    solver: Solution,
 }
 
 #[cfg(test)]
@@ -298,7 +185,7 @@ mod test {
         let input = example_1_input();
 
         let result = process_part1(&input).unwrap();
-        assert_eq!(7036, result);
+        assert_eq!(Answer::Unsigned(7036), result);
     }
 
     #[test]
@@ -306,7 +193,7 @@ mod test {
         let input = example_2_input();
 
         let result = process_part1(&input).unwrap();
-        assert_eq!(11048, result);
+        assert_eq!(Answer::Unsigned(11048), result);
     }
 
     #[test]
@@ -314,7 +201,7 @@ mod test {
         let input = example_1_input();
 
         let result = process_part2(&input).unwrap();
-        assert_eq!(45, result);
+        assert_eq!(Answer::Unsigned(45), result);
     }
 
     #[test]
@@ -322,7 +209,7 @@ mod test {
         let input = example_2_input();
 
         let result = process_part2(&input).unwrap();
-        assert_eq!(64, result);
+        assert_eq!(Answer::Unsigned(64), result);
     }
 
     // from https://www.reddit.com/r/adventofcode/comments/1hfhgl1/2024_day_16_part_1_alternate_test_case/
@@ -335,7 +222,7 @@ mod test {
         let input = alternate_input();
 
         let result = process_part1(&input).unwrap();
-        assert_eq!(21148, result);
+        assert_eq!(Answer::Unsigned(21148), result);
     }
 
     #[test]
@@ -343,6 +230,6 @@ mod test {
         let input = alternate_input();
 
         let result = process_part2(&input).unwrap();
-        assert_eq!(149, result);
+        assert_eq!(Answer::Unsigned(149), result);
     }
 }
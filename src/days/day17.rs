@@ -1,12 +1,9 @@
 use std::str::FromStr;
 use std::sync::Arc;
-use std::time::SystemTime;
 use eyre::eyre;
 use itertools::Itertools;
-use tracing::{debug, info, Instrument, Level, span, trace};
-use crate::days::Day;
-
-pub const DAY: Day = Day(17);
+use crate::days::solver::{register_day, Answer, Solver};
+use crate::days::util::parse::{labelled, pair, parsed};
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
 pub enum ComboOperand {
@@ -77,29 +74,27 @@ impl FromStr for Computer {
         let mut register_a: Option<usize> = None;
         let mut register_b: Option<usize> = None;
         let mut register_c: Option<usize> = None;
-        registers.lines()
-            .map(|line| line.strip_prefix("Register ").unwrap())
-            .map(|line| line.split_once(": ").unwrap())
-            .map(|(register, value)| (register, value.parse().unwrap()))
-            .for_each(|(register, value)| match register {
-                "A" => register_a = Some(value),
-                "B" => register_b = Some(value),
-                "C" => register_c = Some(value),
-                _ => panic!("invalid register name"),
-            });
-
-        let program = program.strip_prefix("Program: ")
-            .ok_or(eyre!("Program should be given after \"Program: \""))?
-            .trim()
+        for line in registers.lines().map(str::trim) {
+            let (register, value) = pair(labelled(line, "Register ")?, ": ")?;
+            let value = Some(parsed(value)?);
+            match register {
+                "A" => register_a = value,
+                "B" => register_b = value,
+                "C" => register_c = value,
+                _ => return Err(eyre!("Invalid register {register:?} in {line:?}")),
+            }
+        }
+
+        let program = labelled(program.trim(), "Program: ")?
             .split(",")
-            .map(|value| value.parse().unwrap())
-            .collect();
+            .map(parsed)
+            .collect::<eyre::Result<_>>()?;
         let program = Arc::new(program);
 
         Ok(Self {
-            register_a: register_a.unwrap(),
-            register_b: register_b.unwrap(),
-            register_c: register_c.unwrap(),
+            register_a: register_a.ok_or(eyre!("Missing register A"))?,
+            register_b: register_b.ok_or(eyre!("Missing register B"))?,
+            register_c: register_c.ok_or(eyre!("Missing register C"))?,
             instruction_pointer: 0,
             program,
         })
@@ -202,15 +197,15 @@ impl FromStr for Input {
     }
 }
 
-pub fn process_part1(input: &Input) -> eyre::Result<Vec<u8>> {
+pub fn process_part1(input: &Input) -> eyre::Result<Answer> {
     let mut computer = input.computer.clone();
 
     let output = computer.execute();
 
-    Ok(output)
+    Ok(output.into())
 }
 
-pub fn process_part2(input: &Input) -> eyre::Result<usize> {
+pub fn process_part2(input: &Input) -> eyre::Result<Answer> {
     let mut computer = input.computer.clone();
     let mut correct_inputs = (0..1 << 3).collect::<Vec<_>>();
     let mut new_correct_inputs = Vec::with_capacity(1 << 3);
@@ -230,7 +225,7 @@ pub fn process_part2(input: &Input) -> eyre::Result<usize> {
                                 computer.register_b = 0;
                                 computer.register_c = 0;
                                 computer.instruction_pointer = 0;
-                                return Ok(input)
+                                return Ok(input.into())
                             }
                             new_correct_inputs.push(input);
                         }
@@ -242,33 +237,31 @@ pub fn process_part2(input: &Input) -> eyre::Result<usize> {
         std::mem::swap(&mut correct_inputs, &mut new_correct_inputs);
         new_correct_inputs.clear();
     }
-    Ok(*correct_inputs.first().unwrap())
+    Ok(correct_inputs[0].into())
 }
 
-pub async fn run() -> eyre::Result<()> {
-    let day_span = span!(Level::ERROR, "", "{}", DAY);
-    async {
-        info!("Running {DAY}");
-
-        let raw_input = super::get_input(DAY).await?;
-        trace!(raw_input);
-
-        let input = raw_input.parse()?;
-        debug!(?input);
-
-        let start1 = SystemTime::now();
-        let result1 = process_part1(&input)?;
-        let end1 = SystemTime::now();
-        let start2 = SystemTime::now();
-        let result2 = process_part2(&input)?;
-        let end2 = SystemTime::now();
-        println!("{DAY} result:");
-        println!("  part 1: {} in {:?}", result1.iter().join(","), end1.duration_since(start1).unwrap());
-        println!("  part 2: {result2} in {:?}", end2.duration_since(start2).unwrap());
-        Ok(())
+pub struct Solution;
+
+impl Solver for Solution {
+    type Parsed = Input;
+
+    fn parse(input: &str) -> eyre::Result<Self::Parsed> {
+        input.parse()
     }
-        .instrument(day_span.or_current())
-        .await
+
+    fn part1(input: &Self::Parsed) -> eyre::Result<Answer> {
+        process_part1(input)
+    }
+
+    fn part2(input: &Self::Parsed) -> eyre::Result<Answer> {
+        process_part2(input)
+    }
+}
+
+register_day! {
+    day: 17,
+    status: Implemented,
+    solver: Solution,
 }
 
 #[cfg(test)]
@@ -289,7 +282,7 @@ mod test {
         let input = example_1_input();
 
         let result = process_part1(&input).unwrap();
-        assert_eq!(vec![4,6,3,5,6,3,5,2,1,0], result);
+        assert_eq!(Answer::Bytes(vec![4,6,3,5,6,3,5,2,1,0]), result);
     }
 
     fn example_2_input() -> Input {
@@ -306,13 +299,13 @@ mod test {
         let input = example_2_input();
 
         let result = process_part1(&input).unwrap();
-        assert_eq!(vec![0,3,5,4,3,0], result);
+        assert_eq!(Answer::Bytes(vec![0,3,5,4,3,0]), result);
     }
 
     #[test]
     pub fn test_example_part2() {
         let input = example_2_input();
         let result = process_part2(&input).unwrap();
-        assert_eq!(117440, result);
+        assert_eq!(Answer::Unsigned(117440), result);
     }
 }
@@ -1,13 +1,28 @@
-use std::cmp::Ordering;
-use std::collections::{BinaryHeap, VecDeque};
+use std::collections::HashSet;
 use std::str::FromStr;
-use std::time::SystemTime;
 use eyre::eyre;
-use tracing::{debug, info, Instrument, Level, span, trace};
-use crate::days::Day;
-use crate::days::util::{Coordinate, Direction, ParsedGrid};
-
-pub const DAY: Day = Day(18);
+// use owo_colors::Style;
+use crate::days::solver::{register_day, Answer, Solver};
+use crate::days::approach::{Approach, ApproachHistory};
+use crate::days::util::{Coordinate, ParsedGrid};
+// use crate::days::util::display::heatmap;
+use crate::days::util::parse::ints_fixed;
+use crate::days::util::search::{astar, first_breaking_change, Strategy};
+
+pub const APPROACH_HISTORY: ApproachHistory = ApproachHistory {
+    day: DAY,
+    approaches: &[
+        Approach { name: "incremental", description: "Drop the bytes one after another and only search a new path when the current one gets cut" },
+        Approach { name: "union-find", description: "Drop all bytes and remove them again from the last one until the exit connects to the start" },
+        Approach { name: "binary-search", description: "Bisect the number of dropped bytes, searching a path every time" },
+    ],
+    active: "union-find",
+    solve: |raw_input| {
+        let input: Input = raw_input.parse()?;
+        process_part2::<71, 71, 1024>(&input)?;
+        Ok(())
+    },
+};
 
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub struct Input {
@@ -21,9 +36,8 @@ impl FromStr for Input {
         let byte_locations = s.lines()
             .map(str::trim)
             .filter(|line| !line.is_empty())
-            .map(|line| line.split_once(",").unwrap())
-            .map(|(x, y)| (x.parse().unwrap(), y.parse().unwrap()))
-            .collect();
+            .map(|line| ints_fixed(line).map(|[x, y]| (x, y)))
+            .collect::<eyre::Result<_>>()?;
 
         Ok(Self {
             byte_locations,
@@ -31,257 +45,77 @@ impl FromStr for Input {
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, Hash)]
-pub struct Node {
-    position: usize,
-    distance: usize,
-}
-
-impl Ord for Node {
-    fn cmp(&self, other: &Self) -> Ordering {
-        other.distance.cmp(&self.distance)
+impl Input {
+    pub fn byte_locations(&self) -> &[(usize, usize)] {
+        &self.byte_locations
     }
 }
 
-impl PartialOrd for Node {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
+pub fn process_part1<const WIDTH: usize, const HEIGHT: usize, const INITIAL: usize>(input: &Input) -> eyre::Result<Answer> {
+    let corrupted = input.byte_locations[0..INITIAL].iter()
+        .map(|&(x, y)| Coordinate(x as isize, y as isize))
+        .collect::<HashSet<_>>();
+    let grid = ParsedGrid::from_fn(WIDTH, HEIGHT, |coordinate| corrupted.contains(&coordinate));
+
+    let grid = &grid;
+    let neighbours = |&position: &usize| grid.neighbors4(position)
+        .filter(move |&(position, _)| !grid.as_slice()[position])
+        .map(|(position, _)| (position, 1));
+    let end = grid.index_to_coordinate(WIDTH * HEIGHT - 1);
+    let heuristic = |&position: &usize| grid.index_to_coordinate(position).manhattan(end);
+    let (distance, _) = astar(0, neighbours, heuristic, |&position| position == WIDTH * HEIGHT - 1)
+        .ok_or(eyre!("The exit can't be reached"))?;
+
+    // let distances = grid.flood(0, |corrupted| corrupted);
+    // println!("{}", heatmap(&distances, WIDTH, |position| grid.as_slice()[position].then(|| Style::new().white())));
+
+    Ok(distance.into())
 }
 
-pub fn process_part1<const WIDTH: usize, const HEIGHT: usize, const INITIAL: usize>(input: &Input) -> eyre::Result<usize> {
-    let mut grid = ParsedGrid::new(WIDTH, HEIGHT);
-
-    for (x, y) in &input.byte_locations[0..INITIAL] {
-        grid.as_mut_slice()[y * WIDTH + x] = true;
-    }
-
-    let mut to_visit = BinaryHeap::new();
-    let mut distances = vec![usize::MAX; grid.as_slice().len()];
-
-    distances[0] = 0;
-    to_visit.push(Node { position: 0, distance: 0 });
-
-    while let Some(Node { position, distance }) = to_visit.pop() {
-        for direction in Direction::ALL {
-            let position = if let Ok(position) = grid.offset_index(position, direction.into()) {
-                position
-            } else {
-                continue;
-            };
-            let distance = distance + 1;
-
-            if distance < distances[position] && !grid.as_slice()[position] {
-                distances[position] = distance;
-                to_visit.push(Node { position, distance });
-            }
-        }
+pub fn process_part2<const WIDTH: usize, const HEIGHT: usize, const INITIAL: usize>(input: &Input) -> eyre::Result<Answer> {
+    let grid = ParsedGrid::<bool>::new(WIDTH, HEIGHT);
+    let corruptions = input.byte_locations.iter()
+        .map(|(x, y)| y * WIDTH + x)
+        .collect::<Vec<_>>();
+
+    let strategy = match APPROACH_HISTORY.selected() {
+        "incremental" => Strategy::Incremental,
+        "binary-search" => Strategy::BinarySearch,
+        _ => Strategy::ReverseUnionFind,
+    };
+    let index = first_breaking_change(&grid, &corruptions, 0, WIDTH * HEIGHT - 1, strategy)
+        .ok_or(eyre!("Could not find any corruption that blocks the path"))?;
+    if index < INITIAL {
+        return Err(eyre!("The path is already blocked by the first {INITIAL} corruptions"));
     }
 
-    // let max_length = distances.iter().filter(|distance| **distance != usize::MAX).max().unwrap().ilog10() + 1;
-    // println!("{}", distances.iter()
-    //     .zip(grid.as_slice())
-    //     .map(|(&distance, &corrupted)| {
-    //         let cell_length = if corrupted || distance == usize::MAX {
-    //             1
-    //         } else if distance == 0 {
-    //             1
-    //         } else {
-    //             distance.ilog10() + 1
-    //         };
-    //         let cell = if corrupted {
-    //             "#".white().to_string()
-    //         } else if distance == usize::MAX {
-    //             "#".bold().bright_red().to_string()
-    //         } else {
-    //             distance.to_string()
-    //         };
-    //         format!("{}{cell}", " ".repeat((max_length - cell_length) as usize))
-    //     })
-    //     .chunks(WIDTH)
-    //     .into_iter()
-    //     .map(|mut line| line.join(" "))
-    //     .join("\n")
-    // );
-
-    Ok(distances[WIDTH * HEIGHT - 1])
+    let (x, y) = input.byte_locations[index];
+    Ok(Answer::Coord(x as isize, y as isize))
 }
 
-pub fn process_part2<const WIDTH: usize, const HEIGHT: usize, const INITIAL: usize>(input: &Input) -> eyre::Result<Coordinate> {
-    let mut grid = ParsedGrid::new(WIDTH, HEIGHT);
-
-    for (x, y) in &input.byte_locations[0..INITIAL] {
-        grid.as_mut_slice()[y * WIDTH + x] = true;
-    }
-
-    let mut to_visit = VecDeque::new();
-    let mut distances = vec![usize::MAX; grid.as_slice().len()];
-
-    distances[0] = 0;
-    to_visit.push_back(Node { position: 0, distance: 0 });
+pub struct Solution;
 
-    while let Some(Node { position, distance }) = to_visit.pop_front() {
-        for direction in Direction::ALL {
-            let position = if let Ok(position) = grid.offset_index(position, direction.into()) {
-                position
-            } else {
-                continue;
-            };
-            let distance = distance + 1;
+impl Solver for Solution {
+    type Parsed = Input;
 
-            if distance < distances[position] && !grid.as_slice()[position] {
-                distances[position] = distance;
-                to_visit.push_back(Node { position, distance });
-            }
-        }
+    fn parse(input: &str) -> eyre::Result<Self::Parsed> {
+        input.parse()
     }
 
-    // let max_length = distances.iter().filter(|distance| **distance != usize::MAX).max().unwrap().ilog10() + 1;
-    // println!("{}", distances.iter()
-    //     .zip(grid.as_slice())
-    //     .map(|(&distance, &corrupted)| {
-    //         let cell_length = if corrupted || distance == usize::MAX || distance == 0 {
-    //             1
-    //         } else {
-    //             distance.ilog10() + 1
-    //         };
-    //         let cell = if corrupted {
-    //             "#".white().to_string()
-    //         } else if distance == usize::MAX {
-    //             "#".bold().bright_red().to_string()
-    //         } else {
-    //             distance.to_string()
-    //         };
-    //         format!("{}{cell}", " ".repeat((max_length - cell_length) as usize))
-    //     })
-    //     .chunks(WIDTH)
-    //     .into_iter()
-    //     .map(|mut line| line.join(" "))
-    //     .join("\n")
-    // );
-
-    for (x, y) in &input.byte_locations[INITIAL..] {
-        let coordinate = Coordinate(*x as isize, *y as isize);
-        // println!("Corrupting {coordinate}");
-        let corruption_position = *y * WIDTH + *x;
-        grid.as_mut_slice()[corruption_position] = true;
-        let blocked_distance = distances[corruption_position];
-        let rescan = distances.iter_mut()
-            .enumerate()
-            .filter_map(|(position, distance)| {
-                if grid.as_slice()[position] {
-                    None
-                } else if *distance > blocked_distance {
-                    *distance = usize::MAX;
-                    None
-                } else if blocked_distance != usize::MAX && *distance == blocked_distance {
-                    Some((position, *distance))
-                } else {
-                    None
-                }
-            })
-            .map(|(position, distance)| Node { position, distance });
-        to_visit.extend(rescan);
-
-        // let max_length = distances.iter().filter(|distance| **distance != usize::MAX).max().unwrap().ilog10() + 1;
-        // println!("{}\n", distances.iter()
-        //     .zip(grid.as_slice())
-        //     .enumerate()
-        //     .map(|(position, (&distance, &corrupted))| {
-        //         let cell_length = if corrupted || distance == usize::MAX || distance == 0 {
-        //             1
-        //         } else {
-        //             distance.ilog10() + 1
-        //         };
-        //         let cell = if position == corruption_position {
-        //             "#".bold().bright_yellow().to_string()
-        //         } else if corrupted {
-        //             "#".white().to_string()
-        //         } else if distance == usize::MAX {
-        //             "#".bold().bright_red().to_string()
-        //         } else {
-        //             distance.to_string()
-        //         };
-        //         format!("{}{cell}", " ".repeat((max_length - cell_length) as usize))
-        //     })
-        //     .chunks(WIDTH)
-        //     .into_iter()
-        //     .map(|mut line| line.join(" "))
-        //     .join("\n")
-        // );
-
-        while let Some(Node { position, distance }) = to_visit.pop_front() {
-            for direction in Direction::ALL {
-                let position = if let Ok(position) = grid.offset_index(position, direction.into()) {
-                    position
-                } else {
-                    continue;
-                };
-                let distance = distance + 1;
-
-                if distance < distances[position] && !grid.as_slice()[position] {
-                    distances[position] = distance;
-                    to_visit.push_back(Node { position, distance });
-                }
-            }
-        }
-
-        // let max_length = distances.iter().filter(|distance| **distance != usize::MAX).max().unwrap().ilog10() + 1;
-        // println!("{}", distances.iter()
-        //     .zip(grid.as_slice())
-        //     .map(|(&distance, &corrupted)| {
-        //         let cell_length = if corrupted || distance == usize::MAX || distance == 0 {
-        //             1
-        //         } else {
-        //             distance.ilog10() + 1
-        //         };
-        //         let cell = if corrupted {
-        //             "#".white().to_string()
-        //         } else if distance == usize::MAX {
-        //             "#".bold().bright_red().to_string()
-        //         } else {
-        //             distance.to_string()
-        //         };
-        //         format!("{}{cell}", " ".repeat((max_length - cell_length) as usize))
-        //     })
-        //     .chunks(WIDTH)
-        //     .into_iter()
-        //     .map(|mut line| line.join(" "))
-        //     .join("\n")
-        // );
-
-        if distances[WIDTH * HEIGHT - 1] == usize::MAX {
-            return Ok(coordinate);
-        }
+    fn part1(input: &Self::Parsed) -> eyre::Result<Answer> {
+        process_part1::<71, 71, 1024>(input)
     }
 
-    Err(eyre!("Could not find any corruption that blocks the path"))
+    fn part2(input: &Self::Parsed) -> eyre::Result<Answer> {
+        process_part2::<71, 71, 1024>(input)
+    }
 }
 
-pub async fn run() -> eyre::Result<()> {
-    let day_span = span!(Level::ERROR, "", "{}", DAY);
-    async {
-        info!("Running {DAY}");
-
-        let raw_input = super::get_input(DAY).await?;
-        trace!(raw_input);
-
-        let input = raw_input.parse()?;
-        debug!(?input);
-
-        let start1 = SystemTime::now();
-        let result1 = process_part1::<71, 71, 1024>(&input)?;
-        let end1 = SystemTime::now();
-        let start2 = SystemTime::now();
-        let result2 = process_part2::<71, 71, 1024>(&input)?;
-        let end2 = SystemTime::now();
-        println!("{DAY} result:");
-        println!("  part 1: {result1} in {:?}", end1.duration_since(start1).unwrap());
-        println!("  part 2: {result2} in {:?}", end2.duration_since(start2).unwrap());
-        Ok(())
-    }
-        .instrument(day_span.or_current())
-        .await
+register_day! {
+    day: 18,
+    status: Implemented,
+    solver: Solution,
+    approach_history: APPROACH_HISTORY,
 }
 
 #[cfg(test)]
@@ -318,7 +152,7 @@ mod test {
                             ".parse().unwrap();
 
         let result = process_part1::<7, 7, 12>(&input).unwrap();
-        assert_eq!(22, result);
+        assert_eq!(Answer::Unsigned(22), result);
     }
 
     #[test]
@@ -351,6 +185,6 @@ mod test {
                             ".parse().unwrap();
 
         let result = process_part2::<7, 7, 12>(&input).unwrap();
-        assert_eq!(Coordinate(6, 1), result);
+        assert_eq!(Answer::Coord(6, 1), result);
     }
 }
@@ -1,10 +1,22 @@
 use std::str::FromStr;
-use std::time::SystemTime;
-use cached::proc_macro::cached;
-use tracing::{debug, info, Instrument, Level, span, trace};
-use crate::days::Day;
-
-pub const DAY: Day = Day(19);
+use crate::days::solver::{register_day, Answer, Solver};
+use crate::days::approach::{Approach, ApproachHistory};
+use crate::days::util::memo::Memo;
+use crate::days::util::par_map_with;
+
+pub const APPROACH_HISTORY: ApproachHistory = ApproachHistory {
+    day: DAY,
+    approaches: &[
+        Approach { name: "sequential", description: "Count the arrangements of one pattern after another, sharing one memo of their suffixes" },
+        Approach { name: "parallel", description: "Count the arrangements of the patterns on all cores, every thread with its own memo" },
+    ],
+    active: "parallel",
+    solve: |raw_input| {
+        let input: Input = raw_input.parse()?;
+        process_part2(&input)?;
+        Ok(())
+    },
+};
 
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub struct Input {
@@ -37,68 +49,79 @@ impl FromStr for Input {
     }
 }
 
-#[cached(key = "String", convert = r#"{ pattern.to_string() }"#)]
-fn count_pattern_combinations(pattern: &str, available_towels: &[String]) -> usize {
-    available_towels
+fn count_pattern_combinations<'a>(pattern: &'a str, available_towels: &[String], memo: &mut Memo<&'a str, usize>) -> usize {
+    if pattern.is_empty() {
+        return 1;
+    }
+
+    memo.get_or_insert_with(pattern, |memo| available_towels
         .iter()
-        .map(|towel|
-            if let Some(rest) = pattern.strip_prefix(towel) {
-                if rest.is_empty() {
-                    1
-                } else {
-                    count_pattern_combinations(rest, available_towels)
-                }
-            } else {
-                0
-            }
-        )
+        .filter_map(|towel| pattern.strip_prefix(towel.as_str()))
+        .map(|rest| count_pattern_combinations(rest, available_towels, memo))
         .sum()
+    )
 }
 
-pub fn process_part1(input: &Input) -> eyre::Result<usize> {
-    let result = input.patterns.iter()
-        .filter(|order| {
-            count_pattern_combinations(order.as_str(), &input.available_towels) > 0
-        })
+/// The number of arrangements of every pattern, counted on a single thread.
+pub fn combinations_sequential(input: &Input) -> Vec<usize> {
+    let mut memo = Memo::new();
+    input.patterns.iter()
+        .map(|pattern| count_pattern_combinations(pattern, &input.available_towels, &mut memo))
+        .collect()
+}
+
+/// The number of arrangements of every pattern, counted on all cores.
+pub fn combinations_parallel(input: &Input) -> Vec<usize> {
+    par_map_with(&input.patterns, Memo::new, |memo, pattern| {
+        count_pattern_combinations(pattern, &input.available_towels, memo)
+    })
+}
+
+fn combinations(input: &Input) -> Vec<usize> {
+    match APPROACH_HISTORY.selected() {
+        "sequential" => combinations_sequential(input),
+        _ => combinations_parallel(input),
+    }
+}
+
+pub fn process_part1(input: &Input) -> eyre::Result<Answer> {
+    let result = combinations(input).into_iter()
+        .filter(|&combinations| combinations > 0)
         .count();
 
-    Ok(result)
+    Ok(result.into())
 }
 
-pub fn process_part2(input: &Input) -> eyre::Result<usize> {
-    let result = input.patterns.iter()
-        .map(|order| {
-            count_pattern_combinations(order.as_str(), &input.available_towels)
-        })
+pub fn process_part2(input: &Input) -> eyre::Result<Answer> {
+    let result = combinations(input).into_iter()
         .sum();
 
-    Ok(result)
+    Ok(result.into())
 }
 
-pub async fn run() -> eyre::Result<()> {
-    let day_span = span!(Level::ERROR, "", "{}", DAY);
-    async {
-        info!("Running {DAY}");
-
-        let raw_input = super::get_input(DAY).await?;
-        trace!(raw_input);
-
-        let input = raw_input.parse()?;
-        debug!(?input);
-
-        let start1 = SystemTime::now();
-        let result1 = process_part1(&input)?;
-        let end1 = SystemTime::now();
-        let start2 = SystemTime::now();
-        let result2 = process_part2(&input)?;
-        let end2 = SystemTime::now();
-        println!("{DAY} result:");
-        println!("  part 1: {result1} in {:?}", end1.duration_since(start1).unwrap());
-        println!("  part 2: {result2} in {:?}", end2.duration_since(start2).unwrap());
-        Ok(())
+pub struct Solution;
+
+impl Solver for Solution {
+    type Parsed = Input;
+
+    fn parse(input: &str) -> eyre::Result<Self::Parsed> {
+        input.parse()
+    }
+
+    fn part1(input: &Self::Parsed) -> eyre::Result<Answer> {
+        process_part1(input)
+    }
+
+    fn part2(input: &Self::Parsed) -> eyre::Result<Answer> {
+        process_part2(input)
     }
-        .instrument(day_span.or_current())
-        .await
+}
+
+register_day! {
+    day: 19,
+    status: Implemented,
+    solver: Solution,
+    approach_history: APPROACH_HISTORY,
 }
 
 #[cfg(test)]
@@ -124,7 +147,7 @@ mod test {
         let input = example_input();
 
         let result = process_part1(&input).unwrap();
-        assert_eq!(6, result);
+        assert_eq!(Answer::Unsigned(6), result);
     }
 
     #[test]
@@ -132,6 +155,14 @@ mod test {
         let input = example_input();
 
         let result = process_part2(&input).unwrap();
-        assert_eq!(16, result);
+        assert_eq!(Answer::Unsigned(16), result);
+    }
+
+    #[test]
+    pub fn test_approaches_agree() {
+        let input = example_input();
+
+        assert_eq!(vec![2, 1, 4, 6, 0, 1, 2, 0], combinations_sequential(&input));
+        assert_eq!(combinations_sequential(&input), combinations_parallel(&input));
     }
 }
@@ -1,11 +1,8 @@
 use std::str::FromStr;
-use std::time::SystemTime;
 use eyre::{anyhow, WrapErr};
 use itertools::Itertools;
-use tracing::{debug, info, Instrument, Level, span, trace};
-use crate::days::Day;
-
-pub const DAY: Day = Day(2);
+use tracing::{debug, trace};
+use crate::days::solver::{register_day, Answer, Solver};
 
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub struct Input {
@@ -73,7 +70,7 @@ fn tolerate(report: &[usize]) -> bool {
     }
 }
 
-pub fn process_part1(input: &Input) -> eyre::Result<String> {
+pub fn process_part1(input: &Input) -> eyre::Result<Answer> {
     let safe_count = input.reports.iter()
         .map(|report| unsafe_index(report.iter()).is_none())
         .enumerate()
@@ -85,10 +82,10 @@ pub fn process_part1(input: &Input) -> eyre::Result<String> {
         .filter(|&(_, safe)| safe)
         .count();
 
-    Ok(safe_count.to_string())
+    Ok(safe_count.into())
 }
 
-pub fn process_part2(input: &Input) -> eyre::Result<String> {
+pub fn process_part2(input: &Input) -> eyre::Result<Answer> {
     let safe_count = input.reports.iter()
         .enumerate()
         .map(|(row, report)| {
@@ -103,33 +100,31 @@ pub fn process_part2(input: &Input) -> eyre::Result<String> {
         .filter(|&(_, safe)| safe)
         .count();
 
-    Ok(safe_count.to_string())
+    Ok(safe_count.into())
 }
 
-pub async fn run() -> eyre::Result<()> {
-    let day_span = span!(Level::ERROR, "", "{}", DAY);
-    async {
-        info!("Running {DAY}");
-
-        let raw_input = super::get_input(DAY).await?;
-        trace!(raw_input);
-
-        let input = raw_input.parse()?;
-        debug!(?input);
-
-        let start1 = SystemTime::now();
-        let result1 = process_part1(&input)?;
-        let end1 = SystemTime::now();
-        let start2 = SystemTime::now();
-        let result2 = process_part2(&input)?;
-        let end2 = SystemTime::now();
-        println!("{DAY} result:");
-        println!("  part 1: {result1} in {:?}", end1.duration_since(start1).unwrap());
-        println!("  part 2: {result2} in {:?}", end2.duration_since(start2).unwrap());
-        Ok(())
+pub struct Solution;
+
+impl Solver for Solution {
+    type Parsed = Input;
+
+    fn parse(input: &str) -> eyre::Result<Self::Parsed> {
+        input.parse()
+    }
+
+    fn part1(input: &Self::Parsed) -> eyre::Result<Answer> {
+        process_part1(input)
     }
-        .instrument(day_span.or_current())
-        .await
+
+    fn part2(input: &Self::Parsed) -> eyre::Result<Answer> {
+        process_part2(input)
+    }
+}
+
+register_day! {
+    day: 2,
+    status: Implemented,
+    solver: Solution,
 }
 
 #[cfg(test)]
@@ -149,9 +144,9 @@ mod test {
         let input = raw_input.parse().unwrap();
 
         let result1 = process_part1(&input).unwrap();
-        assert_eq!("2", result1);
+        assert_eq!(Answer::Unsigned(2), result1);
 
         let result2 = process_part2(&input).unwrap();
-        assert_eq!("4", result2);
+        assert_eq!(Answer::Unsigned(4), result2);
     }
 }
@@ -1,15 +1,10 @@
 use std::convert::identity;
 use std::rc::Rc;
 use std::str::FromStr;
-use std::time::SystemTime;
-use eyre::eyre;
-// use itertools::Itertools;
-// use owo_colors::OwoColorize;
-use tracing::{debug, info, Instrument, Level, span, trace};
-use crate::days::Day;
-use crate::days::util::{Coordinate, Direction, Grid};
-
-pub const DAY: Day = Day(20);
+// use owo_colors::Style;
+use crate::days::solver::{register_day, Answer, Solver};
+use crate::days::util::{Coordinate, Direction, Grid, Marker};
+// use crate::days::util::display::heatmap;
 
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub struct Input {
@@ -22,12 +17,12 @@ impl FromStr for Input {
     type Err = eyre::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let grid: Grid = s.parse()?;
-
-        let start = grid.as_slice().iter().position(|symbol| *symbol == 'S')
-            .ok_or(eyre!("Failed to find start"))?;
-        let end = grid.as_slice().iter().position(|symbol| *symbol == 'E')
-            .ok_or(eyre!("Failed to find end"))?;
+        let (mut start, mut end) = (Marker::new('S'), Marker::new('E'));
+        let grid = Grid::from_str_with(s, |tile, coordinate| {
+            Ok::<_, eyre::Error>(if start.check(tile, coordinate) || end.check(tile, coordinate) { '.' } else { tile })
+        })?;
+        let start = start.index(&grid)?;
+        let end = end.index(&grid)?;
 
         Ok(Self {
             grid,
@@ -37,56 +32,28 @@ impl FromStr for Input {
     }
 }
 
-pub fn process_part1<const SAVE: usize>(input: &Input) -> eyre::Result<usize> {
+pub fn process_part1<const SAVE: usize>(input: &Input) -> eyre::Result<Answer> {
     let distances = input.grid.flood(input.start, |tile| tile == '#');
-    // let max_distance = distances.iter()
-    //     .filter(|&&distance| distance != usize::MAX)
-    //     .map(|distance| if *distance == 0 { 1 } else { distance.ilog10() + 1 })
-    //     .max()
-    //     .unwrap();
 
-    let result = input.grid.as_slice().iter()
-        .enumerate()
-        .filter(|&(_, &tile)| tile == '.' || tile == 'S')
-        .map(|(anchor, _)| {
-            Direction::ALL
-                .iter()
-                .filter_map(|direction|
-                    input.grid.offset_index(anchor, (*direction).into()).ok()
-                        .map(|position| (direction, position))
-                )
-                .flat_map(|(direction, position)| {
-                    [direction.clone(), direction.rotate90()]
+    let result = input.grid.positions(|&tile| tile == '.')
+        .map(|anchor| {
+            input.grid.neighbors4(anchor)
+                .flat_map(|(position, direction)| {
+                    [direction, direction.rotate90()]
                         .into_iter()
                         .filter_map(move |direction|
-                            input.grid.offset_index(position, direction.into()).ok()
+                            input.grid.offset_index(position, direction.into())
                                 .map(|position| position)
                         )
                 })
-                // .inspect(|position| {
+                // .inspect(|&position| {
                 //     if anchor == input.start {
-                //         let max_row = distances.len().ilog10();
                 //         println!("from {anchor} to {position}");
-                //         println!("{}", distances.as_slice().iter().zip(input.grid.as_slice()).enumerate().chunks(input.grid.width()).into_iter().enumerate().map(|(row, distances)| {
-                //             format!("{}{}{}", " ".repeat((max_row - if row == 0 { 0 } else { (row * input.grid.width()).ilog10() }) as usize), row * input.grid.width(), distances.map(|(tile_position, (distance, tile))| {
-                //                 let cell = if *distance == usize::MAX {
-                //                     format!("{}{}", " ".repeat(max_distance as usize), tile)
-                //                 } else if *distance == 0 {
-                //                     format!("{}{}", " ".repeat(max_distance as usize), 0)
-                //                 } else {
-                //                     format!("{}{}", " ".repeat((max_distance - distance.ilog10()) as usize), distance)
-                //                 };
-                //                 if tile_position == anchor {
-                //                     cell.bright_green().bold().to_string()
-                //                 } else if tile_position == *position {
-                //                     cell.bright_red().bold().to_string()
-                //                 } else if *tile == '#' {
-                //                     cell.white().to_string()
-                //                 } else {
-                //                     cell.bright_white().bold().to_string()
-                //                 }
-                //             }).collect::<String>())
-                //         }).join("\n"));
+                //         println!("{}", heatmap(&distances, input.grid.width(), |tile| match tile {
+                //             tile if tile == anchor => Some(Style::new().bright_green().bold()),
+                //             tile if tile == position => Some(Style::new().bright_red().bold()),
+                //             _ => None,
+                //         }));
                 //     }
                 // })
                 .filter(|&position| input.grid.as_slice()[position] != '#')
@@ -95,7 +62,7 @@ pub fn process_part1<const SAVE: usize>(input: &Input) -> eyre::Result<usize> {
         })
         .sum();
 
-    Ok(result)
+    Ok(result.into())
 }
 
 pub struct Offsets<'grid, const MAX: usize> {
@@ -130,8 +97,8 @@ impl<const MAX: usize> Iterator for Offsets<'_, MAX> {
             return None;
         }
 
-        let offset = self.straight * self.straight_offset as isize + self.queer * self.queer_offset as isize;
-        let distance = self.straight_offset + self.queer_offset;
+        let offset = self.straight_offset as isize * self.straight + self.queer_offset as isize * self.queer;
+        let distance = offset.manhattan(Coordinate(0, 0));
         let position = self.grid.offset_index(self.anchor, offset);
 
         if self.queer_offset == 0 {
@@ -145,42 +112,19 @@ impl<const MAX: usize> Iterator for Offsets<'_, MAX> {
             self.queer_offset -= 1;
         }
 
-        Some(position.ok().map(|position| (position, distance)))
+        Some(position.map(|position| (position, distance)))
     }
 }
 
-pub fn process_part2<const SAVE: usize>(input: &Input) -> eyre::Result<usize> {
+pub fn process_part2<const SAVE: usize>(input: &Input) -> eyre::Result<Answer> {
     let distances = Rc::new(input.grid.flood(input.start, |tile| tile == '#'));
-    // let max_distance = distances.iter()
-    //     .filter(|&&distance| distance != usize::MAX)
-    //     .map(|distance| if *distance == 0 { 1 } else { distance.ilog10() + 1 })
-    //     .max()
-    //     .unwrap();
+    // println!("{}", heatmap(&distances, input.grid.width(), |_| None));
 
-    // println!("{}", distances.as_slice().iter().zip(input.grid.as_slice()).chunks(input.grid.width()).into_iter().map(|distances| {
-    //     distances.map(|(distance, tile)| {
-    //         if *distance == usize::MAX {
-    //             format!("{}{}", " ".repeat(max_distance as usize), tile.white().to_string())
-    //         } else if *distance == 0 {
-    //             format!("{}{}", " ".repeat(max_distance as usize), 0)
-    //         } else {
-    //             format!("{}{}", " ".repeat((max_distance - distance.ilog10()) as usize), distance)
-    //         }
-    //     }).collect::<String>()
-    // }).join("\n"));
-
-    let skips = input.grid.as_slice().iter()
-        .enumerate()
-        .filter(|&(_, &tile)| tile == '.' || tile == 'S')
-        .flat_map(|(anchor, _)| {
+    let skips = input.grid.positions(|&tile| tile == '.')
+        .flat_map(|anchor| {
             let distances = distances.clone();
-            Direction::ALL
-                .iter()
-                .filter_map(move |direction|
-                    input.grid.offset_index(anchor, (*direction).into()).ok()
-                        .map(|position| (direction, position))
-                )
-                .flat_map(|(direction, position)| Offsets::<19>::new(position, *direction, &input.grid))
+            input.grid.neighbors4(anchor)
+                .flat_map(|(position, direction)| Offsets::<19>::new(position, direction, &input.grid))
                 .filter_map(identity)
                 .filter(|&(position, _)| input.grid.as_slice()[position] != '#')
                 .map(|(position, distance)| (position, distance + 1))
@@ -194,61 +138,41 @@ pub fn process_part2<const SAVE: usize>(input: &Input) -> eyre::Result<usize> {
         .collect::<Vec<_>>();
 
     // for &(from, to, saved) in &skips {
-    //     // if anchor == input.start {
-    //         let max_row = distances.len().ilog10();
-    //         println!("from {from} to {to} distance {} saving {}", distances[to] as isize - distances[from] as isize, saved);
-    //         println!("{}", distances.as_slice().iter().zip(input.grid.as_slice()).enumerate().chunks(input.grid.width()).into_iter().enumerate().map(|(row, distances)| {
-    //             format!("{}{}{}", " ".repeat((max_row - if row == 0 { 0 } else { (row * input.grid.width()).ilog10() }) as usize), row * input.grid.width(), distances.map(|(tile_position, (distance, tile))| {
-    //                 let cell = if *distance == usize::MAX {
-    //                     format!("{}{}", " ".repeat(max_distance as usize), tile)
-    //                 } else if *distance == 0 {
-    //                     format!("{}{}", " ".repeat(max_distance as usize), 0)
-    //                 } else {
-    //                     format!("{}{}", " ".repeat((max_distance - distance.ilog10()) as usize), distance)
-    //                 };
-    //                 if tile_position == from {
-    //                     cell.bright_green().bold().to_string()
-    //                 } else if tile_position == to {
-    //                     cell.bright_red().bold().to_string()
-    //                 } else if *tile == '#' {
-    //                     cell.white().to_string()
-    //                 } else {
-    //                     cell.bright_white().bold().to_string()
-    //                 }
-    //             }).collect::<String>())
-    //         }).join("\n"));
-    //     // }
+    //     println!("from {from} to {to} distance {} saving {saved}", distances[to] as isize - distances[from] as isize);
+    //     println!("{}", heatmap(&distances, input.grid.width(), |tile| match tile {
+    //         tile if tile == from => Some(Style::new().bright_green().bold()),
+    //         tile if tile == to => Some(Style::new().bright_red().bold()),
+    //         _ => None,
+    //     }));
     // }
 
     let result = skips.len();
 
-    Ok(result)
+    Ok(result.into())
 }
 
-pub async fn run() -> eyre::Result<()> {
-    let day_span = span!(Level::ERROR, "", "{}", DAY);
-    async {
-        info!("Running {DAY}");
+pub struct Solution;
+
+impl Solver for Solution {
+    type Parsed = Input;
 
-        let raw_input = super::get_input(DAY).await?;
-        trace!(raw_input);
+    fn parse(input: &str) -> eyre::Result<Self::Parsed> {
+        input.parse()
+    }
 
-        let input = raw_input.parse()?;
-        debug!(?input);
+    fn part1(input: &Self::Parsed) -> eyre::Result<Answer> {
+        process_part1::<100>(input)
+    }
 
-        let start1 = SystemTime::now();
-        let result1 = process_part1::<100>(&input)?;
-        let end1 = SystemTime::now();
-        let start2 = SystemTime::now();
-        let result2 = process_part2::<100>(&input)?;
-        let end2 = SystemTime::now();
-        println!("{DAY} result:");
-        println!("  part 1: {result1} in {:?}", end1.duration_since(start1).unwrap());
-        println!("  part 2: {result2} in {:?}", end2.duration_since(start2).unwrap());
-        Ok(())
+    fn part2(input: &Self::Parsed) -> eyre::Result<Answer> {
+        process_part2::<100>(input)
     }
-        .instrument(day_span.or_current())
-        .await
+}
+
+register_day! {
+    day: 20,
+    status: Implemented,
+    solver: Solution,
 }
 
 #[cfg(test)]
@@ -279,7 +203,7 @@ mod test {
         let input = example_input();
 
         let result = process_part1::<2>(&input).unwrap();
-        assert_eq!(14 + 14 + 2 + 4 + 2 + 3 + 5, result);
+        assert_eq!(Answer::Unsigned(14 + 14 + 2 + 4 + 2 + 3 + 5), result);
     }
 
     #[test]
@@ -287,6 +211,6 @@ mod test {
         let input = example_input();
 
         let result = process_part2::<50>(&input).unwrap();
-        assert_eq!(32 + 31 + 29 + 39 + 25 + 23 + 20 + 19 + 12 + 14 + 12 + 22 + 4 + 3, result);
+        assert_eq!(Answer::Unsigned(32 + 31 + 29 + 39 + 25 + 23 + 20 + 19 + 12 + 14 + 12 + 22 + 4 + 3), result);
     }
 }
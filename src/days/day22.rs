@@ -0,0 +1,140 @@
+use std::str::FromStr;
+use itertools::Itertools;
+use crate::days::solver::{register_day, Answer, Solver};
+use crate::days::util::Lines;
+
+/// Every secret is kept below this modulus
+const PRUNE: u64 = 16_777_216;
+/// The number of possible sequences of four price changes, each in `-9..=9`
+const SEQUENCES: usize = 19usize.pow(4);
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Secret(u64);
+
+impl Secret {
+    pub fn next(self) -> Self {
+        let mut secret = self.0;
+        secret = (secret ^ (secret * 64)) % PRUNE;
+        secret = (secret ^ (secret / 32)) % PRUNE;
+        secret = (secret ^ (secret * 2048)) % PRUNE;
+        Self(secret)
+    }
+
+    /// This secret and every one it evolves into.
+    pub fn evolve(self) -> impl Iterator<Item = Self> {
+        std::iter::successors(Some(self), |secret| Some(secret.next()))
+    }
+
+    pub fn price(self) -> u8 {
+        (self.0 % 10) as u8
+    }
+}
+
+impl FromStr for Secret {
+    type Err = eyre::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self(s.parse()?))
+    }
+}
+
+pub type Input = Lines<Secret>;
+
+pub fn process_part1(input: &Input) -> eyre::Result<Answer> {
+    let result = input.iter()
+        .map(|secret| secret.evolve().nth(2000).unwrap().0)
+        .sum();
+
+    Ok(Answer::Unsigned(result))
+}
+
+pub fn process_part2(input: &Input) -> eyre::Result<Answer> {
+    let mut bananas = vec![0usize; SEQUENCES];
+    let mut last_buyer = vec![usize::MAX; SEQUENCES];
+
+    for (buyer, secret) in input.iter().enumerate() {
+        let mut sequence = 0;
+        let changes = secret.evolve()
+            .take(2001)
+            .map(Secret::price)
+            .tuple_windows()
+            .enumerate();
+        for (change, (previous, price)) in changes {
+            let delta = price as i8 - previous as i8;
+            sequence = (sequence * 19 + (delta + 9) as usize) % SEQUENCES;
+            // the monkey only sells at the first occurrence of the sequence
+            if change >= 3 && last_buyer[sequence] != buyer {
+                last_buyer[sequence] = buyer;
+                bananas[sequence] += price as usize;
+            }
+        }
+    }
+
+    Ok(bananas.into_iter().max().unwrap_or(0).into())
+}
+
+pub struct Solution;
+
+impl Solver for Solution {
+    type Parsed = Input;
+
+    fn parse(input: &str) -> eyre::Result<Self::Parsed> {
+        input.parse()
+    }
+
+    fn part1(input: &Self::Parsed) -> eyre::Result<Answer> {
+        process_part1(input)
+    }
+
+    fn part2(input: &Self::Parsed) -> eyre::Result<Answer> {
+        process_part2(input)
+    }
+}
+
+register_day! {
+    day: 22,
+    status: Implemented,
+    solver: Solution,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    pub fn test_evolve() {
+        let secrets = Secret(123).evolve()
+            .skip(1)
+            .take(10)
+            .map(|secret| secret.0)
+            .collect::<Vec<_>>();
+        assert_eq!(vec![
+            15887950, 16495136, 527345, 704524, 1553684,
+            12683156, 11100544, 12249484, 7753432, 5908254,
+        ], secrets);
+    }
+
+    #[test]
+    pub fn test_example_part1() {
+        let input: Input = r"1
+                             10
+                             100
+                             2024
+                             ".parse().unwrap();
+
+        let result = process_part1(&input).unwrap();
+        assert_eq!(Answer::Unsigned(37327623), result);
+    }
+
+    #[test]
+    pub fn test_example_part2() {
+        let input: Input = r"1
+                             2
+                             3
+                             2024
+                             ".parse().unwrap();
+
+        let result = process_part2(&input).unwrap();
+        assert_eq!(Answer::Unsigned(23), result);
+    }
+}
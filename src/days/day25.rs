@@ -0,0 +1,186 @@
+use std::str::FromStr;
+use eyre::eyre;
+use itertools::Itertools;
+use crate::days::solver::{register_day, Answer, Solver};
+
+const WIDTH: usize = 5;
+const HEIGHT: usize = 7;
+
+/// The height of every column, not counting the full row at the base of locks and keys.
+pub type Heights = [u8; WIDTH];
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Schematic {
+    Lock(Heights),
+    Key(Heights),
+}
+
+impl FromStr for Schematic {
+    type Err = eyre::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let rows = s.lines()
+            .map(|line| line.trim())
+            .filter(|line| !line.is_empty())
+            .collect::<Vec<_>>();
+        if rows.len() != HEIGHT || rows.iter().any(|row| row.len() != WIDTH) {
+            return Err(eyre!("Schematic is not {WIDTH}x{HEIGHT}:\n{s}"));
+        }
+
+        let mut heights = [0; WIDTH];
+        for row in &rows[1..HEIGHT - 1] {
+            for (height, tile) in heights.iter_mut().zip(row.chars()) {
+                match tile {
+                    '#' => *height += 1,
+                    '.' => (),
+                    _ => return Err(eyre!("Invalid tile {tile} in schematic")),
+                }
+            }
+        }
+
+        match (rows[0], rows[HEIGHT - 1]) {
+            ("#####", ".....") => Ok(Self::Lock(heights)),
+            (".....", "#####") => Ok(Self::Key(heights)),
+            _ => Err(eyre!("Schematic is neither a lock nor a key:\n{s}")),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Input {
+    locks: Vec<Heights>,
+    keys: Vec<Heights>,
+}
+
+impl FromStr for Input {
+    type Err = eyre::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let schematics = s.lines()
+            .map(|line| line.trim())
+            .chunk_by(|line| line.is_empty())
+            .into_iter()
+            .filter(|(empty, _)| !empty)
+            .map(|(_, lines)| lines.join("\n").parse())
+            .collect::<Result<Vec<Schematic>, _>>()?;
+
+        let (locks, keys) = schematics.into_iter()
+            .partition_map(|schematic| match schematic {
+                Schematic::Lock(heights) => itertools::Either::Left(heights),
+                Schematic::Key(heights) => itertools::Either::Right(heights),
+            });
+
+        Ok(Self {
+            locks,
+            keys,
+        })
+    }
+}
+
+fn fits(lock: &Heights, key: &Heights) -> bool {
+    lock.iter()
+        .zip(key)
+        .all(|(lock, key)| (lock + key) as usize <= HEIGHT - 2)
+}
+
+pub fn process_part1(input: &Input) -> eyre::Result<Answer> {
+    let result = input.locks.iter()
+        .cartesian_product(&input.keys)
+        .filter(|(lock, key)| fits(lock, key))
+        .count();
+
+    Ok(result.into())
+}
+
+/// Day 25 has no second puzzle, its star is awarded for having all the others.
+pub fn process_part2(_input: &Input) -> eyre::Result<Answer> {
+    Ok(Answer::Text("Merry Christmas!".to_string()))
+}
+
+pub struct Solution;
+
+impl Solver for Solution {
+    type Parsed = Input;
+
+    fn parse(input: &str) -> eyre::Result<Self::Parsed> {
+        input.parse()
+    }
+
+    fn part1(input: &Self::Parsed) -> eyre::Result<Answer> {
+        process_part1(input)
+    }
+
+    fn part2(input: &Self::Parsed) -> eyre::Result<Answer> {
+        process_part2(input)
+    }
+}
+
+register_day! {
+    day: 25,
+    status: Implemented,
+    solver: Solution,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn example_input() -> Input {
+        r"#####
+          .####
+          .####
+          .####
+          .#.#.
+          .#...
+          .....
+
+          #####
+          ##.##
+          .#.##
+          ...##
+          ...#.
+          ...#.
+          .....
+
+          .....
+          #....
+          #....
+          #...#
+          #.#.#
+          #.###
+          #####
+
+          .....
+          .....
+          #.#..
+          ###..
+          ###.#
+          ###.#
+          #####
+
+          .....
+          .....
+          .....
+          #....
+          #.#..
+          #.#.#
+          #####
+          ".parse().unwrap()
+    }
+
+    #[test]
+    pub fn test_parse() {
+        let input = example_input();
+
+        assert_eq!(vec![[0, 5, 3, 4, 3], [1, 2, 0, 5, 3]], input.locks);
+        assert_eq!(vec![[5, 0, 2, 1, 3], [4, 3, 4, 0, 2], [3, 0, 2, 0, 1]], input.keys);
+    }
+
+    #[test]
+    pub fn test_example_part1() {
+        let input = example_input();
+
+        let result = process_part1(&input).unwrap();
+        assert_eq!(Answer::Unsigned(3), result);
+    }
+}
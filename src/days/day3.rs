@@ -1,11 +1,8 @@
 use std::ops::{Index, Range};
 use std::str::FromStr;
-use std::time::SystemTime;
 use eyre::{anyhow, WrapErr};
-use tracing::{debug, info, Instrument, Level, span, trace};
-use crate::days::Day;
-
-pub const DAY: Day = Day(3);
+use tracing::{debug, trace};
+use crate::days::solver::{register_day, Answer, Solver};
 
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 #[non_exhaustive]
@@ -128,7 +125,7 @@ impl FromStr for Input {
     }
 }
 
-pub fn process_part1(input: &Input) -> eyre::Result<String> {
+pub fn process_part1(input: &Input) -> eyre::Result<Answer> {
     let result = input.instructions.iter()
         .filter_map(|instruction|
             match instruction {
@@ -138,10 +135,10 @@ pub fn process_part1(input: &Input) -> eyre::Result<String> {
 )
         .sum::<usize>();
 
-    Ok(result.to_string())
+    Ok(result.into())
 }
 
-pub fn process_part2(input: &Input) -> eyre::Result<String> {
+pub fn process_part2(input: &Input) -> eyre::Result<Answer> {
     let filtered_instructions = input.instructions.iter()
         .scan(true, |execute, instruction| {
             match instruction {
@@ -162,37 +159,33 @@ pub fn process_part2(input: &Input) -> eyre::Result<String> {
         .collect::<Vec<_>>();
     debug!(?filtered_instructions);
 
-    let result = process_part1(&Input {
+    process_part1(&Input {
         instructions: filtered_instructions,
-    })?;
-
-    Ok(result.to_string())
+    })
 }
 
-pub async fn run() -> eyre::Result<()> {
-    let day_span = span!(Level::ERROR, "", "{}", DAY);
-    async {
-        info!("Running {DAY}");
-
-        let raw_input = super::get_input(DAY).await?;
-        trace!(raw_input);
-
-        let input = raw_input.parse()?;
-        debug!(?input);
-
-        let start1 = SystemTime::now();
-        let result1 = process_part1(&input)?;
-        let end1 = SystemTime::now();
-        let start2 = SystemTime::now();
-        let result2 = process_part2(&input)?;
-        let end2 = SystemTime::now();
-        println!("{DAY} result:");
-        println!("  part 1: {result1} in {:?}", end1.duration_since(start1).unwrap());
-        println!("  part 2: {result2} in {:?}", end2.duration_since(start2).unwrap());
-        Ok(())
+pub struct Solution;
+
+impl Solver for Solution {
+    type Parsed = Input;
+
+    fn parse(input: &str) -> eyre::Result<Self::Parsed> {
+        input.parse()
+    }
+
+    fn part1(input: &Self::Parsed) -> eyre::Result<Answer> {
+        process_part1(input)
+    }
+
+    fn part2(input: &Self::Parsed) -> eyre::Result<Answer> {
+        process_part2(input)
     }
-        .instrument(day_span.or_current())
-        .await
+}
+
+register_day! {
+    day: 3,
+    status: Implemented,
+    solver: Solution,
 }
 
 #[cfg(test)]
@@ -205,12 +198,12 @@ mod test {
         let input = raw_input.parse().unwrap();
 
         let result = process_part1(&input).unwrap();
-        assert_eq!("161", result);
+        assert_eq!(Answer::Unsigned(161), result);
 
         let raw_input = r#"xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))"#;
         let input = raw_input.parse().unwrap();
 
         let result = process_part2(&input).unwrap();
-        assert_eq!("48", result);
+        assert_eq!(Answer::Unsigned(48), result);
     }
 }
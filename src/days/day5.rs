@@ -1,128 +1,36 @@
-use std::cmp::Ordering;
 use std::collections::HashSet;
-use std::fmt::{Display, Formatter};
 use std::num::ParseIntError;
 use std::str::FromStr;
-use std::time::SystemTime;
-use eyre::{anyhow, eyre};
-use itertools::Itertools;
-use tracing::{debug, info, Instrument, Level, span, trace};
-use crate::days::Day;
+use eyre::{anyhow, eyre, WrapErr};
+use tracing::trace;
+use crate::days::solver::{register_day, Answer, Solver};
+use crate::days::util::toposort;
 
-pub const DAY: Day = Day(5);
-
-#[derive(Clone, Debug, Eq, PartialEq, Hash)]
-pub struct Graph {
-    sorted_nodes: Vec<u8>,
-    node_mapping: Vec<u8>,
-    adjacency_matrix: Vec<bool>,
-}
-
-impl Display for Graph {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        f.debug_list()
-            .entries(&self.sorted_nodes)
-            .finish()?;
-        writeln!(f)?;
-        let matrix = self.adjacency_matrix.iter()
-            .chunks(self.sorted_nodes.len())
-            .into_iter()
-            .map(|row|
-                row.map(|&adjacent| if adjacent { '1' } else { '0' }).join(" ")
-            )
-            .join("\n");
-        write!(f, "{matrix}")
-    }
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Input {
+    rules: Vec<(u8, u8)>,
+    manuals: Vec<Vec<u8>>,
 }
 
-impl Graph {
-    pub fn new(edges: &[(u8, u8)]) -> Self {
-        let sorted_nodes: Vec<u8> = edges.iter()
-            .map(|(from, to)| [from, to])
-            .flatten()
-            .cloned()
-            .unique()
-            .sorted()
-            .collect();
-        let node_mapping = (0..*sorted_nodes.last().unwrap())
-            .map(|node| sorted_nodes.binary_search(&node).unwrap_or(0) as u8)
-            .collect::<Vec<_>>();
-        let node_count = sorted_nodes.len();
-        let edges = edges.iter()
-            .map(|(from, to)| (sorted_nodes.binary_search(from).unwrap(), sorted_nodes.binary_search(to).unwrap()))
-            .collect::<Vec<_>>();
-
-        let mut adjacency_matrix = vec![false; node_count * node_count];
-        for &(from, to) in &edges {
-            adjacency_matrix[from * node_count + to] = true;
-        }
-
-        let edge_count = adjacency_matrix.iter().filter(|&&adjacent| adjacent).count();
-        assert_eq!(edge_count, edges.len());
-
-        Self { sorted_nodes, node_mapping, adjacency_matrix }
+impl Input {
+    /// The pages of `manual` in the order the rules require. Rules about pages that aren't part of
+    /// the manual don't matter.
+    fn ordered(&self, manual: &[u8]) -> eyre::Result<Vec<u8>> {
+        let pages = toposort(manual.iter().copied(), self.rules.iter().copied())
+            .wrap_err_with(|| format!("Failed to order the pages of {manual:?}"))?;
+        trace!("{pages:?}");
+        Ok(pages)
     }
 
-    pub fn add_transitive(&mut self) {
-        let node_count = self.sorted_nodes.len();
-        let without_predecessor = (0..node_count)
-            .map(|node| {
-                (node, self.predecessors(node).len())
+    /// The middle page of every manual, once ordered, together with whether it already was.
+    fn middle_pages(&self) -> eyre::Result<Vec<(usize, bool)>> {
+        self.manuals.iter()
+            .map(|manual| {
+                let pages = self.ordered(manual)?;
+                Ok((pages[pages.len() / 2] as usize, pages.eq(manual)))
             })
-            .filter(|(_, count)| *count == 0)
-            .map(|(node, _)| node)
-            .collect::<Vec<_>>();
-
-        for node in without_predecessor {
-            let successors = self.successors(node);
-            for successor in successors {
-                self.add_transitive_for_node(successor);
-            }
-        }
-    }
-
-    fn successors(&self, node: usize) -> Vec<usize> {
-        let node_count = self.sorted_nodes.len();
-        (&self.adjacency_matrix[node * node_count..node * (node_count + 1)]).iter()
-            .enumerate()
-            .filter(|&(_, &adjacent)| adjacent)
-            .map(|(node, _)| node)
-            .collect()
-    }
-
-    fn predecessors(&self, node: usize) -> Vec<usize> {
-        let node_count = self.sorted_nodes.len();
-        self.adjacency_matrix.iter()
-            .skip(node)
-            .step_by(node_count)
-            .enumerate()
-            .filter(|&(_, &adjacent)| adjacent)
-            .map(|(node, _)| node)
             .collect()
     }
-
-    fn add_edge(&mut self, from: usize, to: usize) {
-        self.adjacency_matrix[from * self.sorted_nodes.len() + to] = true;
-    }
-
-    fn has_edge(&self, from: usize, to: usize) -> bool {
-        self.adjacency_matrix[from * self.sorted_nodes.len() + to]
-    }
-
-    fn add_transitive_for_node(&mut self, node: usize) {
-        let predecessors = self.predecessors(node);
-        for successor in self.successors(node) {
-            for &predecessor in &predecessors {
-                self.add_edge(predecessor, successor);
-            }
-            self.add_transitive_for_node(successor);
-        }
-    }
-}
-
-#[derive(Debug, Clone, Eq, PartialEq, Hash)]
-pub struct Input {
-    manuals: Vec<(Graph, Vec<u8>)>,
 }
 
 impl FromStr for Input {
@@ -152,98 +60,59 @@ impl FromStr for Input {
             )
             .collect::<eyre::Result<_>>()?;
 
-        let manuals = manuals.into_iter()
-            .map(|manual| {
-                let lookup: HashSet<u8> = HashSet::from_iter(manual.iter().cloned());
-                let rules = rules.iter()
-                    .filter(|(from, to)| lookup.contains(from) && lookup.contains(to))
-                    .cloned()
-                    .collect::<Vec<_>>();
-                let graph = Graph::new(&rules);
-                (graph, manual)
-            })
-            .collect();
+        for manual in &manuals {
+            if manual.iter().collect::<HashSet<_>>().len() != manual.len() {
+                return Err(eyre!("Manual {manual:?} contains a page twice"));
+            }
+        }
 
         Ok(Self {
+            rules,
             manuals,
         })
     }
 }
 
-pub fn process_part1(input: &Input) -> eyre::Result<String> {
-    let result: usize = input.manuals.iter()
-        .map(|(graph, manuals)| {
-            let mut graph = graph.clone();
-            graph.add_transitive();
-            let mut pages = (0..graph.sorted_nodes.len()).collect::<Vec<_>>();
-            pages.sort_by(|&a, &b|
-                if graph.has_edge(a, b) {
-                    Ordering::Less
-                } else {
-                    Ordering::Greater
-                });
-            let pages = pages.iter()
-                .map(|&node| graph.sorted_nodes[node])
-                .collect::<Vec<_>>();
-            trace!("{pages:?}");
-            (pages, manuals)
-        })
-        .filter(|(pages, manuals)| pages.eq(*manuals))
-        .map(|(pages, _)| pages[pages.len()/2] as usize)
+pub fn process_part1(input: &Input) -> eyre::Result<Answer> {
+    let result: usize = input.middle_pages()?.into_iter()
+        .filter(|&(_, ordered)| ordered)
+        .map(|(page, _)| page)
         .sum();
 
-    Ok(result.to_string())
+    Ok(result.into())
 }
 
-pub fn process_part2(input: &Input) -> eyre::Result<String> {
-    let result: usize = input.manuals.iter()
-        .map(|(graph, manuals)| {
-            let mut graph = graph.clone();
-            graph.add_transitive();
-            let mut pages = (0..graph.sorted_nodes.len()).collect::<Vec<_>>();
-            pages.sort_by(|&a, &b|
-                if graph.has_edge(a, b) {
-                    Ordering::Less
-                } else {
-                    Ordering::Greater
-                });
-            let pages = pages.iter()
-                .map(|&node| graph.sorted_nodes[node])
-                .collect::<Vec<_>>();
-            trace!("{pages:?}");
-            (pages, manuals)
-        })
-        .filter(|(pages, manuals)| !pages.eq(*manuals))
-        .map(|(pages, _)| pages[pages.len()/2] as usize)
+pub fn process_part2(input: &Input) -> eyre::Result<Answer> {
+    let result: usize = input.middle_pages()?.into_iter()
+        .filter(|&(_, ordered)| !ordered)
+        .map(|(page, _)| page)
         .sum();
 
-    Ok(result.to_string())
+    Ok(result.into())
 }
 
-pub async fn run() -> eyre::Result<()> {
-    let day_span = span!(Level::ERROR, "", "{}", DAY);
-    async {
-        info!("Running {DAY}");
+pub struct Solution;
+
+impl Solver for Solution {
+    type Parsed = Input;
 
-        let raw_input = super::get_input(DAY).await?;
-        trace!(raw_input);
+    fn parse(input: &str) -> eyre::Result<Self::Parsed> {
+        input.parse()
+    }
 
-        let input = raw_input.parse()?;
-        debug!(?input);
+    fn part1(input: &Self::Parsed) -> eyre::Result<Answer> {
+        process_part1(input)
+    }
 
-        let start1 = SystemTime::now();
-        let result1 = process_part1(&input)?;
-        let end1 = SystemTime::now();
-        let start2 = SystemTime::now();
-        let result2 = process_part2(&input)?;
-        let end2 = SystemTime::now();
-        println!("{DAY} result:");
-        println!("  part 1: {result1} in {:?}", end1.duration_since(start1).unwrap());
-        println!("  part 2: {result2} in {:?}", end2.duration_since(start2).unwrap());
-        Ok(())
+    fn part2(input: &Self::Parsed) -> eyre::Result<Answer> {
+        process_part2(input)
     }
-        .instrument(day_span.or_current())
-        .await
+}
+
+register_day! {
+    day: 5,
+    status: Implemented,
+    solver: Solution,
 }
 
 #[cfg(test)]
@@ -284,9 +153,17 @@ mod test {
         let input = raw_input.parse().unwrap();
 
         let result1 = process_part1(&input).unwrap();
-        assert_eq!("143", result1);
+        assert_eq!(Answer::Unsigned(143), result1);
 
         let result2 = process_part2(&input).unwrap();
-        assert_eq!("123", result2);
+        assert_eq!(Answer::Unsigned(123), result2);
+    }
+
+    #[test]
+    pub fn test_cycle() {
+        let input: Input = "1|2\n2|3\n3|1\n\n1,2,3\n".parse().unwrap();
+
+        let error = process_part1(&input).unwrap_err();
+        assert_eq!("Found a cycle between [1, 2, 3]", error.root_cause().to_string());
     }
 }
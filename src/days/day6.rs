@@ -1,13 +1,32 @@
 use std::fmt::{Display, Formatter};
 use std::ops::Deref;
 use std::str::FromStr;
-use std::time::SystemTime;
 use itertools::Itertools;
 use owo_colors::OwoColorize;
-use tracing::{debug, info, Instrument, Level, span, trace};
-use crate::days::Day;
-
-pub const DAY: Day = Day(6);
+use tracing::trace;
+use crate::days::solver::{register_day, Answer, Solver};
+use crate::days::approach::{Approach, ApproachHistory};
+use crate::days::util::{cycle, Direction, DirectionSet, Grid, Marker};
+use crate::days::util::display::diff;
+use crate::days::util::walk::TimedWalk;
+
+pub const APPROACH_HISTORY: ApproachHistory = ApproachHistory {
+    day: DAY,
+    approaches: &[
+        Approach { name: "resimulate", description: "Walk the whole map again for every obstacle on the original path" },
+        Approach { name: "skip-ahead", description: "Start at the new obstacle and stop as soon as the walk rejoins the original one before or after it" },
+        Approach { name: "cycle-detection", description: "Walk the whole map again for every obstacle on the original path, finding loops with Brent's algorithm instead of marking the visited tiles" },
+        Approach { name: "jump-table", description: "Walk the whole map again for every obstacle on the original path, jumping straight to the next obstacle with a precomputed table" },
+        #[cfg(feature = "rayon")]
+        Approach { name: "parallel", description: "Walk the whole map again for every obstacle on the original path, trying the obstacles on all cores" },
+    ],
+    active: "resimulate",
+    solve: |raw_input| {
+        let input: Input = raw_input.parse()?;
+        process_part2(&input)?;
+        Ok(())
+    },
+};
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub struct Rotation(u8);
@@ -65,6 +84,10 @@ impl Rotation {
         }
     }
 
+    pub fn direction(&self) -> Direction {
+        Direction::ALL[self.index()]
+    }
+
     pub fn go(&self, position: usize, width: usize) -> usize {
         match *self {
             Self::NORTH => position - width,
@@ -76,27 +99,6 @@ impl Rotation {
     }
 }
 
-fn display_directions(directions: u8) -> char {
-    [
-        '.', // 0b0000
-        '╵', // 0b0001
-        '╶', // 0b0010
-        '└', // 0b0011
-        '╷', // 0b0100
-        '│', // 0b0101
-        '┌', // 0b0110
-        '├', // 0b0111
-        '╴', // 0b1000
-        '┘', // 0b1001
-        '─', // 0b1010
-        '┴', // 0b1011
-        '┐', // 0b1100
-        '┤', // 0b1101
-        '┬', // 0b1110
-        '┼', // 0b1111
-    ][directions as usize]
-}
-
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct Position {
     pub position: usize,
@@ -190,14 +192,14 @@ impl Position {
 
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct Input {
-    char_map: Vec<char>,
+    map: Grid,
     position: Position,
 }
 
 impl Input {
     pub fn step(&mut self) -> Option<usize> {
         let new_position = self.position.look()?;
-        if self.char_map[new_position] == '#' {
+        if self.map.as_slice()[new_position] == '#' {
             self.position.rotate90();
             Some(self.position.position)
         } else {
@@ -210,70 +212,159 @@ impl FromStr for Input {
     type Err = eyre::Report;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let s = s.lines()
-            .map(|line| line.trim())
-            .filter(|line| !line.is_empty())
-            .join("\n");
-        let width = s.find('\n').unwrap();
-        let char_map: Vec<char> = s.lines().map(|line| line.chars()).flatten().collect();
+        let mut guard = Marker::new('^');
+        let map = Grid::from_str_with(s, |tile, coordinate| {
+            Ok::<_, eyre::Report>(if guard.check(tile, coordinate) { '.' } else { tile })
+        })?;
 
         let position = Position {
-            position: char_map.iter().position(|character| *character == '^').unwrap(),
+            position: guard.index(&map)?,
             direction: Rotation::NORTH,
-            width,
-            height: char_map.len() / width,
+            width: map.width(),
+            height: map.height(),
         };
 
         Ok(Self {
-            char_map,
+            map,
             position,
         })
     }
 }
 
-fn movement_map(input: &Input) -> Result<Vec<u8>, Vec<u8>> {
+fn movement_map(input: &Input) -> Result<Vec<DirectionSet>, Vec<DirectionSet>> {
     let mut map = input.clone();
-    let mut visited = vec![0u8; map.char_map.len()];
-    visited[map.position.position] |= *map.position.direction;
+    let mut visited = vec![DirectionSet::EMPTY; map.map.as_slice().len()];
+    visited[map.position.position].insert(map.position.direction.direction());
 
     while let Some(position) = map.step() {
         // visiting a position in the same direction twice is a loop
-        if visited[position] & *map.position.direction != 0 {
+        if !visited[position].insert(map.position.direction.direction()) {
             return Err(visited);
         }
-        visited[position] |= *map.position.direction;
     }
 
     Ok(visited)
 }
 
-pub fn process_part1(input: &Input) -> eyre::Result<String> {
+/// For every tile and direction the guard can walk in, the tile it stops on in front of the next
+/// obstacle, or [`None`] if it walks off the map instead.
+struct JumpTable {
+    jumps: Vec<[Option<usize>; 4]>,
+}
+
+impl JumpTable {
+    fn new(input: &Input) -> Self {
+        let len = input.map.as_slice().len();
+        let mut jumps = vec![[None; 4]; len];
+        for direction in Rotation::ALL {
+            // the tile ahead has to be done before the tile behind it
+            let order: Box<dyn Iterator<Item = usize>> = match direction {
+                Rotation::NORTH | Rotation::WEST => Box::new(0..len),
+                _ => Box::new((0..len).rev()),
+            };
+            for position in order {
+                let guard = Position { position, direction, ..input.position.clone() };
+                jumps[position][direction.index()] = match guard.look() {
+                    None => None,
+                    Some(next) if input.map.as_slice()[next] == '#' => Some(position),
+                    Some(next) => jumps[next][direction.index()],
+                };
+            }
+        }
+
+        Self { jumps }
+    }
+
+    /// How many steps straight ahead of `guard` `tile` is, if it is ahead at all.
+    fn steps_to(guard: &Position, tile: usize) -> Option<usize> {
+        let (from, width) = (guard.position, guard.width);
+        let same_column = from % width == tile % width;
+        let same_row = from / width == tile / width;
+        match guard.direction {
+            Rotation::NORTH if same_column && tile < from => Some((from - tile) / width),
+            Rotation::SOUTH if same_column && tile > from => Some((tile - from) / width),
+            Rotation::EAST if same_row && tile > from => Some(tile - from),
+            Rotation::WEST if same_row && tile < from => Some(from - tile),
+            _ => None,
+        }
+    }
+
+    /// The tile `guard` stops on with an extra obstacle at `obstacle`, or [`None`] if it walks off
+    /// the map. The extra obstacle only matters if it is in the same row or column as the guard,
+    /// so only those jumps are cut short.
+    fn jump(&self, guard: &Position, obstacle: usize) -> Option<usize> {
+        let target = self.jumps[guard.position][guard.direction.index()];
+        let blocked = match (Self::steps_to(guard, obstacle), target) {
+            (None, _) => false,
+            (Some(_), None) => true,
+            (Some(to_obstacle), Some(target)) => to_obstacle <= Self::steps_to(guard, target).unwrap_or(0),
+        };
+        if blocked {
+            Some(guard.direction.rotate90().rotate90().go(obstacle, guard.width))
+        } else {
+            target
+        }
+    }
+}
+
+/// Like [`movement_map`] with an extra obstacle at `obstacle`, but the guard jumps from obstacle to
+/// obstacle with `jumps`, so only the tiles it turns on are marked.
+fn movement_map_fast(input: &Input, jumps: &JumpTable, obstacle: usize) -> Result<Vec<DirectionSet>, Vec<DirectionSet>> {
+    let mut guard = input.position.clone();
+    let mut visited = vec![DirectionSet::EMPTY; input.map.as_slice().len()];
+
+    // turning in the same direction on a tile twice is a loop
+    while visited[guard.position].insert(guard.direction.direction()) {
+        let Some(position) = jumps.jump(&guard, obstacle) else {
+            return Ok(visited);
+        };
+        guard.position = position;
+        guard.rotate90();
+    }
+
+    Err(visited)
+}
+
+/// The walk of the guard on the unmodified map, which never loops.
+fn timed_walk(input: &Input) -> TimedWalk {
+    let mut map = input.clone();
+    let mut walk = TimedWalk::new(map.map.as_slice().len());
+    walk.visit(map.position.position, map.position.direction.direction());
+
+    while let Some(position) = map.step() {
+        walk.visit(position, map.position.direction.direction());
+    }
+
+    walk
+}
+
+pub fn process_part1(input: &Input) -> eyre::Result<Answer> {
     let visited = movement_map(input).unwrap();
     // println!("{}\n", visualize_visited(&visited, input.position.width));
 
-    let result: usize = visited.into_iter().filter(|&directions| directions != 0).count();
+    let result: usize = visited.into_iter().filter(|directions| !directions.is_empty()).count();
 
-    Ok(result.to_string())
+    Ok(result.into())
 }
 
 #[allow(dead_code)]
-fn visualize_visited(visited: &[(u8, [Option<usize>; 4])], width: usize) -> String {
+fn visualize_visited(visited: &[(DirectionSet, [Option<usize>; 4])], width: usize) -> String {
     visited.chunks(width)
         .into_iter()
-        .map(|row| row.iter().map(|&(visited, _)| if visited != 0 { '1'.bright_green().bold().to_string() } else { '0'.dimmed().to_string() }).join(""))
+        .map(|row| row.iter().map(|&(visited, _)| if !visited.is_empty() { '1'.bright_green().bold().to_string() } else { '0'.dimmed().to_string() }).join(""))
         .join("\n")
 }
 
 #[allow(dead_code)]
-fn visualize_paths(input: &Input, visited: &[u8], width: usize, obstacle: Option<usize>, direction: Option<(usize, Rotation)>, mark: Option<usize>, new_obstacle: Option<usize>) -> String {
+fn visualize_paths(input: &Input, visited: &[DirectionSet], width: usize, obstacle: Option<usize>, direction: Option<(usize, Rotation)>, mark: Option<usize>, new_obstacle: Option<usize>) -> String {
     visited.iter().enumerate().chunks(width)
         .into_iter()
         .map(|row|
             row.into_iter().map(|(position, &directions)| {
-                let text = if input.char_map[position] == '#' {
-                    '#'
+                let text = if input.map.as_slice()[position] == '#' {
+                    '#'.to_string()
                 } else {
-                    display_directions(directions)
+                    directions.to_string()
                 };
                 match (position, obstacle, direction, mark, new_obstacle) {
                     (position, Some(obstacle), _, _, _) if obstacle == position => text.bold().bright_red().to_string(),
@@ -289,7 +380,7 @@ fn visualize_paths(input: &Input, visited: &[u8], width: usize, obstacle: Option
 }
 
 #[allow(dead_code)]
-fn visualize_visited_time(visited: &[(u8, [Option<usize>; 4])], width: usize, obstacle: Option<usize>, direction: Option<(usize, Rotation)>, mark: Option<usize>, new_obstacle: Option<usize>) -> String {
+fn visualize_visited_time(visited: &[(DirectionSet, [Option<usize>; 4])], width: usize, obstacle: Option<usize>, direction: Option<(usize, Rotation)>, mark: Option<usize>, new_obstacle: Option<usize>) -> String {
     let times = visited
         .into_iter()
         .map(|(_, times)| times.iter().filter_map(|time| *time).next().unwrap_or(0))
@@ -351,7 +442,7 @@ fn visualize_visited_time(visited: &[(u8, [Option<usize>; 4])], width: usize, ob
 //
 //     let passed_direction = position.direction.rotate90();
 //     while let Some(position) = position.step() {
-//         if input.char_map[position] == '#' {
+//         if input.map.as_slice()[position] == '#' {
 //             // found an obstacle in this direction
 //             break;
 //         }
@@ -366,7 +457,7 @@ fn visualize_visited_time(visited: &[(u8, [Option<usize>; 4])], width: usize, ob
 //         // do not check obstacle if path goes in that direction (we'll check it anyway)
 //         if movement_map[position] & *passed_direction.rotate90() == 0 {
 //             if let Some(existing_obstacle) = obstacle_position.look() {
-//                 if input.char_map[existing_obstacle] == '#' {
+//                 if input.map.as_slice()[existing_obstacle] == '#' {
 //                     #[cfg(debug_assertions)]
 //                     println!("{}", visualize_paths(&input, movement_map, input.position.width, Some(obstacle), Some((deflected_position, deflected_direction)), Some(existing_obstacle), None));
 //                     #[cfg(debug_assertions)]
@@ -404,65 +495,163 @@ fn visualize_visited_time(visited: &[(u8, [Option<usize>; 4])], width: usize, ob
 //     added_obstacles
 // }
 
-pub fn process_part2(input: &Input) -> eyre::Result<String> {
+fn count_loops_resimulate(input: &Input) -> usize {
     let original_movement = movement_map(input).unwrap();
     let mut new_map = input.clone();
-    let result: usize = input.char_map.iter()
-        .enumerate()
-        .filter(|(_, character)| **character != '#' && **character != '^')
-        .filter(|(position, _)| original_movement[*position] != 0)
-        .map(|(position, _)| {
-            let tmp = new_map.char_map[position];
-            new_map.char_map[position] = '#';
+    input.map.positions(|&character| character != '#')
+        .filter(|&position| position != input.position.position)
+        .filter(|&position| !original_movement[position].is_empty())
+        .map(|position| {
+            let tmp = new_map.map.as_slice()[position];
+            new_map.map.as_mut_slice()[position] = '#';
             let movement = movement_map(&new_map);
-            // #[cfg(debug_assertions)]
-            // match &movement {
-                // Ok(map) | Err(map) => {
-                //     println!("{}", visualize_paths(&new_map, &map, input.position.width, Some(position), None, None, None))
-                // },
-                // Err(map) => {
-                    // println!("{}", visualize_paths(&new_map, &map, input.position.width, Some(position), None, None, None));
-                    // println!();
-                // },
-                // Ok(map) => {
-                //     println!("{}", visualize_paths(&new_map, &map, input.position.width, Some(position), None, None, None));
-                //     println!();
-                // },
-                // _ => (),
-            // }
-            new_map.char_map[position] = tmp;
+            trace!("obstacle at {position}\n{}", diff(&input.map, &new_map.map));
+            new_map.map.as_mut_slice()[position] = tmp;
             (position, movement)
         })
         .filter_map(|(position, map)| map.err().map(|map| (position, map)))
-        .count();
+        .count()
+}
 
-    Ok(result.to_string())
+/// The guard after its next step or turn on `map`, or [`None`] once it leaves the map.
+fn next_position(map: &Grid, position: &Position) -> Option<Position> {
+    let mut position = position.clone();
+    let next = position.look()?;
+    if map.as_slice()[next] == '#' {
+        position.rotate90();
+    } else {
+        position.position = next;
+    }
+    Some(position)
 }
 
-pub async fn run() -> eyre::Result<()> {
-    let day_span = span!(Level::ERROR, "", "{}", DAY);
-    async {
-        info!("Running {DAY}");
-
-        let raw_input = super::get_input(DAY).await?;
-        trace!(raw_input);
-
-        let input = raw_input.parse()?;
-        debug!(?input);
-
-        let start1 = SystemTime::now();
-        let result1 = process_part1(&input)?;
-        let end1 = SystemTime::now();
-        let start2 = SystemTime::now();
-        let result2 = process_part2(&input)?;
-        let end2 = SystemTime::now();
-        println!("{DAY} result:");
-        println!("  part 1: {result1} in {:?}", end1.duration_since(start1).unwrap());
-        println!("  part 2: {result2} in {:?}", end2.duration_since(start2).unwrap());
-        Ok(())
+fn count_loops_cycle_detection(input: &Input) -> usize {
+    let original_movement = movement_map(input).unwrap();
+    let mut map = input.map.clone();
+    input.map.positions(|&character| character != '#')
+        .filter(|&position| position != input.position.position)
+        .filter(|&position| !original_movement[position].is_empty())
+        .filter(|&position| {
+            map.as_mut_slice()[position] = '#';
+            let loops = cycle::detect(input.position.clone(), |guard| next_position(&map, guard)).is_some();
+            map.as_mut_slice()[position] = '.';
+            loops
+        })
+        .count()
+}
+
+/// Whether placing an obstacle at `obstacle` on the original `walk` makes the guard loop. The guard
+/// walks the original path until it first runs into the obstacle, so the walk starts right there.
+/// Once it is in a state of the original walk its fate is known: states before the first visit of
+/// the obstacle lead back to it, states after the last visit lead off the map.
+fn loops_with_obstacle(input: &Input, walk: &TimedWalk, obstacle: usize) -> bool {
+    let (first, direction) = walk.first_visit(obstacle).unwrap();
+    let last = walk.last_visit(obstacle).unwrap();
+
+    let mut position = Position {
+        position: obstacle,
+        direction: Rotation::ALL[direction.index()],
+        ..input.position.clone()
+    };
+    position.position = position.look_back().unwrap();
+    position.rotate90();
+
+    let mut visited = vec![DirectionSet::EMPTY; input.map.as_slice().len()];
+    loop {
+        match walk.time(position.position, position.direction.direction()) {
+            Some(time) if time < first => return true,
+            Some(time) if time > last => return false,
+            _ => (),
+        }
+        if !visited[position.position].insert(position.direction.direction()) {
+            return true;
+        }
+
+        let Some(next) = position.look() else {
+            return false;
+        };
+        if next == obstacle || input.map.as_slice()[next] == '#' {
+            position.rotate90();
+        } else {
+            position.position = next;
+        }
+    }
+}
+
+fn count_loops_jump_table(input: &Input) -> usize {
+    let original_movement = movement_map(input).unwrap();
+    let jumps = JumpTable::new(input);
+    input.map.positions(|&character| character != '#')
+        .filter(|&position| position != input.position.position && !original_movement[position].is_empty())
+        .filter(|&position| movement_map_fast(input, &jumps, position).is_err())
+        .count()
+}
+
+/// Like [`count_loops_resimulate`], but every rayon worker places the obstacles on its own copy of
+/// the map.
+#[cfg(feature = "rayon")]
+fn count_loops_parallel(input: &Input) -> usize {
+    use rayon::prelude::*;
+
+    let original_movement = movement_map(input).unwrap();
+    input.map.positions(|&character| character != '#')
+        .filter(|&position| position != input.position.position && !original_movement[position].is_empty())
+        .collect::<Vec<_>>()
+        .into_par_iter()
+        .map_init(|| input.clone(), |new_map, position| {
+            new_map.map.as_mut_slice()[position] = '#';
+            let loops = movement_map(new_map).is_err();
+            new_map.map.as_mut_slice()[position] = '.';
+            loops
+        })
+        .filter(|&loops| loops)
+        .count()
+}
+
+fn count_loops_skip_ahead(input: &Input) -> usize {
+    let walk = timed_walk(input);
+    (0..input.map.as_slice().len())
+        .filter(|&position| position != input.position.position && walk.visited(position))
+        .filter(|&position| loops_with_obstacle(input, &walk, position))
+        .count()
+}
+
+pub fn process_part2(input: &Input) -> eyre::Result<Answer> {
+    let result = match APPROACH_HISTORY.selected() {
+        "skip-ahead" => count_loops_skip_ahead(input),
+        "cycle-detection" => count_loops_cycle_detection(input),
+        "jump-table" => count_loops_jump_table(input),
+        #[cfg(feature = "rayon")]
+        "parallel" => count_loops_parallel(input),
+        _ => count_loops_resimulate(input),
+    };
+
+    Ok(result.into())
+}
+
+pub struct Solution;
+
+impl Solver for Solution {
+    type Parsed = Input;
+
+    fn parse(input: &str) -> eyre::Result<Self::Parsed> {
+        input.parse()
+    }
+
+    fn part1(input: &Self::Parsed) -> eyre::Result<Answer> {
+        process_part1(input)
+    }
+
+    fn part2(input: &Self::Parsed) -> eyre::Result<Answer> {
+        process_part2(input)
     }
-        .instrument(day_span.or_current())
-        .await
+}
+
+register_day! {
+    day: 6,
+    status: Implemented,
+    solver: Solution,
+    approach_history: APPROACH_HISTORY,
 }
 
 #[cfg(test)]
@@ -486,10 +675,15 @@ mod test {
         let input = raw_input.parse().unwrap();
 
         let result = process_part1(&input).unwrap();
-        assert_eq!("41", result);
+        assert_eq!(Answer::Unsigned(41), result);
 
         let result = process_part2(&input).unwrap();
-        assert_eq!("6", result);
+        assert_eq!(Answer::Unsigned(6), result);
+        assert_eq!(6, count_loops_skip_ahead(&input));
+        assert_eq!(6, count_loops_cycle_detection(&input));
+        assert_eq!(6, count_loops_jump_table(&input));
+        #[cfg(feature = "rayon")]
+        assert_eq!(6, count_loops_parallel(&input));
 
         let raw_input = r"
 ..........
@@ -506,6 +700,34 @@ mod test {
         let input = raw_input.parse().unwrap();
 
         let result = process_part2(&input).unwrap();
-        assert_eq!("1", result);
+        assert_eq!(Answer::Unsigned(1), result);
+    }
+
+    #[test]
+    pub fn test_strategies_agree() {
+        use rand::{Rng, SeedableRng};
+        use rand::rngs::StdRng;
+
+        let mut rng = StdRng::seed_from_u64(6);
+        for _ in 0..500 {
+            let (width, height) = (rng.gen_range(2..24), rng.gen_range(2..24));
+            let mut raw_input = (0..height)
+                .map(|_| (0..width).map(|_| if rng.gen_bool(0.15) { '#' } else { '.' }).collect::<String>())
+                .collect::<Vec<_>>();
+            let (x, y) = (rng.gen_range(0..width), rng.gen_range(0..height));
+            raw_input[y].replace_range(x..x + 1, "^");
+            let raw_input = raw_input.join("\n");
+
+            let input: Input = raw_input.parse().unwrap();
+            // the guard may already be stuck in a loop without any new obstacle
+            if movement_map(&input).is_err() {
+                continue;
+            }
+            assert_eq!(count_loops_resimulate(&input), count_loops_skip_ahead(&input), "{raw_input}");
+            assert_eq!(count_loops_resimulate(&input), count_loops_cycle_detection(&input), "{raw_input}");
+            assert_eq!(count_loops_resimulate(&input), count_loops_jump_table(&input), "{raw_input}");
+            #[cfg(feature = "rayon")]
+            assert_eq!(count_loops_resimulate(&input), count_loops_parallel(&input), "{raw_input}");
+        }
     }
 }
@@ -2,14 +2,10 @@ use std::cmp::Ordering;
 use std::collections::VecDeque;
 use std::fmt::{Display, Formatter};
 use std::str::FromStr;
-use std::time::SystemTime;
 use eyre::anyhow;
-use tracing::{debug, info, Instrument, Level, span, trace};
-use crate::days::Day;
+use crate::days::solver::{register_day, Answer, Solver};
 use crate::days::util::Lines;
 
-pub const DAY: Day = Day(7);
-
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
 pub enum Operator {
     Add,
@@ -34,8 +30,8 @@ impl Default for Operator {
 }
 
 impl Operator {
-    const ALL1: [Self; 2] = [Self::Add, Self::Multiply];
-    const ALL2: [Self; 3] = [Self::Add, Self::Multiply, Self::Concatenate];
+    pub const ALL1: [Self; 2] = [Self::Add, Self::Multiply];
+    pub const ALL2: [Self; 3] = [Self::Add, Self::Multiply, Self::Concatenate];
 
     pub fn apply(&self, a: usize, b: usize) -> usize {
         match self {
@@ -44,6 +40,18 @@ impl Operator {
             Self::Concatenate => a.checked_mul(10usize.checked_pow(b.checked_ilog10().unwrap().checked_add(1).unwrap()).unwrap()).unwrap().checked_add(b).unwrap(),
         }
     }
+
+    /// The `a` for which `apply(a, b)` is `result`, if there is one.
+    pub fn undo(&self, result: usize, b: usize) -> Option<usize> {
+        match self {
+            Self::Add => result.checked_sub(b),
+            Self::Multiply => (b != 0 && result % b == 0).then(|| result / b),
+            Self::Concatenate => {
+                let shift = 10usize.checked_pow(b.checked_ilog10()? + 1)?;
+                (result % shift == b).then(|| result / shift)
+            }
+        }
+    }
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
@@ -75,6 +83,25 @@ impl Equation {
         }
         false
     }
+
+    /// Like [`Self::try_solve`], but works from the result back to the first operand, undoing the
+    /// operators on the last one. Most operators can't be undone for most operands, so most
+    /// branches end right away.
+    pub fn try_solve_reverse(&self, operators: &[Operator]) -> bool {
+        self.solvable_reverse(self.result, self.operands.len(), operators)
+    }
+
+    /// Whether the first `count` operands can be combined into `result`.
+    fn solvable_reverse(&self, result: usize, count: usize, operators: &[Operator]) -> bool {
+        let last = self.operands[count - 1];
+        if count == 1 {
+            return last == result;
+        }
+
+        operators.iter()
+            .filter_map(|operator| operator.undo(result, last))
+            .any(|result| self.solvable_reverse(result, count - 1, operators))
+    }
 }
 
 impl FromStr for Equation {
@@ -100,81 +127,72 @@ impl FromStr for Equation {
 
 pub type Input = Lines<Equation>;
 
-pub async fn process_part1(input: &Input) -> eyre::Result<(usize, Vec<Equation>)> {
-    let handles = input.iter()
-        .map(|equation| {
-            let mut equation = equation.clone();
-            tokio::spawn(async move { (equation.try_solve(&Operator::ALL1), equation) })
-            // (equation.try_solve(&Operator::ALL1), equation)
-        })
-        .collect::<Vec<_>>();
-    let mut result = 0;
-    let mut failed = Vec::new();
-    for handle in handles {
-        let (solved, equation) = handle.await?;
-        // let (solved, equation) = handle;
-        if solved {
-            result += equation.result;
-        } else {
-            failed.push(equation)
-        }
-    }
+/// Parses the equations in parallel, as there are a lot of them.
+#[cfg(feature = "rayon")]
+fn parse_input(raw_input: &str) -> eyre::Result<Input> {
+    Input::par_from_str(raw_input)
+}
 
-    Ok((result, failed))
+#[cfg(not(feature = "rayon"))]
+fn parse_input(raw_input: &str) -> eyre::Result<Input> {
+    raw_input.parse()
 }
 
-pub async fn process_part2(input: &[Equation], part1: usize) -> eyre::Result<usize> {
-    let handles = input.iter()
-        .map(|equation| {
-            let mut equation = equation.clone();
-            tokio::spawn(async move { (equation.try_solve(&Operator::ALL2), equation) })
-            // (equation.try_solve(&Operator::ALL2), equation)
-        })
-        .collect::<Vec<_>>();
-    let mut result = part1;
-    for handle in handles {
-        let (solved, equation) = handle.await?;
-        // let (solved, equation) = handle;
-        if solved {
-            result += equation.result;
-        }
-    }
+/// The sum of the results of the equations that can be solved with `operators`.
+fn calibration_result(input: &Input, operators: &[Operator]) -> usize {
+    input.iter()
+        .filter(|equation| equation.try_solve_reverse(operators))
+        .map(|equation| equation.result)
+        .sum()
+}
+
+/// Like [`calibration_result`], but with the breadth-first [`Equation::try_solve`], to compare the
+/// two solvers.
+pub fn calibration_result_forward(input: &Input, operators: &[Operator]) -> usize {
+    input.iter()
+        .filter(|equation| equation.clone().try_solve(operators))
+        .map(|equation| equation.result)
+        .sum()
+}
 
-    Ok(result)
+pub fn process_part1(input: &Input) -> eyre::Result<Answer> {
+    Ok(calibration_result(input, &Operator::ALL1).into())
 }
 
-pub async fn run() -> eyre::Result<()> {
-    let day_span = span!(Level::ERROR, "", "{}", DAY);
-    async {
-        info!("Running {DAY}");
+pub fn process_part2(input: &Input) -> eyre::Result<Answer> {
+    Ok(calibration_result(input, &Operator::ALL2).into())
+}
 
-        let raw_input = super::get_input(DAY).await?;
-        trace!(raw_input);
+pub struct Solution;
 
-        let input = raw_input.parse()?;
-        debug!(?input);
+impl Solver for Solution {
+    type Parsed = Input;
 
-        let start1 = SystemTime::now();
-        let (result1, failed) = process_part1(&input).await?;
-        let end1 = SystemTime::now();
-        let start2 = SystemTime::now();
-        let result2 = process_part2(&failed, result1).await?;
-        let end2 = SystemTime::now();
-        println!("{DAY} result:");
-        println!("  part 1: {result1} in {:?}", end1.duration_since(start1).unwrap());
-        println!("  part 2: {result2} in {:?}", end2.duration_since(start2).unwrap());
-        Ok(())
+    fn parse(input: &str) -> eyre::Result<Self::Parsed> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Parsed) -> eyre::Result<Answer> {
+        process_part1(input)
+    }
+
+    fn part2(input: &Self::Parsed) -> eyre::Result<Answer> {
+        process_part2(input)
     }
-        .instrument(day_span.or_current())
-        .await
+}
+
+register_day! {
+    day: 7,
+    status: Implemented,
+    solver: Solution,
+    example: test::EXAMPLE => ("3749", "11387"),
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
 
-    fn example_input() -> Input {
-        r"190: 10 19
+    pub const EXAMPLE: &str = r"190: 10 19
           3267: 81 40 27
           83: 17 5
           156: 15 6
@@ -183,28 +201,30 @@ mod test {
           192: 17 8 14
           21037: 9 7 18 13
           292: 11 6 16 20
-          ".parse().unwrap()
+          ";
+
+    fn example_input() -> Input {
+        EXAMPLE.parse().unwrap()
     }
 
-    #[tokio::test]
-    pub async fn test_part1() {
+    #[test]
+    pub fn test_part1() {
         let input = example_input();
-        // println!("{input:?}");
 
-        let (result, _) = process_part1(&input).await.unwrap();
-        assert_eq!(3749, result);
+        let result = process_part1(&input).unwrap();
+        assert_eq!(Answer::Unsigned(3749), result);
     }
 
-    #[tokio::test]
-    pub async fn test_part1_custom() {
+    #[test]
+    pub fn test_part1_custom() {
         let raw_input = r"3744: 9 7 18 13
                                104831: 9 7 18 13 4 7
                                104832: 9 7 18 13 4 7
                                ";
         let input: Input = raw_input.parse().unwrap();
 
-        let (result, _) = process_part1(&input).await.unwrap();
-        assert_eq!(108576, result);
+        let result = process_part1(&input).unwrap();
+        assert_eq!(Answer::Unsigned(108576), result);
     }
 
     #[test]
@@ -221,12 +241,36 @@ mod test {
         }
     }
 
-    #[tokio::test]
-    pub async fn test_part2() {
+    #[test]
+    pub fn test_undo() {
+        for (a, b) in [(2, 1), (327, 934), (0, 5), (12, 10)] {
+            for operator in Operator::ALL2 {
+                assert_eq!(Some(a), operator.undo(operator.apply(a, b), b), "{a} {operator} {b}");
+            }
+        }
+        assert_eq!(None, Operator::Multiply.undo(7, 2));
+        assert_eq!(None, Operator::Concatenate.undo(1234, 5));
+        assert_eq!(None, Operator::Add.undo(1, 2));
+    }
+
+    #[test]
+    pub fn test_solvers_agree() {
+        let input = example_input();
+
+        for operators in [&Operator::ALL1[..], &Operator::ALL2[..]] {
+            for equation in input.iter() {
+                assert_eq!(equation.clone().try_solve(operators), equation.try_solve_reverse(operators), "{equation:?}");
+            }
+        }
+        assert_eq!(11387, calibration_result_forward(&input, &Operator::ALL2));
+        assert_eq!(11387, calibration_result(&input, &Operator::ALL2));
+    }
+
+    #[test]
+    pub fn test_part2() {
         let input: Input = example_input();
 
-        let (result, failed) = process_part1(&input).await.unwrap();
-        let result = process_part2(&failed, result).await.unwrap();
-        assert_eq!(11387, result);
+        let result = process_part2(&input).unwrap();
+        assert_eq!(Answer::Unsigned(11387), result);
     }
 }
@@ -1,14 +1,11 @@
 use std::collections::HashSet;
-use std::fmt::{Debug, Formatter};
 use std::hash::{Hash, Hasher};
 use std::str::FromStr;
-use std::time::SystemTime;
-use owo_colors::OwoColorize;
-use tracing::{debug, info, Instrument, Level, span, trace};
-use crate::days::Day;
+use owo_colors::AnsiColors;
+use crate::days::solver::{register_day, Answer, Solver};
+use crate::days::util::display::{Blend, Highlight, Highlighter};
 use crate::days::util::Grid;
-
-pub const DAY: Day = Day(8);
+use crate::days::util::bit_grid::BitGrid;
 
 fn char_to_index(character: char) -> u8 {
     match character {
@@ -19,14 +16,18 @@ fn char_to_index(character: char) -> u8 {
     }
 }
 
-fn color_character(character: char) -> String {
-    match char_to_index(character) % 4 {
-        0 => character.green().bold().to_string(),
-        1 => character.bright_red().bold().to_string(),
-        2 => character.bright_yellow().bold().to_string(),
-        3 => character.white().bold().to_string(),
-        _ => unreachable!(),
+fn highlight_character(character: char) -> Highlight {
+    if character == '.' {
+        return Highlight::new().symbol(character).dimmed();
     }
+    let color = match char_to_index(character) % 4 {
+        0 => AnsiColors::Green,
+        1 => AnsiColors::BrightRed,
+        2 => AnsiColors::BrightYellow,
+        3 => AnsiColors::White,
+        _ => unreachable!(),
+    };
+    Highlight::new().symbol(character).foreground(color).bold()
 }
 
 pub struct Antinode<'input, 'layer: 'input, 'pair: 'layer + 'input> {
@@ -35,35 +36,15 @@ pub struct Antinode<'input, 'layer: 'input, 'pair: 'layer + 'input> {
 }
 
 impl Antinode<'_, '_, '_> {
-    pub fn display<F: Fn(char, usize) -> Option<String>>(&self, postprocess: F) -> AntinodeDisplay<F> {
-        AntinodeDisplay {
-            anitnode: self,
-            postprocess,
-        }
+    pub fn highlighter(&self) -> Highlighter<'_> {
+        let position = self.position;
+        self.pair.highlighter()
+            .layer(3, Blend::Replace, move |index| (index == position)
+                .then_some(Highlight::new().symbol('#').foreground(AnsiColors::Yellow).background(AnsiColors::BrightMagenta)))
     }
 
-    pub fn mark(&self, mask: &mut [bool]) {
-        mask[self.position] = true;
-    }
-}
-
-pub struct AntinodeDisplay<'input, 'layer: 'input, 'pair: 'layer + 'input, 'antinode: 'pair + 'layer + 'input, F: Fn(char, usize) -> Option<String>> {
-    anitnode: &'antinode Antinode<'input, 'layer, 'pair>,
-    postprocess: F,
-}
-
-impl<F: Fn(char, usize) -> Option<String>> std::fmt::Display for AntinodeDisplay<'_, '_, '_, '_, F> {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.anitnode.pair.display(|character, index| {
-            if let Some(result) = (self.postprocess)(character, index) {
-                return Some(result);
-            }
-            if index == self.anitnode.position {
-                Some('#'.yellow().on_bright_purple().to_string())
-            } else {
-                None
-            }
-        }))
+    pub fn mark(&self, mask: &mut BitGrid) {
+        mask.set(self.position, true);
     }
 }
 
@@ -74,23 +55,21 @@ pub struct Pair<'input, 'layer: 'input> {
 }
 
 impl Pair<'_, '_> {
-    pub fn display<F: Fn(char, usize) -> Option<String>>(&self, postprocess: F) -> PairDisplay<F> {
-        PairDisplay {
-            pair: self,
-            postprocess,
-        }
+    pub fn highlighter(&self) -> Highlighter<'_> {
+        let (first, second) = (self.first, self.second);
+        self.layer.highlighter()
+            .layer(2, Blend::Over, move |index| (index == first || index == second)
+                .then_some(Highlight::new().background(AnsiColors::BrightCyan)))
     }
 
     pub fn antinodes(&self) -> Vec<Antinode<'_, '_, '_>> {
         let first_coordinate = self.layer.input.grid.index_to_coordinate(self.first);
         let second_coordinate = self.layer.input.grid.index_to_coordinate(self.second);
-        [
-            second_coordinate - first_coordinate + second_coordinate,
-            first_coordinate - second_coordinate + first_coordinate,
-        ]
+        // every antinode is one of the antennas reflected through the other one
+        [first_coordinate.rotate_around(second_coordinate, 2), second_coordinate.rotate_around(first_coordinate, 2)]
             .map(|coordinate| self.layer.input.grid.coordinate_to_index(coordinate))
             .into_iter()
-            .filter_map(|index| index.ok())
+            .flatten()
             .map(|position| Antinode {
                 pair: self,
                 position,
@@ -104,60 +83,36 @@ impl Pair<'_, '_> {
         let mut antinodes = Vec::new();
 
         let difference = first_coordinate - second_coordinate;
-        let mut coordinate = first_coordinate;
-        while let Ok(position) = self.layer.input.grid.coordinate_to_index(coordinate) {
-            antinodes.push(Antinode {
-                pair: self,
-                position,
-            });
-            coordinate += difference;
-        }
-
-        let difference = second_coordinate - first_coordinate;
-        let mut coordinate = second_coordinate;
-        while let Ok(position) = self.layer.input.grid.coordinate_to_index(coordinate) {
-            antinodes.push(Antinode {
-                pair: self,
-                position,
-            });
-            coordinate += difference;
+        for (start, step) in [(first_coordinate, difference), (second_coordinate, -difference)] {
+            let mut coordinate = start;
+            while let Some(position) = self.layer.input.grid.coordinate_to_index(coordinate) {
+                antinodes.push(Antinode {
+                    pair: self,
+                    position,
+                });
+                coordinate += step;
+            }
         }
 
         antinodes
     }
 }
 
-pub struct PairDisplay<'input, 'layer: 'input, 'pair: 'layer + 'input, F: Fn(char, usize) -> Option<String>> {
-    pair: &'pair Pair<'input, 'layer>,
-    postprocess: F,
-}
-
-impl<F: Fn(char, usize) -> Option<String>> std::fmt::Display for PairDisplay<'_, '_, '_, F> {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.pair.layer.display(|character, index| {
-            if let Some(result) = (self.postprocess)(character, index) {
-                return Some(result);
-            }
-            if index == self.pair.first || index == self.pair.second {
-                Some(color_character(character).on_bright_cyan().to_string())
-            } else {
-                None
-            }
-        }))
-    }
-}
-
 pub struct Layer<'input> {
     input: &'input Input,
     character: char,
 }
 
 impl Layer<'_> {
-    pub fn display<F: Fn(char, usize) -> Option<String>>(&self, postprocess: F) -> LayerDisplay<F> {
-        LayerDisplay {
-            layer: self,
-            postprocess,
-        }
+    pub fn highlighter(&self) -> Highlighter<'_> {
+        let grid = &self.input.grid;
+        let character = self.character;
+        self.input.highlighter()
+            .layer(1, Blend::Replace, move |index| if grid.as_slice()[index] == character {
+                Some(highlight_character(character))
+            } else {
+                Some(highlight_character('.'))
+            })
     }
 
     pub fn pairs(&self) -> Vec<Pair<'_, '_>> {
@@ -178,32 +133,6 @@ impl Layer<'_> {
     }
 }
 
-pub struct LayerDisplay<'input, 'layer: 'input, F: Fn(char, usize) -> Option<String>> {
-    layer: &'layer Layer<'input>,
-    postprocess: F,
-}
-
-impl<F: Fn(char, usize) -> Option<String>> std::fmt::Display for LayerDisplay<'_, '_, F> {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        let formatted_character = color_character(self.layer.character);
-        self.layer.input.grid.display(|character, index| {
-            let character = if character != self.layer.character {
-                '.'
-            } else {
-                character
-            };
-            if let Some(result) = (self.postprocess)(character, index) {
-                return result;
-            }
-            if character == '.' {
-                character.dimmed().to_string()
-            } else {
-                formatted_character.clone()
-            }
-        }).fmt(f)
-    }
-}
-
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct Input {
     grid: Grid,
@@ -219,11 +148,9 @@ impl Hash for Input {
 }
 
 impl Input {
-    pub fn display<F: Fn(char, usize) -> Option<String>>(&self, postprocess: F) -> InputDisplay<F> {
-        InputDisplay {
-            input: self,
-            postprocess,
-        }
+    pub fn highlighter(&self) -> Highlighter<'_> {
+        self.grid.highlighter(|&tile| Highlight::new().symbol(tile))
+            .layer(0, Blend::Replace, |index| Some(highlight_character(self.grid.as_slice()[index])))
     }
 
     pub fn layers(&self) -> Vec<Layer<'_>> {
@@ -236,44 +163,19 @@ impl Input {
     }
 }
 
-pub struct InputDisplay<'input, F: Fn(char, usize) -> Option<String>> {
-    input: &'input Input,
-    postprocess: F,
-}
-
-impl<F: Fn(char, usize) -> Option<String>> std::fmt::Display for InputDisplay<'_, F> {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        self.input.grid.display(|character, index| {
-            if let Some(result) = (self.postprocess)(character, index) {
-                return result;
-            }
-            if character == '.' {
-                character.dimmed().to_string()
-            } else {
-                color_character(character)
-            }
-        }).fmt(f)
-    }
-}
-
 impl FromStr for Input {
     type Err = eyre::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let grid: Grid = s.parse()?;
+        let characters = grid.histogram().into_keys()
+            .filter(|&character| character != '.')
+            .collect::<HashSet<_>>();
         let mut positions: [Vec<usize>; 62] = array_init::array_init(|_| Vec::new());
-
-        for (index, character) in grid.as_slice().iter().enumerate() {
-            if *character != '.' {
-                positions[char_to_index(*character) as usize].push(index);
-            }
+        for &character in &characters {
+            positions[char_to_index(character) as usize] = grid.positions_of(character);
         }
 
-        let characters = grid.as_slice().iter()
-            .filter(|character| **character != '.')
-            .cloned()
-            .collect();
-
         Ok(Self {
             grid,
             characters,
@@ -282,66 +184,64 @@ impl FromStr for Input {
     }
 }
 
-pub fn process_part1(input: &Input) -> eyre::Result<usize> {
-    let mut mask = vec![false; input.grid.as_slice().len()];
-    // println!("{}\n", input.display(|_, _| None));
+pub fn process_part1(input: &Input) -> eyre::Result<Answer> {
+    let mut mask = BitGrid::new(input.grid.width(), input.grid.height());
+    // println!("{}\n", input.highlighter());
     for layer in input.layers() {
-        // println!("{}\n", layer.display(|_, _| None));
+        // println!("{}\n", layer.highlighter());
         for pair in layer.pairs() {
-            // println!("{}\n", pair.display(|_, _| None));
+            // println!("{}\n", pair.highlighter());
             for antinode in pair.antinodes() {
-                // println!("{}\n", antinode.display(|_, _| None));
+                // println!("{}\n", antinode.highlighter());
                 antinode.mark(&mut mask);
             }
         }
     }
-    let result: usize = mask.iter().filter(|has_antinode| **has_antinode).count();
+    let result = mask.count_ones();
 
-    Ok(result)
+    Ok(result.into())
 }
 
-pub fn process_part2(input: &Input) -> eyre::Result<usize> {
-    let mut mask = vec![false; input.grid.as_slice().len()];
-    // println!("{}\n", input.display(|_, _| None));
+pub fn process_part2(input: &Input) -> eyre::Result<Answer> {
+    let mut mask = BitGrid::new(input.grid.width(), input.grid.height());
+    // println!("{}\n", input.highlighter());
     for layer in input.layers() {
-        // println!("{}\n", layer.display(|_, _| None));
+        // println!("{}\n", layer.highlighter());
         for pair in layer.pairs() {
-            // println!("{}\n", pair.display(|_, _| None));
+            // println!("{}\n", pair.highlighter());
             for antinode in pair.antinodes2() {
-                // println!("{}\n", antinode.display(|_, _| None));
+                // println!("{}\n", antinode.highlighter());
                 antinode.mark(&mut mask);
             }
         }
     }
-    let result: usize = mask.iter().filter(|has_antinode| **has_antinode).count();
+    let result = mask.count_ones();
 
-    Ok(result)
+    Ok(result.into())
 }
 
-pub async fn run() -> eyre::Result<()> {
-    let day_span = span!(Level::ERROR, "", "{}", DAY);
-    async {
-        info!("Running {DAY}");
-
-        let raw_input = super::get_input(DAY).await?;
-        trace!(raw_input);
-
-        let input = raw_input.parse()?;
-        debug!(?input);
-
-        let start1 = SystemTime::now();
-        let result1 = process_part1(&input)?;
-        let end1 = SystemTime::now();
-        let start2 = SystemTime::now();
-        let result2 = process_part2(&input)?;
-        let end2 = SystemTime::now();
-        println!("{DAY} result:");
-        println!("  part 1: {result1} in {:?}", end1.duration_since(start1).unwrap());
-        println!("  part 2: {result2} in {:?}", end2.duration_since(start2).unwrap());
-        Ok(())
+pub struct Solution;
+
+impl Solver for Solution {
+    type Parsed = Input;
+
+    fn parse(input: &str) -> eyre::Result<Self::Parsed> {
+        input.parse()
+    }
+
+    fn part1(input: &Self::Parsed) -> eyre::Result<Answer> {
+        process_part1(input)
     }
-        .instrument(day_span.or_current())
-        .await
+
+    fn part2(input: &Self::Parsed) -> eyre::Result<Answer> {
+        process_part2(input)
+    }
+}
+
+register_day! {
+    day: 8,
+    status: Implemented,
+    solver: Solution,
 }
 
 #[cfg(test)]
@@ -369,7 +269,7 @@ mod test {
         let input = example_input();
 
         let result = process_part1(&input).unwrap();
-        assert_eq!(14, result);
+        assert_eq!(Answer::Unsigned(14), result);
     }
 
     #[test]
@@ -377,6 +277,6 @@ mod test {
         let input = example_input();
 
         let result = process_part2(&input).unwrap();
-        assert_eq!(34, result);
+        assert_eq!(Answer::Unsigned(34), result);
     }
 }
@@ -1,11 +1,24 @@
 use std::cmp::min;
+use std::collections::BinaryHeap;
 use std::str::FromStr;
-use std::time::SystemTime;
 use eyre::eyre;
-use tracing::{debug, info, Instrument, Level, span, trace};
-use crate::days::Day;
-
-pub const DAY: Day = Day(9);
+use crate::days::solver::{register_day, Answer, Solver};
+use crate::days::approach::{Approach, ApproachHistory};
+use crate::days::util::interval_set::IntervalSet;
+
+pub const APPROACH_HISTORY: ApproachHistory = ApproachHistory {
+    day: DAY,
+    approaches: &[
+        Approach { name: "filler-buckets", description: "Fill every free space from the back with the highest file of the best fitting length, kept in a max-heap per length" },
+        Approach { name: "free-space", description: "Move every file from the back into the leftmost free space of an interval set that fits it" },
+    ],
+    active: "filler-buckets",
+    solve: |raw_input| {
+        let input: Input = raw_input.parse()?;
+        process_part2(&input)?;
+        Ok(())
+    },
+};
 
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub struct FileBlock {
@@ -108,7 +121,7 @@ fn sum_range(start: usize, end: usize) -> usize {
     // (start..=end).sum::<usize>()
 }
 
-pub fn process_part1(input: &Input) -> eyre::Result<usize> {
+pub fn process_part1(input: &Input) -> eyre::Result<Answer> {
     let filled = input.fill_holes();
 
     let checksum = filled.iter()
@@ -116,14 +129,44 @@ pub fn process_part1(input: &Input) -> eyre::Result<usize> {
         .map(|(index, &val)| index * val)
         .sum();
 
-    Ok(checksum)
+    Ok(checksum.into())
 }
 
-pub fn process_part2(input: &Input) -> eyre::Result<usize> {
-    let mut filler_sizes: [_; 9] = array_init::array_init(|_| vec![]);
+/// Moves every file, from the highest id down, into the leftmost free space before it that fits
+/// it. The space a file leaves behind is never filled, as the files still to move lie before it.
+pub fn compact_free_space(input: &Input) -> usize {
+    let mut free = input.empty_blocks.iter()
+        .map(|empty| empty.index..empty.index + empty.len as usize)
+        .collect::<IntervalSet<_>>();
+
+    input.file_blocks.iter()
+        .rev()
+        .filter(|file| file.len != 0)
+        .map(|file| {
+            let len = file.len as usize;
+            let target = free.ranges().iter()
+                .take_while(|range| range.start < file.index)
+                .find(|range| range.end - range.start >= len)
+                .map(|range| range.start);
+            let index = match target {
+                Some(index) => {
+                    free.remove(index..index + len);
+                    index
+                }
+                None => file.index,
+            };
+            file.id * sum_range(index, index + len - 1)
+        })
+        .sum()
+}
+
+/// Fills every free space from the front with the highest file behind it that fits, keeping the
+/// ids of the files still to move in a max-heap per file size.
+pub fn compact_filler_heaps(input: &Input) -> usize {
+    let mut fillers: [BinaryHeap<usize>; 9] = array_init::array_init(|_| BinaryHeap::new());
     for block in &input.file_blocks {
         if block.len != 0 {
-            filler_sizes[block.len as usize - 1].push(block);
+            fillers[block.len as usize - 1].push(block.id);
         }
     }
 
@@ -131,57 +174,64 @@ pub fn process_part2(input: &Input) -> eyre::Result<usize> {
     for empty in &input.empty_blocks {
         let mut space = empty.len;
 
-        if space == 0 {
-            continue;
-        }
-
-        while let Some(filler) = find_best_filler(&mut filler_sizes, empty, space) {
-            let filler = filler.pop().unwrap();
+        while let Some(filler) = find_best_filler(input, &mut fillers, empty, space) {
+            let filler = &input.file_blocks[filler.pop().unwrap()];
             let index = empty.index + (empty.len - space) as usize;
             checksum += filler.id * sum_range(index, index + filler.len as usize - 1);
             space -= filler.len;
         }
     }
 
-    let filler_sum = filler_sizes.iter()
+    let filler_sum = fillers.iter()
         .flat_map(|filler| filler.iter())
+        .map(|&id| &input.file_blocks[id])
         .map(|filler| filler.id * sum_range(filler.index, filler.index + filler.len as usize - 1))
         .sum::<usize>();
 
-    Ok(checksum + filler_sum)
+    checksum + filler_sum
 }
 
-fn find_best_filler<'a, 'b>(filler_sizes: &'a mut [Vec<&'b FileBlock>; 9], empty: &EmptyBlock, space:  u8) -> Option<&'a mut Vec<&'b FileBlock>> {
-    filler_sizes[0..space as usize]
+/// The heap of the highest file that fits into `space` and lies behind `empty`. Files are in id
+/// order on the disk, so if the highest file of a size lies before `empty`, all others do as well.
+fn find_best_filler<'a>(input: &Input, fillers: &'a mut [BinaryHeap<usize>; 9], empty: &EmptyBlock, space: u8) -> Option<&'a mut BinaryHeap<usize>> {
+    fillers[0..space as usize]
         .iter_mut()
-        .filter(|filler| filler.last().is_some_and(|filler| filler.index > empty.index)) // GIANT THANK YOU TO https://www.reddit.com/r/adventofcode/comments/1hajykk/comment/m19cr94/?utm_source=share&utm_medium=web3x&utm_name=web3xcss&utm_term=1&utm_content=share_button
-        .max_by(|a, b| a.last().unwrap().id.cmp(&b.last().unwrap().id))
+        .filter(|filler| filler.peek().is_some_and(|&id| input.file_blocks[id].index > empty.index)) // GIANT THANK YOU TO https://www.reddit.com/r/adventofcode/comments/1hajykk/comment/m19cr94/?utm_source=share&utm_medium=web3x&utm_name=web3xcss&utm_term=1&utm_content=share_button
+        .max_by_key(|filler| *filler.peek().unwrap())
 }
 
-pub async fn run() -> eyre::Result<()> {
-    let day_span = span!(Level::ERROR, "", "{}", DAY);
-    async {
-        info!("Running {DAY}");
-
-        let raw_input = super::get_input(DAY).await?;
-        trace!(raw_input);
-
-        let input = raw_input.parse()?;
-        debug!(?input);
-
-        let start1 = SystemTime::now();
-        let result1 = process_part1(&input)?;
-        let end1 = SystemTime::now();
-        let start2 = SystemTime::now();
-        let result2 = process_part2(&input)?;
-        let end2 = SystemTime::now();
-        println!("{DAY} result:");
-        println!("  part 1: {result1} in {:?}", end1.duration_since(start1).unwrap());
-        println!("  part 2: {result2} in {:?}", end2.duration_since(start2).unwrap());
-        Ok(())
+pub fn process_part2(input: &Input) -> eyre::Result<Answer> {
+    let result = match APPROACH_HISTORY.selected() {
+        "free-space" => compact_free_space(input),
+        _ => compact_filler_heaps(input),
+    };
+
+    Ok(result.into())
+}
+
+pub struct Solution;
+
+impl Solver for Solution {
+    type Parsed = Input;
+
+    fn parse(input: &str) -> eyre::Result<Self::Parsed> {
+        input.parse()
     }
-        .instrument(day_span.or_current())
-        .await
+
+    fn part1(input: &Self::Parsed) -> eyre::Result<Answer> {
+        process_part1(input)
+    }
+
+    fn part2(input: &Self::Parsed) -> eyre::Result<Answer> {
+        process_part2(input)
+    }
+}
+
+register_day! {
+    day: 9,
+    status: Implemented,
+    solver: Solution,
+    approach_history: APPROACH_HISTORY,
 }
 
 #[cfg(test)]
@@ -197,7 +247,7 @@ mod test {
         let input = example_input();
 
         let result = process_part1(&input).unwrap();
-        assert_eq!(1928, result);
+        assert_eq!(Answer::Unsigned(1928), result);
     }
 
     #[test]
@@ -208,7 +258,7 @@ mod test {
         let input = "11013".parse().unwrap();
 
         let result = process_part1(&input).unwrap();
-        assert_eq!(0 + (1..4).map(|i| i * 2).sum::<usize>(), result);
+        assert_eq!(Answer::from(0 + (1..4).map(|i| i * 2).sum::<usize>()), result);
 
         // 0.........111112223333
         // 03333.....11111222
@@ -217,7 +267,7 @@ mod test {
         let input = "1950304".parse().unwrap();
 
         let result = process_part1(&input).unwrap();
-        assert_eq!([0, 3, 3, 3, 3, 2, 2, 2, 1, 1, 1, 1, 1].iter().enumerate().map(|(index, &val)| index * val).sum::<usize>(), result);
+        assert_eq!(Answer::from([0, 3, 3, 3, 3, 2, 2, 2, 1, 1, 1, 1, 1].iter().enumerate().map(|(index, &val)| index * val).sum::<usize>()), result);
 
         // 0..1..2
         // 02.1
@@ -225,7 +275,7 @@ mod test {
         let input = "12121".parse().unwrap();
 
         let result = process_part1(&input).unwrap();
-        assert_eq!([0, 2, 1].iter().enumerate().map(|(index, &val)| index * val).sum::<usize>(), result);
+        assert_eq!(Answer::from([0, 2, 1].iter().enumerate().map(|(index, &val)| index * val).sum::<usize>()), result);
     }
 
     #[test]
@@ -233,23 +283,23 @@ mod test {
         let input = example_input();
 
         let result = process_part2(&input).unwrap();
-        assert_eq!(2858, result);
+        assert_eq!(Answer::Unsigned(2858), result);
     }
 
     #[test]
     pub fn test_custom_part2() {
         let input = "001".parse().unwrap();
         let result = process_part2(&input).unwrap();
-        assert_eq!(0, result);
+        assert_eq!(Answer::Unsigned(0), result);
 
         let input = "0630201".parse().unwrap();
         let result = process_part2(&input).unwrap();
-        assert_eq!(18, result);
+        assert_eq!(Answer::Unsigned(18), result);
 
         // input from https://www.reddit.com/r/adventofcode/comments/1hajykk/2024_day_9_part_2_cant_get_part_2_to_work_any/
         let input = "12235".parse().unwrap();
         let result = process_part2(&input).unwrap();
-        assert_eq!(1 + 2 + (8..=12).sum::<usize>() * 2, result);
+        assert_eq!(Answer::from(1 + 2 + (8..=12).sum::<usize>() * 2), result);
     }
 
     #[test]
@@ -257,10 +307,23 @@ mod test {
         // input from https://www.reddit.com/r/adventofcode/comments/1haauty/2024_day_9_part_2_bonus_test_case_that_might_make/
         let input = include_str!("../../test/input/day9_evil1.in").parse().unwrap();
         let result = process_part2(&input).unwrap();
-        assert_eq!(97898222299196, result);
+        assert_eq!(Answer::Unsigned(97898222299196), result);
 
         let input = include_str!("../../test/input/day9_evil2.in").parse().unwrap();
         let result = process_part2(&input).unwrap();
-        assert_eq!(5799706413896802, result);
+        assert_eq!(Answer::Unsigned(5799706413896802), result);
+    }
+
+    #[test]
+    pub fn test_free_space() {
+        for raw_input in ["2333133121414131402", "001", "0630201", "12235"] {
+            let input = raw_input.parse().unwrap();
+            assert_eq!(process_part2(&input).unwrap(), Answer::from(compact_free_space(&input)), "{raw_input}");
+            assert_eq!(compact_free_space(&input), compact_filler_heaps(&input), "{raw_input}");
+        }
+
+        let input = include_str!("../../test/input/day9_evil1.in").parse().unwrap();
+        assert_eq!(97898222299196, compact_free_space(&input));
+        assert_eq!(97898222299196, compact_filler_heaps(&input));
     }
 }
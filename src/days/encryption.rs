@@ -0,0 +1,61 @@
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use eyre::eyre;
+use sha2::{Digest, Sha256};
+
+use crate::days::config;
+
+const NONCE_LEN: usize = 12;
+
+/// The passphrase to encrypt cached inputs with: `AOC_INPUT_KEY` if set, or the session token if
+/// `encrypt_inputs` is enabled in the config. Inputs are stored in plain text without one.
+pub fn passphrase() -> eyre::Result<Option<String>> {
+    if let Ok(passphrase) = dotenvy::var("AOC_INPUT_KEY") {
+        return Ok(Some(passphrase));
+    }
+
+    if config::Config::load()?.encrypt_inputs {
+        Ok(Some(config::session()?))
+    } else {
+        Ok(None)
+    }
+}
+
+fn cipher(passphrase: &str) -> ChaCha20Poly1305 {
+    let key = Sha256::digest(passphrase.as_bytes());
+    ChaCha20Poly1305::new(Key::from_slice(&key))
+}
+
+/// Encrypts `plaintext` with a random nonce, which is prepended to the ciphertext.
+pub fn encrypt(plaintext: &str, passphrase: &str) -> eyre::Result<Vec<u8>> {
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let ciphertext = cipher(passphrase).encrypt(&nonce, plaintext.as_bytes())
+        .map_err(|_| eyre!("Failed to encrypt"))?;
+
+    Ok(nonce.into_iter().chain(ciphertext).collect())
+}
+
+pub fn decrypt(data: &[u8], passphrase: &str) -> eyre::Result<String> {
+    if data.len() < NONCE_LEN {
+        return Err(eyre!("The encrypted data is truncated"));
+    }
+    let (nonce, ciphertext) = data.split_at(NONCE_LEN);
+    let plaintext = cipher(passphrase).decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| eyre!("Failed to decrypt, the passphrase is probably wrong"))?;
+
+    Ok(String::from_utf8(plaintext)?)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    pub fn test_round_trip() {
+        let encrypted = encrypt("3   4\n4   3\n", "passphrase").unwrap();
+
+        assert_eq!("3   4\n4   3\n", decrypt(&encrypted, "passphrase").unwrap());
+        assert!(decrypt(&encrypted, "wrong").is_err());
+        assert_ne!(encrypted, encrypt("3   4\n4   3\n", "passphrase").unwrap());
+    }
+}
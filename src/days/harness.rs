@@ -0,0 +1,91 @@
+use std::fmt::{Debug, Display, Formatter};
+use std::time::{Duration, SystemTime};
+use tracing::{debug, info, Instrument, Level, span, trace};
+
+use super::{answer_cache, Day, ExpectedAnswers, Source};
+use super::approach::ApproachHistory;
+use super::solver::{Answer, Solver};
+
+/// The answers of a run of a day with the time parsing and each part took, printed by whoever
+/// started the run so concurrently running days don't interleave their output.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DayResult {
+    pub day: Day,
+    pub source: Source,
+    pub parse: Duration,
+    pub part1: (Answer, Duration),
+    pub part2: (Answer, Duration),
+    /// The answers were taken from the [`answer_cache`](super::answer_cache) without solving
+    pub cached: bool,
+    /// The answers the puzzle description gives, when the run was on its example
+    pub expected: Option<ExpectedAnswers>,
+}
+
+impl Display for DayResult {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.cached {
+            writeln!(f, "{} result ({}) [cached]:", self.day, self.source)?;
+            writeln!(f, "  part 1: {}", self.part1.0)?;
+            return write!(f, "  part 2: {}", self.part2.0);
+        }
+        writeln!(f, "{} result ({}):", self.day, self.source)?;
+        writeln!(f, "  parsed in {:?}", self.parse)?;
+        write!(f, "  part 1: {} in {:?}", self.part1.0, self.part1.1)?;
+        if let Some(expected) = &self.expected {
+            write!(f, " (expected {})", expected.part1.as_deref().unwrap_or("unknown"))?;
+        }
+        write!(f, "\n  part 2: {} in {:?}", self.part2.0, self.part2.1)?;
+        if let Some(expected) = &self.expected {
+            write!(f, " (expected {})", expected.part2.as_deref().unwrap_or("unknown"))?;
+        }
+        Ok(())
+    }
+}
+
+/// The standard harness of a day: fetches the input, parses it and solves both parts with `S` in
+/// spans, recording the selected strategy if the day has an `approach_history`.
+pub async fn run_day<S: Solver>(day: Day, approach_history: Option<ApproachHistory>) -> eyre::Result<DayResult>
+where
+    S::Parsed: Debug,
+{
+    let strategy = approach_history.map(|history| history.selected());
+    let day_span = span!(Level::ERROR, "day", day = *day);
+    async {
+        info!("Running {day}");
+
+        let puzzle_input = super::get_input(super::YEAR, day).await?;
+        let raw_input = &puzzle_input.body;
+        trace!(raw_input);
+        if let Some(result) = answer_cache::cached_result(day, &puzzle_input) {
+            return Ok(result);
+        }
+
+        let start_parse = SystemTime::now();
+        let input = span!(Level::ERROR, "parse", day = *day, phase = "parse")
+            .in_scope(|| S::parse(raw_input))?;
+        let end_parse = SystemTime::now();
+        debug!(?input);
+
+        let start1 = SystemTime::now();
+        let result1 = span!(Level::ERROR, "solve", day = *day, part = 1, phase = "solve", strategy)
+            .in_scope(|| S::part1(&input))?;
+        let end1 = SystemTime::now();
+        let start2 = SystemTime::now();
+        let result2 = span!(Level::ERROR, "solve", day = *day, part = 2, phase = "solve", strategy)
+            .in_scope(|| S::part2(&input))?;
+        let end2 = SystemTime::now();
+        let result = DayResult {
+            day,
+            source: puzzle_input.source,
+            parse: end_parse.duration_since(start_parse).unwrap(),
+            part1: (result1, end1.duration_since(start1).unwrap()),
+            part2: (result2, end2.duration_since(start2).unwrap()),
+            cached: false,
+            expected: puzzle_input.expected.clone(),
+        };
+        answer_cache::record(&puzzle_input, &result);
+        Ok(result)
+    }
+        .instrument(day_span.or_current())
+        .await
+}
@@ -0,0 +1,130 @@
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+
+use eyre::WrapErr;
+use owo_colors::OwoColorize;
+use serde::{Deserialize, Serialize};
+
+use super::Day;
+
+/// When a star was collected.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct Star {
+    pub get_star_ts: u64,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Member {
+    pub id: u64,
+    /// Anonymous users have no name
+    pub name: Option<String>,
+    pub stars: usize,
+    pub local_score: usize,
+    pub last_star_ts: u64,
+    /// The stars by day and part
+    #[serde(default)]
+    pub completion_day_level: BTreeMap<usize, BTreeMap<u8, Star>>,
+}
+
+impl Member {
+    pub fn display_name(&self) -> String {
+        self.name.clone()
+            .unwrap_or_else(|| format!("(anonymous user #{})", self.id))
+    }
+
+    /// How many parts of `day` were solved.
+    pub fn stars_on(&self, day: usize) -> usize {
+        self.completion_day_level.get(&day)
+            .map_or(0, |parts| parts.len())
+    }
+}
+
+/// A private leaderboard as returned by its JSON API.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Leaderboard {
+    pub owner_id: u64,
+    pub event: String,
+    pub members: BTreeMap<String, Member>,
+}
+
+impl Leaderboard {
+    /// The members by local score, ties broken by who got their last star first.
+    pub fn ranking(&self) -> Vec<&Member> {
+        let mut members = self.members.values().collect::<Vec<_>>();
+        members.sort_by_key(|member| (std::cmp::Reverse(member.local_score), member.last_star_ts, member.id));
+        members
+    }
+}
+
+impl Display for Leaderboard {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let ranking = self.ranking();
+        let rank_width = ranking.len().to_string().len();
+        let score_width = ranking.first()
+            .map_or(1, |member| member.local_score.to_string().len());
+
+        write!(f, "{:rank_width$}  {:score_width$}  ", "", "")?;
+        for day in Day::ALL {
+            match *day / 10 {
+                0 => write!(f, " ")?,
+                tens => write!(f, "{}", tens.dimmed())?,
+            }
+        }
+        writeln!(f)?;
+        write!(f, "{:rank_width$}  {:score_width$}  ", "", "")?;
+        for day in Day::ALL {
+            write!(f, "{}", (*day % 10).dimmed())?;
+        }
+
+        for (rank, member) in ranking.into_iter().enumerate() {
+            writeln!(f)?;
+            write!(f, "{:>rank_width$}) {:>score_width$}  ", rank + 1, member.local_score)?;
+            for day in Day::ALL {
+                match member.stars_on(*day) {
+                    0 => write!(f, "{}", '*'.dimmed())?,
+                    1 => write!(f, "{}", '*'.bright_blue())?,
+                    _ => write!(f, "{}", '*'.bright_yellow())?,
+                }
+            }
+            write!(f, " {} ({} stars)", member.display_name(), member.stars)?;
+        }
+        Ok(())
+    }
+}
+
+/// Fetches the private leaderboard `id` with the session of the current profile. The last response
+/// is cached in `leaderboards/` and only downloaded again if it has changed.
+pub async fn get_leaderboard(id: u64) -> eyre::Result<Leaderboard> {
+    let leaderboard_dir = super::cache_dir(Path::new("leaderboards"))?;
+    let leaderboard_file = leaderboard_dir.join(format!("{id}.json"));
+
+    let leaderboard = super::fetch_cached(&format!("https://adventofcode.com/{}/leaderboard/private/view/{id}.json", super::YEAR), "application/json", &format!("leaderboard {id}"), &leaderboard_file).await?;
+    serde_json::from_str(&leaderboard)
+        .wrap_err(format!("Failed to parse leaderboard {id}"))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const LEADERBOARD: &str = r#"{"owner_id":1,"event":"2024","members":{
+        "1":{"id":1,"name":"owner","stars":3,"local_score":10,"global_score":0,"last_star_ts":1733120000,
+             "completion_day_level":{"1":{"1":{"get_star_ts":1733030000,"star_index":0},"2":{"get_star_ts":1733031000,"star_index":1}},"2":{"1":{"get_star_ts":1733120000,"star_index":2}}}},
+        "2":{"id":2,"name":null,"stars":0,"local_score":0,"global_score":0,"last_star_ts":0,"completion_day_level":{}},
+        "3":{"id":3,"name":"rival","stars":3,"local_score":10,"global_score":0,"last_star_ts":1733110000,
+             "completion_day_level":{"1":{"1":{"get_star_ts":1733040000,"star_index":0}},"2":{"1":{"get_star_ts":1733100000,"star_index":1},"2":{"get_star_ts":1733110000,"star_index":2}}}}
+    }}"#;
+
+    #[test]
+    pub fn test_parse_leaderboard() {
+        let leaderboard: Leaderboard = serde_json::from_str(LEADERBOARD).unwrap();
+
+        let ranking = leaderboard.ranking();
+        assert_eq!(vec![3, 1, 2], ranking.iter().map(|member| member.id).collect::<Vec<_>>());
+        assert_eq!(2, ranking[1].stars_on(1));
+        assert_eq!(1, ranking[1].stars_on(2));
+        assert_eq!(0, ranking[1].stars_on(3));
+        assert_eq!("(anonymous user #2)", ranking[2].display_name());
+    }
+}
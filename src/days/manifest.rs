@@ -0,0 +1,105 @@
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+
+use eyre::WrapErr;
+use serde::{Deserialize, Serialize};
+
+use super::Day;
+
+/// The SHA-256 hashes of the inputs the answers in `answers.toml` were produced from, stored in
+/// `input.manifest.toml` in the crate root. The inputs themselves aren't committed, so this is how
+/// a checkout can tell whether its inputs are the recorded ones.
+///
+/// ```toml
+/// [2024]
+/// day1 = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Manifest {
+    years: BTreeMap<String, BTreeMap<String, String>>,
+}
+
+/// How a local input compares to the one recorded in the [`Manifest`].
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Verification {
+    Matches,
+    Differs { recorded: String, local: String },
+    /// There is no cached input
+    Missing,
+    /// The manifest has no hash for the day
+    Unrecorded,
+}
+
+impl Display for Verification {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Matches => write!(f, "matches the manifest"),
+            Self::Differs { recorded, local } => write!(f, "differs from the manifest, {local} instead of {recorded}"),
+            Self::Missing => write!(f, "not cached"),
+            Self::Unrecorded => write!(f, "not in the manifest"),
+        }
+    }
+}
+
+impl Manifest {
+    pub fn file() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("input.manifest.toml")
+    }
+
+    pub fn load() -> eyre::Result<Self> {
+        let file = Self::file();
+        match std::fs::read_to_string(&file) {
+            Ok(manifest) => toml::from_str(&manifest)
+                .wrap_err(format!("Failed to parse {}", file.display())),
+            Err(_) => Ok(Self::default()),
+        }
+    }
+
+    pub fn save(&self) -> eyre::Result<()> {
+        let file = Self::file();
+        std::fs::write(&file, toml::to_string(self)?)
+            .wrap_err(format!("Failed to write {}", file.display()))
+    }
+
+    pub fn get(&self, year: u16, day: Day) -> Option<&str> {
+        self.years.get(&year.to_string())?
+            .get(&format!("day{}", *day))
+            .map(String::as_str)
+    }
+
+    pub fn record(&mut self, year: u16, day: Day, sha256: String) {
+        self.years.entry(year.to_string()).or_default()
+            .insert(format!("day{}", *day), sha256);
+    }
+
+    /// Compares an input, if it is cached, with the recorded one.
+    pub fn verify(&self, year: u16, day: Day, input: Option<&str>) -> Verification {
+        match (self.get(year, day), input.map(super::sha256)) {
+            (_, None) => Verification::Missing,
+            (None, Some(_)) => Verification::Unrecorded,
+            (Some(recorded), Some(local)) if recorded == local => Verification::Matches,
+            (Some(recorded), Some(local)) => Verification::Differs { recorded: recorded.to_string(), local },
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::days::sha256;
+    use super::*;
+
+    #[test]
+    pub fn test_verify() {
+        let mut manifest = Manifest::default();
+        manifest.record(2024, Day(1), sha256("abc"));
+        let manifest: Manifest = toml::from_str(&toml::to_string(&manifest).unwrap()).unwrap();
+
+        assert_eq!(Verification::Matches, manifest.verify(2024, Day(1), Some("abc")));
+        assert!(matches!(manifest.verify(2024, Day(1), Some("abd")), Verification::Differs { .. }));
+        assert_eq!(Verification::Missing, manifest.verify(2024, Day(1), None));
+        assert_eq!(Verification::Unrecorded, manifest.verify(2024, Day(2), Some("abc")));
+        assert_eq!(Verification::Unrecorded, manifest.verify(2023, Day(1), Some("abc")));
+    }
+}
@@ -0,0 +1,176 @@
+use std::path::Path;
+
+use eyre::{eyre, WrapErr};
+use scraper::{ElementRef, Html, Selector};
+use serde::{Deserialize, Serialize};
+
+use super::Day;
+
+/// Returns the HTML page of a day's puzzle, downloading it on first use.
+///
+/// The second part is only included once the first part has been solved with the current session,
+/// so until then the cached page is revalidated on every use.
+pub async fn get_puzzle_page(day: Day) -> eyre::Result<String> {
+    let puzzle_dir = super::cache_dir(Path::new("puzzles"))?;
+
+    let page_file = puzzle_dir.join(format!("day{}.html", *day));
+    let page = std::fs::read_to_string(&page_file);
+
+    match page {
+        Ok(page) if has_part2(&page) => Ok(page),
+        _ => super::fetch_cached(&super::day_url(super::YEAR, day), "text/html", &format!("{day} puzzle"), &page_file).await,
+    }
+}
+
+fn has_part2(page: &str) -> bool {
+    page.contains(r#"id="part2""#)
+}
+
+/// Returns the description of a day's puzzle as Markdown.
+pub async fn get_puzzle(day: Day) -> eyre::Result<String> {
+    let puzzle_dir = super::cache_dir(Path::new("puzzles"))?;
+
+    let puzzle_file = puzzle_dir.join(format!("day{}.md", *day));
+    let puzzle = to_markdown(&get_puzzle_page(day).await?)
+        .wrap_err(format!("Failed to extract {day} puzzle"))?;
+    std::fs::write(&puzzle_file, &puzzle)
+        .context(format!("Failed to write puzzle to {}", puzzle_file.display()))?;
+
+    Ok(puzzle)
+}
+
+/// The first example of a puzzle together with the answers the description gives for it.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct Example {
+    #[serde(skip)]
+    pub input: String,
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+/// Returns the example of a day's puzzle, extracting it from the puzzle page on first use.
+///
+/// The example is stored as `test/input/dayN_example.in` with the expected answers next to it in
+/// `test/input/dayN_example.toml`, where they can be corrected by hand if the extraction guessed wrong.
+pub async fn get_example(day: Day) -> eyre::Result<Example> {
+    let example_dir = super::cache_dir(Path::new("test/input"))?;
+
+    let input_file = example_dir.join(format!("day{}_example.in", *day));
+    let answers_file = example_dir.join(format!("day{}_example.toml", *day));
+
+    if let (Ok(input), Ok(answers)) = (std::fs::read_to_string(&input_file), std::fs::read_to_string(&answers_file)) {
+        let answers: Example = toml::from_str(&answers)
+            .wrap_err(format!("Failed to parse {}", answers_file.display()))?;
+        Ok(Example { input, ..answers })
+    } else {
+        let example = extract_example(&get_puzzle_page(day).await?)
+            .wrap_err(format!("Failed to extract {day} example"))?;
+        std::fs::write(&input_file, &example.input)
+            .context(format!("Failed to write example to {}", input_file.display()))?;
+        std::fs::write(&answers_file, toml::to_string(&example)?)
+            .context(format!("Failed to write example answers to {}", answers_file.display()))?;
+
+        Ok(example)
+    }
+}
+
+fn to_markdown(page: &str) -> eyre::Result<String> {
+    let document = Html::parse_document(page);
+    let articles = Selector::parse("article").unwrap();
+
+    let parts = document.select(&articles)
+        .map(|article| html2md::parse_html(&article.inner_html()))
+        .collect::<Vec<_>>();
+
+    if parts.is_empty() {
+        Err(eyre!("The page does not contain a puzzle description"))
+    } else {
+        Ok(parts.join("\n\n"))
+    }
+}
+
+/// Takes the first code block of the description as the example and the last emphasized code
+/// of each part as its answer, which is where the puzzles usually reveal the example's solution.
+fn extract_example(page: &str) -> eyre::Result<Example> {
+    let document = Html::parse_document(page);
+    let articles = Selector::parse("article").unwrap();
+    let code_blocks = Selector::parse("pre > code").unwrap();
+    let answers = Selector::parse("code > em").unwrap();
+
+    let mut parts = document.select(&articles);
+    let part1 = parts.next()
+        .ok_or(eyre!("The page does not contain a puzzle description"))?;
+    let part2 = parts.next();
+
+    let input = part1.select(&code_blocks)
+        .next()
+        .ok_or(eyre!("The puzzle description does not contain an example"))?
+        .text()
+        .collect();
+    let answer = |part: ElementRef| part.select(&answers)
+        .last()
+        .map(|answer| answer.text().collect());
+
+    Ok(Example {
+        input,
+        part1: answer(part1),
+        part2: part2.and_then(answer),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    pub fn test_to_markdown() {
+        let page = r#"<html><body><main>
+            <article class="day-desc"><h2>--- Day 1: Historian Hysteria ---</h2><p>Pair up the <em>smallest</em> numbers.</p></article>
+            <p>Your puzzle answer was <code>11</code>.</p>
+            <article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>Count the <code>3</code>s.</p></article>
+        </main></body></html>"#;
+
+        let markdown = to_markdown(page).unwrap();
+        assert!(markdown.starts_with("\\--- Day 1: Historian Hysteria ---\n"));
+        assert!(markdown.contains("Pair up the *smallest* numbers."));
+        assert!(markdown.contains("Count the `3`s."));
+        assert!(!markdown.contains("Your puzzle answer"));
+    }
+
+    #[test]
+    pub fn test_to_markdown_missing_article() {
+        assert!(to_markdown("<html><body><p>Please log in</p></body></html>").is_err());
+    }
+
+    #[test]
+    pub fn test_extract_example() {
+        let page = r#"<html><body><main>
+            <article class="day-desc"><h2>--- Day 1: Historian Hysteria ---</h2>
+            <p>For example:</p>
+            <pre><code>3   4
+4   3
+</code></pre>
+            <p>The first pair is <code><em>3</em></code> and <code>4</code>.</p>
+            <pre><code>not the example</code></pre>
+            <p>The total distance is <code><em>11</em></code>.</p></article>
+            <article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>The similarity score is <code><em>31</em></code>.</p></article>
+        </main></body></html>"#;
+
+        let example = extract_example(page).unwrap();
+        assert_eq!(Example {
+            input: "3   4\n4   3\n".to_string(),
+            part1: Some("11".to_string()),
+            part2: Some("31".to_string()),
+        }, example);
+    }
+
+    #[test]
+    pub fn test_extract_example_part1_only() {
+        let page = r#"<article><pre><code>1 &lt; 2</code></pre><p>Result: <code><em>3</em></code></p></article>"#;
+
+        let example = extract_example(page).unwrap();
+        assert_eq!("1 < 2", example.input);
+        assert_eq!(Some("3".to_string()), example.part1);
+        assert_eq!(None, example.part2);
+    }
+}
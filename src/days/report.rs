@@ -0,0 +1,175 @@
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::io::BufRead;
+use std::time::Duration;
+
+use eyre::{eyre, WrapErr};
+use serde::{Deserialize, Serialize};
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Phase {
+    Parse,
+    Solve,
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct DayReport {
+    pub day: usize,
+    pub parse: Option<Duration>,
+    pub part1: Option<Duration>,
+    pub part2: Option<Duration>,
+    /// The approach the day was solved with, if it has more than one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub strategy: Option<String>,
+}
+
+impl DayReport {
+    pub fn total(&self) -> Duration {
+        [self.parse, self.part1, self.part2].into_iter()
+            .flatten()
+            .sum()
+    }
+}
+
+impl Display for DayReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let format = |duration: Option<Duration>| duration
+            .map(|duration| format!("{duration:?}"))
+            .unwrap_or("-".to_string());
+
+        write!(f, "Day {:>2}: parse {:>12} | part 1 {:>12} | part 2 {:>12} | total {:>12?}",
+            self.day, format(self.parse), format(self.part1), format(self.part2), self.total(),
+        )?;
+        if let Some(strategy) = &self.strategy {
+            write!(f, " | {strategy}")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct RunReport {
+    pub days: Vec<DayReport>,
+}
+
+#[derive(Deserialize)]
+struct LogLine {
+    fields: LogFields,
+    span: Option<LogSpan>,
+}
+
+#[derive(Deserialize)]
+struct LogFields {
+    message: Option<String>,
+    #[serde(rename = "time.busy")]
+    busy: Option<String>,
+    #[serde(rename = "time.idle")]
+    idle: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct LogSpan {
+    day: Option<usize>,
+    part: Option<u8>,
+    phase: Option<Phase>,
+    strategy: Option<String>,
+}
+
+/// Parses the timings of span close events, e.g. `1.50ms` or `372µs`.
+fn parse_timing(timing: &str) -> eyre::Result<Duration> {
+    let unit_start = timing.find(|char: char| char.is_alphabetic())
+        .ok_or(eyre!("Timing {timing} has no unit"))?;
+    let (value, unit) = timing.split_at(unit_start);
+    let value: f64 = value.parse()
+        .wrap_err(format!("Invalid timing {timing}"))?;
+
+    let seconds = match unit {
+        "ns" => value / 1e9,
+        "µs" => value / 1e6,
+        "ms" => value / 1e3,
+        "s" => value,
+        _ => return Err(eyre!("Timing {timing} has an unknown unit")),
+    };
+    Ok(Duration::from_secs_f64(seconds))
+}
+
+impl RunReport {
+    /// Aggregates the phase spans of a log captured with `AOC_LOG_FORMAT=json`.
+    pub fn from_log(log: impl BufRead) -> eyre::Result<Self> {
+        let mut days = BTreeMap::<usize, DayReport>::new();
+
+        for (number, line) in log.lines().enumerate() {
+            let line = line?;
+            // stderr may contain more than the log, e.g. cargo's build output or a panic message
+            if !line.starts_with('{') {
+                continue;
+            }
+            let line: LogLine = serde_json::from_str(&line)
+                .wrap_err(format!("Failed to parse log line {}", number + 1))?;
+
+            let (Some("close"), Some(busy), Some(idle), Some(span)) = (line.fields.message.as_deref(), line.fields.busy, line.fields.idle, line.span) else {
+                continue;
+            };
+            let (Some(day), Some(phase)) = (span.day, span.phase) else {
+                continue;
+            };
+            let elapsed = parse_timing(&busy)? + parse_timing(&idle)?;
+
+            let report = days.entry(day)
+                .or_insert_with(|| DayReport { day, ..Default::default() });
+            let timing = match (phase, span.part) {
+                (Phase::Parse, _) => &mut report.parse,
+                (Phase::Solve, Some(1)) => &mut report.part1,
+                (Phase::Solve, Some(2)) => &mut report.part2,
+                (Phase::Solve, part) => return Err(eyre!("Invalid part {part:?} on line {}", number + 1)),
+            };
+            *timing = Some(timing.unwrap_or_default() + elapsed);
+            if span.strategy.is_some() {
+                report.strategy = span.strategy;
+            }
+        }
+
+        Ok(Self {
+            days: days.into_values().collect(),
+        })
+    }
+
+    pub fn total(&self) -> Duration {
+        self.days.iter()
+            .map(DayReport::total)
+            .sum()
+    }
+}
+
+impl Display for RunReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for day in &self.days {
+            writeln!(f, "{day}")?;
+        }
+        write!(f, "Total: {:?}", self.total())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    pub fn test_from_log() {
+        let log = r#"{"timestamp":"2024-12-01T05:00:00.000000Z","level":"INFO","fields":{"message":"Running Day 1"},"target":"aoc_2024::days::day1","span":{"day":1,"name":"day"},"spans":[{"day":1,"name":"day"}]}
+{"timestamp":"2024-12-01T05:00:00.000001Z","level":"ERROR","fields":{"message":"close","time.busy":"1.50ms","time.idle":"500µs"},"target":"aoc_2024::days::day1","span":{"day":1,"phase":"parse","name":"parse"},"spans":[{"day":1,"name":"day"}]}
+{"timestamp":"2024-12-01T05:00:00.000002Z","level":"ERROR","fields":{"message":"close","time.busy":"17.8µs","time.idle":"200ns"},"target":"aoc_2024::days::day1","span":{"day":1,"part":1,"phase":"solve","name":"solve"},"spans":[{"day":1,"name":"day"}]}
+{"timestamp":"2024-12-01T05:00:00.000003Z","level":"ERROR","fields":{"message":"close","time.busy":"1.00s","time.idle":"0ns"},"target":"aoc_2024::days::day2","span":{"day":2,"part":2,"phase":"solve","strategy":"dp","name":"solve"},"spans":[{"day":2,"name":"day"}]}
+{"timestamp":"2024-12-01T05:00:00.000004Z","level":"ERROR","fields":{"message":"close","time.busy":"2.00s","time.idle":"1.00ms"},"target":"aoc_2024::days::day1","span":{"day":1,"name":"day"},"spans":[]}
+"#;
+
+        let report = RunReport::from_log(log.as_bytes()).unwrap();
+        assert_eq!(RunReport {
+            days: vec![
+                DayReport { day: 1, parse: Some(Duration::from_millis(2)), part1: Some(Duration::from_nanos(18_000)), part2: None, strategy: None },
+                DayReport { day: 2, parse: None, part1: None, part2: Some(Duration::from_secs(1)), strategy: Some("dp".to_string()) },
+            ],
+        }, report);
+    }
+}
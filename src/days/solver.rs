@@ -0,0 +1,238 @@
+use std::fmt::{Display, Formatter};
+use eyre::eyre;
+use itertools::Itertools;
+use serde::Serialize;
+
+use super::Day;
+use super::util::Coordinate;
+
+/// The answer to one part of a puzzle. It is submitted as it is displayed, e.g. `6,1` for a
+/// coordinate and `4,6,3` for bytes.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize)]
+#[serde(untagged)]
+pub enum Answer {
+    Unsigned(u64),
+    Text(String),
+    Coord(isize, isize),
+    Bytes(Vec<u8>),
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Unsigned(answer) => write!(f, "{answer}"),
+            Self::Text(answer) => write!(f, "{answer}"),
+            Self::Coord(x, y) => write!(f, "{x},{y}"),
+            Self::Bytes(answer) => write!(f, "{}", answer.iter().join(",")),
+        }
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(value: usize) -> Self {
+        Self::Unsigned(value as u64)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Self::Text(value)
+    }
+}
+
+impl From<Coordinate> for Answer {
+    fn from(Coordinate(x, y): Coordinate) -> Self {
+        Self::Coord(x, y)
+    }
+}
+
+impl From<Vec<u8>> for Answer {
+    fn from(value: Vec<u8>) -> Self {
+        Self::Bytes(value)
+    }
+}
+
+/// One of the two parts of a puzzle.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::One => write!(f, "part 1"),
+            Self::Two => write!(f, "part 2"),
+        }
+    }
+}
+
+impl TryFrom<u8> for Part {
+    type Error = eyre::Error;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(Self::One),
+            2 => Ok(Self::Two),
+            _ => Err(eyre!("The part must be 1 or 2")),
+        }
+    }
+}
+
+/// The hash of an answer to `part` of `day`, salted with both so the hashes of the mostly small
+/// numeric answers can't simply be looked up or matched across days.
+pub fn answer_hash(day: Day, part: u8, answer: &str) -> String {
+    super::sha256(&format!("{}/day{}/part{part}:{answer}", super::YEAR, *day))
+}
+
+/// The accepted answers for the author's input as [`answer_hash`]es, which a day registers to be
+/// verified by `cargo test --features my-answers,real-inputs` without an `answers.toml`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct MyAnswers {
+    pub part1: Option<&'static str>,
+    pub part2: Option<&'static str>,
+}
+
+impl MyAnswers {
+    /// Whether `answer` is the accepted one for `part`, if that is known.
+    pub fn verify(&self, day: Day, part: u8, answer: &Answer) -> Option<bool> {
+        let expected = match part {
+            1 => self.part1,
+            2 => self.part2,
+            _ => None,
+        }?;
+        Some(expected == answer_hash(day, part, &answer.to_string()))
+    }
+}
+
+/// A day's solution, split into parsing the input once and solving both parts from it.
+pub trait Solver {
+    type Parsed;
+
+    fn parse(input: &str) -> eyre::Result<Self::Parsed>;
+    fn part1(input: &Self::Parsed) -> eyre::Result<Answer>;
+    fn part2(input: &Self::Parsed) -> eyre::Result<Answer>;
+}
+
+/// The parsed input of a day together with its [`Solver`], so the parts of every day can be solved
+/// (and timed) alike without knowing the type of its input.
+pub trait ParsedInput {
+    fn part1(&self) -> eyre::Result<Answer>;
+    fn part2(&self) -> eyre::Result<Answer>;
+}
+
+struct Parsed<S: Solver>(S::Parsed);
+
+impl<S: Solver> ParsedInput for Parsed<S> {
+    fn part1(&self) -> eyre::Result<Answer> {
+        S::part1(&self.0)
+    }
+
+    fn part2(&self) -> eyre::Result<Answer> {
+        S::part2(&self.0)
+    }
+}
+
+/// Parses `input` with `S` into a [`ParsedInput`].
+pub fn parse<S: Solver + 'static>(input: &str) -> eyre::Result<Box<dyn ParsedInput>> {
+    Ok(Box::new(Parsed::<S>(S::parse(input)?)))
+}
+
+/// Parses `input` and solves both parts of it with `S`.
+pub fn solve<S: Solver>(input: &str) -> eyre::Result<(Answer, Answer)> {
+    let input = S::parse(input)?;
+    Ok((S::part1(&input)?, S::part2(&input)?))
+}
+
+/// Declares the `DAY` and `STATUS` of a day module, its [`run_day`](super::harness::run_day)
+/// harness solving with `solver` and its [`Registration`](super::Registration):
+///
+/// ```ignore
+/// register_day! {
+///     day: 8,
+///     status: Implemented,
+///     solver: Solution,
+/// }
+/// ```
+///
+/// Days solved in more than one way add `approach_history: APPROACH_HISTORY`, days with hashed
+/// answers `my_answers: MY_ANSWERS`. Days with a harness of their own add `run: custom` and define
+/// `run()` themselves. `example: r"..." => ("part1", "part2")` adds a test solving the example.
+macro_rules! register_day {
+    (
+        day: $day:literal,
+        status: $status:ident,
+        solver: $solver:ty
+        $(, approach_history: $history:expr)?
+        $(, my_answers: $answers:expr)?
+        $(, run: $run:ident)?
+        $(, example: $example:expr => ($part1:expr, $part2:expr))?
+        $(,)?
+    ) => {
+        pub const DAY: $crate::days::Day = $crate::days::Day($day);
+        pub const STATUS: $crate::days::Status = $crate::days::Status::$status;
+
+        $crate::days::solver::register_day!(@run $solver, $crate::days::solver::register_day!(@option $($history)?) $(, $run)?);
+
+        ::inventory::submit! {
+            $crate::days::Registration {
+                day: DAY,
+                status: STATUS,
+                approach_history: $crate::days::solver::register_day!(@option $($history)?),
+                run: || Box::pin(run()),
+                parse: $crate::days::solver::parse::<$solver>,
+                solve: $crate::days::solver::solve::<$solver>,
+                my_answers: $crate::days::solver::register_day!(@option $($answers)?),
+            }
+        }
+
+        $(
+            #[cfg(test)]
+            #[test]
+            fn test_solver_example() {
+                let (part1, part2) = $crate::days::solver::solve::<$solver>($example).unwrap();
+                assert_eq!($part1, part1.to_string(), "part 1");
+                assert_eq!($part2, part2.to_string(), "part 2");
+            }
+        )?
+    };
+    (@run $solver:ty, $history:expr) => {
+        pub async fn run() -> eyre::Result<$crate::days::harness::DayResult> {
+            $crate::days::harness::run_day::<$solver>(DAY, $history).await
+        }
+    };
+    (@run $solver:ty, $history:expr, custom) => {};
+    (@option) => { None };
+    (@option $value:expr) => { Some($value) };
+}
+
+pub(crate) use register_day;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    pub fn test_verify_my_answers() {
+        let day = Day::try_from(1).unwrap();
+        let hash = answer_hash(day, 1, "42");
+        let my_answers = MyAnswers {
+            part1: Some(Box::leak(hash.into_boxed_str())),
+            part2: None,
+        };
+
+        assert_eq!(Some(true), my_answers.verify(day, 1, &Answer::Unsigned(42)));
+        assert_eq!(Some(false), my_answers.verify(day, 1, &Answer::Unsigned(43)));
+        assert_eq!(None, my_answers.verify(day, 2, &Answer::Unsigned(42)));
+        assert_ne!(answer_hash(day, 1, "42"), answer_hash(day, 2, "42"));
+    }
+
+    #[test]
+    pub fn test_display_answer() {
+        assert_eq!("42", Answer::Unsigned(42).to_string());
+        assert_eq!("6,1", Answer::from(Coordinate(6, 1)).to_string());
+        assert_eq!("4,6,3,5", Answer::Bytes(vec![4, 6, 3, 5]).to_string());
+        assert_eq!("[6,1]", serde_json::to_string(&Answer::Coord(6, 1)).unwrap());
+    }
+}
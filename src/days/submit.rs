@@ -0,0 +1,277 @@
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use eyre::{eyre, WrapErr};
+use scraper::{Html, Selector};
+use serde::{Deserialize, Serialize};
+
+use super::{config, Day};
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Hint {
+    TooHigh,
+    TooLow,
+}
+
+/// What AoC replied to a submitted answer.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Verdict {
+    Correct,
+    Wrong(Option<Hint>),
+    /// An answer was submitted too recently, nothing was checked.
+    TooRecent,
+    /// The part was already solved or isn't unlocked yet.
+    WrongLevel,
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Correct => write!(f, "That's the right answer"),
+            Self::Wrong(None) => write!(f, "That's not the right answer"),
+            Self::Wrong(Some(Hint::TooHigh)) => write!(f, "That's not the right answer, it is too high"),
+            Self::Wrong(Some(Hint::TooLow)) => write!(f, "That's not the right answer, it is too low"),
+            Self::TooRecent => write!(f, "An answer was submitted too recently"),
+            Self::WrongLevel => write!(f, "The part is already solved or still locked"),
+        }
+    }
+}
+
+/// The verdict of an answer page together with how long to wait before submitting again.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Response {
+    pub verdict: Verdict,
+    pub wait: Option<Duration>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct Guess {
+    pub answer: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hint: Option<Hint>,
+}
+
+/// The answers submitted for one part of a day.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct GuessHistory {
+    #[serde(default)]
+    pub wrong: Vec<Guess>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub correct: Option<String>,
+    /// Seconds since the unix epoch until which AoC refuses answers.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locked_until: Option<u64>,
+}
+
+impl GuessHistory {
+    /// Checks whether submitting `answer` at `now` could be worth it, based on the previous guesses.
+    pub fn check(&self, answer: &str, now: SystemTime) -> eyre::Result<()> {
+        if let Some(correct) = &self.correct {
+            return Err(eyre!("The part was already solved with {correct}"));
+        }
+        if let Some(locked_until) = self.locked_until.map(|locked_until| UNIX_EPOCH + Duration::from_secs(locked_until)) {
+            if let Ok(wait) = locked_until.duration_since(now) {
+                return Err(eyre!("Answers are refused for another {}s", wait.as_secs()));
+            }
+        }
+
+        for guess in &self.wrong {
+            if guess.answer == answer {
+                return Err(eyre!("{answer} was already submitted and is wrong"));
+            }
+            let (Ok(answer), Ok(guess_answer)) = (answer.parse::<i128>(), guess.answer.parse::<i128>()) else {
+                continue;
+            };
+            match guess.hint {
+                Some(Hint::TooHigh) if answer >= guess_answer => return Err(eyre!("{answer} is too high, {guess_answer} already was")),
+                Some(Hint::TooLow) if answer <= guess_answer => return Err(eyre!("{answer} is too low, {guess_answer} already was")),
+                _ => (),
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn record(&mut self, answer: &str, response: Response, now: SystemTime) {
+        match response.verdict {
+            Verdict::Correct => self.correct = Some(answer.to_string()),
+            Verdict::Wrong(hint) => self.wrong.push(Guess { answer: answer.to_string(), hint }),
+            Verdict::TooRecent | Verdict::WrongLevel => (),
+        }
+        self.locked_until = response.wait
+            .map(|wait| (now + wait).duration_since(UNIX_EPOCH).unwrap_or_default().as_secs());
+    }
+}
+
+/// The guesses of every day and part, stored as `guesses.json` next to the inputs.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Guesses {
+    days: BTreeMap<usize, BTreeMap<u8, GuessHistory>>,
+}
+
+impl Guesses {
+    fn file() -> eyre::Result<PathBuf> {
+        Ok(config::input_dir()?.join("guesses.json"))
+    }
+
+    pub fn load() -> eyre::Result<Self> {
+        let file = Self::file()?;
+        match std::fs::read_to_string(&file) {
+            Ok(guesses) => serde_json::from_str(&guesses)
+                .wrap_err(format!("Failed to parse {}", file.display())),
+            Err(_) => Ok(Self::default()),
+        }
+    }
+
+    pub fn save(&self) -> eyre::Result<()> {
+        let file = Self::file()?;
+        if let Some(dir) = file.parent() {
+            super::cache_dir(dir)?;
+        }
+        std::fs::write(&file, serde_json::to_string_pretty(self)?)
+            .wrap_err(format!("Failed to write {}", file.display()))
+    }
+
+    pub fn get(&self, day: Day, part: u8) -> Option<&GuessHistory> {
+        self.days.get(&*day)?.get(&part)
+    }
+
+    pub fn get_mut(&mut self, day: Day, part: u8) -> &mut GuessHistory {
+        self.days.entry(*day).or_default()
+            .entry(part).or_default()
+    }
+}
+
+/// Parses `You have 1m 30s left to wait` and `Please wait one minute before trying again`.
+fn parse_wait(text: &str) -> Option<Duration> {
+    if let Some((before, _)) = text.split_once(" left to wait") {
+        let seconds = before.rsplit("have ").next()?
+            .split_whitespace()
+            .map(|part| {
+                let (value, unit) = part.split_at(part.find(|char: char| !char.is_ascii_digit())?);
+                let value: u64 = value.parse().ok()?;
+                match unit {
+                    "h" => Some(value * 3600),
+                    "m" => Some(value * 60),
+                    "s" => Some(value),
+                    _ => None,
+                }
+            })
+            .sum::<Option<u64>>()?;
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let (before, _) = text.split_once(" before trying again")?;
+    let mut words = before.rsplit(' ');
+    let unit = words.next()?;
+    let value = match words.next()? {
+        "one" | "a" => 1,
+        "two" => 2,
+        "five" => 5,
+        "ten" => 10,
+        value => value.parse().ok()?,
+    };
+    match unit.trim_end_matches('s') {
+        "minute" => Some(Duration::from_secs(value * 60)),
+        "second" => Some(Duration::from_secs(value)),
+        _ => None,
+    }
+}
+
+/// Parses the page AoC answers a submission with.
+pub fn parse_response(page: &str) -> eyre::Result<Response> {
+    let document = Html::parse_document(page);
+    let article = document.select(&Selector::parse("main article").unwrap())
+        .next()
+        .ok_or(eyre!("The answer page has no message"))?;
+    let text = article.text().collect::<String>();
+
+    let verdict = if text.contains("That's the right answer") {
+        Verdict::Correct
+    } else if text.contains("That's not the right answer") {
+        Verdict::Wrong(if text.contains("your answer is too high") {
+            Some(Hint::TooHigh)
+        } else if text.contains("your answer is too low") {
+            Some(Hint::TooLow)
+        } else {
+            None
+        })
+    } else if text.contains("You gave an answer too recently") {
+        Verdict::TooRecent
+    } else if text.contains("You don't seem to be solving the right level") {
+        Verdict::WrongLevel
+    } else {
+        return Err(eyre!("Unknown answer: {}", text.trim()));
+    };
+
+    Ok(Response {
+        verdict,
+        wait: parse_wait(&text),
+    })
+}
+
+/// Submits `answer` for a part of a day unless the previous guesses already rule it out.
+pub async fn submit(day: Day, part: u8, answer: &str) -> eyre::Result<Verdict> {
+    let mut guesses = Guesses::load()?;
+    if let Some(history) = guesses.get(day, part) {
+        history.check(answer, SystemTime::now())?;
+    }
+
+    let page = super::client()?
+        .post(format!("{}/answer", super::day_url(super::YEAR, day)))
+        .form(&[("level", part.to_string()), ("answer", answer.to_string())])
+        .send().await
+        .context(format!("Failed to submit {day} part {part}"))?
+        .error_for_status()
+        .context(format!("Failed to submit {day} part {part}"))?
+        .text().await
+        .context(format!("Failed to submit {day} part {part}"))?;
+    let response = parse_response(&page)?;
+
+    guesses.get_mut(day, part).record(answer, response, SystemTime::now());
+    guesses.save()?;
+
+    Ok(response.verdict)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn page(message: &str) -> String {
+        format!("<html><body><main><article><p>{message}</p></article></main></body></html>")
+    }
+
+    #[test]
+    pub fn test_parse_response() {
+        assert_eq!(Response { verdict: Verdict::Correct, wait: None },
+            parse_response(&page("That's the right answer!  You are <em>one gold star</em> closer to finding the Chief Historian.")).unwrap());
+        assert_eq!(Response { verdict: Verdict::Wrong(Some(Hint::TooLow)), wait: Some(Duration::from_secs(60)) },
+            parse_response(&page("That's not the right answer; your answer is too low.  If you're stuck, make sure you're using the full input data. Please wait one minute before trying again.")).unwrap());
+        assert_eq!(Response { verdict: Verdict::Wrong(None), wait: Some(Duration::from_secs(300)) },
+            parse_response(&page("That's not the right answer.  If you're stuck, make sure you're using the full input data. Please wait 5 minutes before trying again.")).unwrap());
+        assert_eq!(Response { verdict: Verdict::TooRecent, wait: Some(Duration::from_secs(92)) },
+            parse_response(&page("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 32s left to wait.")).unwrap());
+    }
+
+    #[test]
+    pub fn test_check_guesses() {
+        let now = UNIX_EPOCH + Duration::from_secs(1_000);
+        let mut history = GuessHistory::default();
+        history.record("100", Response { verdict: Verdict::Wrong(Some(Hint::TooHigh)), wait: Some(Duration::from_secs(60)) }, now);
+        history.record("10", Response { verdict: Verdict::Wrong(Some(Hint::TooLow)), wait: None }, now);
+        history.record("abc", Response { verdict: Verdict::Wrong(None), wait: Some(Duration::from_secs(60)) }, now);
+
+        assert!(history.check("50", now).is_err());
+        let later = now + Duration::from_secs(61);
+        assert!(history.check("50", later).is_ok());
+        assert!(history.check("100", later).is_err());
+        assert!(history.check("150", later).is_err());
+        assert!(history.check("10", later).is_err());
+        assert!(history.check("abc", later).is_err());
+    }
+}
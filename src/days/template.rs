@@ -1,9 +1,5 @@
 use std::str::FromStr;
-use std::time::SystemTime;
-use tracing::{debug, info, Instrument, Level, span, trace};
-use crate::days::Day;
-
-pub const DAY: Day = Day(todo!());
+use crate::days::solver::{register_day, Answer, Solver};
 
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub struct Input {
@@ -18,42 +14,41 @@ impl FromStr for Input {
     }
 }
 
-pub fn process_part1(input: &Input) -> eyre::Result<usize> {
+pub fn process_part1(input: &Input) -> eyre::Result<Answer> {
     let result: usize = todo!();
 
-    Ok(result)
+    Ok(result.into())
 }
 
-pub fn process_part2(input: &Input) -> eyre::Result<usize> {
+pub fn process_part2(input: &Input) -> eyre::Result<Answer> {
     let result: usize = todo!();
 
-    Ok(result)
+    Ok(result.into())
 }
 
-pub async fn run() -> eyre::Result<()> {
-    let day_span = span!(Level::ERROR, "", "{}", DAY);
-    async {
-        info!("Running {DAY}");
-
-        let raw_input = super::get_input(DAY).await?;
-        trace!(raw_input);
-
-        let input = raw_input.parse()?;
-        debug!(?input);
-
-        let start1 = SystemTime::now();
-        let result1 = process_part1(&input)?;
-        let end1 = SystemTime::now();
-        // let start2 = SystemTime::now();
-        // let result2 = process_part2(&input)?;
-        // let end2 = SystemTime::now();
-        println!("{DAY} result:");
-        println!("  part 1: {result1} in {:?}", end1.duration_since(start1).unwrap());
-        // println!("  part 2: {result2} in {:?}", end2.duration_since(start2).unwrap());
-        Ok(())
+pub struct Solution;
+
+impl Solver for Solution {
+    type Parsed = Input;
+
+    fn parse(input: &str) -> eyre::Result<Self::Parsed> {
+        input.parse()
+    }
+
+    fn part1(input: &Self::Parsed) -> eyre::Result<Answer> {
+        process_part1(input)
     }
-        .instrument(day_span.or_current())
-        .await
+
+    fn part2(input: &Self::Parsed) -> eyre::Result<Answer> {
+        process_part2(input)
+    }
+}
+
+// replace with the number of the day and add the module to `days.rs`
+register_day! {
+    day: 0,
+    status: Stub,
+    solver: Solution,
 }
 
 #[cfg(test)]
@@ -69,7 +64,7 @@ mod test {
         let input = example_input();
 
         let result = process_part1(&input).unwrap();
-        assert_eq!(todo!() as usize, result);
+        assert_eq!(Answer::Unsigned(todo!()), result);
     }
 
     #[ignore]
@@ -78,6 +73,6 @@ mod test {
         let input = example_input();
 
         let result = process_part2(&input).unwrap();
-        assert_eq!(todo!() as usize, result);
+        assert_eq!(Answer::Unsigned(todo!()), result);
     }
 }
@@ -0,0 +1,67 @@
+use eyre::{eyre, WrapErr};
+use reqwest::header::ACCEPT;
+use reqwest::StatusCode;
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize)]
+pub struct Release {
+    pub tag_name: String,
+    pub html_url: String,
+}
+
+/// Parses versions like `1.2.3` or tags like `v1.2.3`, ignoring pre-release suffixes.
+fn parse_version(version: &str) -> Option<(u64, u64, u64)> {
+    let version = version.trim_start_matches('v');
+    let version = version.split(['-', '+']).next()?;
+    let mut parts = version.split('.').map(str::parse::<u64>);
+    let major = parts.next()?.ok()?;
+    let minor = parts.next().unwrap_or(Ok(0)).ok()?;
+    let patch = parts.next().unwrap_or(Ok(0)).ok()?;
+    Some((major, minor, patch))
+}
+
+fn is_newer(tag: &str, current: &str) -> bool {
+    matches!((parse_version(tag), parse_version(current)), (Some(tag), Some(current)) if tag > current)
+}
+
+/// The latest release published in the repository, if there is any.
+pub async fn latest_release() -> eyre::Result<Option<Release>> {
+    let repository = env!("CARGO_PKG_REPOSITORY").strip_prefix("https://github.com/")
+        .ok_or(eyre!("The repository is not hosted on GitHub"))?;
+    let response = super::public_client()
+        .get(format!("https://api.github.com/repos/{repository}/releases/latest"))
+        .header(ACCEPT, "application/vnd.github+json")
+        .send().await
+        .context("Failed to request the latest release")?;
+    if response.status() == StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+
+    let release = response
+        .error_for_status()
+        .context("Failed to request the latest release")?
+        .json().await
+        .context("Failed to parse the latest release")?;
+    Ok(Some(release))
+}
+
+/// The latest release if it is newer than this build.
+pub async fn check_update() -> eyre::Result<Option<Release>> {
+    Ok(latest_release().await?
+        .filter(|release| is_newer(&release.tag_name, env!("CARGO_PKG_VERSION"))))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    pub fn test_is_newer() {
+        assert!(is_newer("v0.2.0", "0.1.0"));
+        assert!(is_newer("1.0", "0.9.9"));
+        assert!(is_newer("v0.1.1-beta", "0.1.0"));
+        assert!(!is_newer("v0.1.0", "0.1.0"));
+        assert!(!is_newer("v0.0.9", "0.1.0"));
+        assert!(!is_newer("nightly", "0.1.0"));
+    }
+}
@@ -1,25 +1,80 @@
-use std::collections::VecDeque;
+use std::borrow::Borrow;
+use std::collections::{HashMap, VecDeque};
+use std::error::Error;
 use std::fmt::{Debug, Display, Formatter};
 use std::hash::Hash;
-use std::ops::{Add, AddAssign, Deref, Mul, Sub, SubAssign};
+use std::num::NonZeroUsize;
+use std::ops::{Add, AddAssign, Deref, Div, Index, IndexMut, Mul, Neg, Sub, SubAssign};
 use std::str::FromStr;
+use std::sync::Arc;
 
 use eyre::anyhow;
 use itertools::Itertools;
 
+pub mod bit_grid;
+pub mod counter;
+pub mod cycle;
+pub mod display;
+pub mod disjoint_set;
+pub mod graph;
+pub mod hex;
+pub mod interval_set;
+pub mod math;
+pub mod memo;
+pub mod parse;
+pub mod render;
+pub mod search;
+pub mod walk;
+
+pub use search::{astar, dijkstra};
+
+use bit_grid::BitGrid;
+use display::{Highlight, Highlighter};
+
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub struct Lines<Line: FromStr + Sized + Clone + Debug + Eq + PartialEq + Hash> {
     lines: Vec<Line>,
 }
 
-impl<Line: FromStr<Err = eyre::Error> + Sized + Clone + Debug + Eq + PartialEq + Hash> FromStr for Lines<Line> {
+impl<Line: FromStr + Sized + Clone + Debug + Eq + PartialEq + Hash> FromStr for Lines<Line>
+where
+    Line::Err: Into<eyre::Report>,
+{
     type Err = eyre::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let lines = s.lines()
             .map(|line| line.trim())
             .filter(|line| !line.is_empty())
-            .map(Line::from_str)
+            .map(|line| Line::from_str(line).map_err(Into::into))
+            .collect::<Result<_, _>>()?;
+        Ok(Self { lines })
+    }
+}
+
+impl<Line: FromStr + Sized + Clone + Debug + Eq + PartialEq + Hash> Lines<Line> {
+    pub fn iter(&self) -> std::slice::Iter<'_, Line> {
+        self.lines.iter()
+    }
+
+    pub fn into_inner(self) -> Vec<Line> {
+        self.lines
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<Line: FromStr + Sized + Clone + Debug + Eq + PartialEq + Hash + Send> Lines<Line>
+where
+    Line::Err: Into<eyre::Report>,
+{
+    /// Like [`FromStr::from_str`], but parses the lines in parallel. The lines keep their order.
+    pub fn par_from_str(s: &str) -> eyre::Result<Self> {
+        use rayon::prelude::*;
+
+        let lines = s.par_lines()
+            .map(|line| line.trim())
+            .filter(|line| !line.is_empty())
+            .map(|line| Line::from_str(line).map_err(Into::into))
             .collect::<Result<_, _>>()?;
         Ok(Self { lines })
     }
@@ -33,7 +88,7 @@ impl<Line: FromStr + Sized + Clone + Debug + Eq + PartialEq + Hash> Deref for Li
     }
 }
 
-#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub enum Direction {
     North,
     East,
@@ -112,6 +167,15 @@ impl Direction {
         }
     }
 
+    pub const fn index(&self) -> usize {
+        match self {
+            Self::North => 0,
+            Self::East => 1,
+            Self::South => 2,
+            Self::West => 3,
+        }
+    }
+
     pub const fn mask(&self) -> u8 {
         match self {
             Direction::North => 1 << 0,
@@ -120,30 +184,185 @@ impl Direction {
             Direction::West => 1 << 3,
         }
     }
+}
 
-    pub fn from_mask(mask: u8) -> Vec<Self> {
-        (0..4).filter(|shift| (mask & (1 << *shift) != 0))
-            .map(|shift| {
-                match shift {
-                    0 => Self::North,
-                    1 => Self::East,
-                    2 => Self::South,
-                    3 => Self::West,
-                    _ => unreachable!(),
-                }
-            }).collect()
+impl Display for Direction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.symbol())
+    }
+}
+
+impl TryFrom<char> for Direction {
+    type Error = eyre::Error;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            '^' => Ok(Self::North),
+            '>' => Ok(Self::East),
+            'v' => Ok(Self::South),
+            '<' => Ok(Self::West),
+            _ => Err(anyhow!("Invalid direction '{value}'")),
+        }
+    }
+}
+
+impl From<Direction> for Coordinate {
+    fn from(direction: Direction) -> Self {
+        match direction {
+            Direction::North => Coordinate(0, -1),
+            Direction::East => Coordinate(1, 0),
+            Direction::South => Coordinate(0, 1),
+            Direction::West => Coordinate(-1, 0),
+        }
+    }
+}
+
+/// A set of [`Direction`]s, like the ones a walk passed a tile in. It shows as the box-drawing
+/// character of [`Direction::DISPLAY`] connecting them.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct DirectionSet(u8);
+
+impl DirectionSet {
+    pub const EMPTY: Self = Self(0);
+    pub const ALL: Self = Self(0b1111);
+
+    pub const fn new() -> Self {
+        Self::EMPTY
+    }
+
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    pub const fn len(self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub const fn contains(self, direction: Direction) -> bool {
+        self.0 & direction.mask() != 0
+    }
+
+    /// Adds `direction`. Returns `false` if it already was in the set.
+    pub fn insert(&mut self, direction: Direction) -> bool {
+        let inserted = !self.contains(direction);
+        self.0 |= direction.mask();
+        inserted
+    }
+
+    /// Removes `direction`. Returns `false` if it wasn't in the set.
+    pub fn remove(&mut self, direction: Direction) -> bool {
+        let removed = self.contains(direction);
+        self.0 &= !direction.mask();
+        removed
+    }
+
+    /// The directions in the set, clockwise from north.
+    pub fn iter(self) -> impl Iterator<Item = Direction> {
+        Direction::ALL.into_iter().filter(move |&direction| self.contains(direction))
     }
 }
 
-impl Into<Coordinate> for Direction {
-    fn into(self) -> Coordinate {
+impl Display for DirectionSet {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", Direction::DISPLAY[self.0 as usize])
+    }
+}
+
+impl From<Direction> for DirectionSet {
+    fn from(direction: Direction) -> Self {
+        Self(direction.mask())
+    }
+}
+
+impl FromIterator<Direction> for DirectionSet {
+    fn from_iter<I: IntoIterator<Item = Direction>>(iter: I) -> Self {
+        let mut set = Self::new();
+        for direction in iter {
+            set.insert(direction);
+        }
+        set
+    }
+}
+
+/// A [`Direction`] or one of the diagonals between them, for puzzles that also move diagonally.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Direction8 {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction8 {
+    /// Every direction, clockwise from north.
+    pub const ALL: [Self; 8] = [
+        Self::North,
+        Self::NorthEast,
+        Self::East,
+        Self::SouthEast,
+        Self::South,
+        Self::SouthWest,
+        Self::West,
+        Self::NorthWest,
+    ];
+
+    pub const fn index(&self) -> usize {
+        *self as usize
+    }
+
+    pub const fn symbol(&self) -> char {
         match self {
-            Self::North => Coordinate(0, -1),
-            Self::East => Coordinate(1, 0),
-            Self::South => Coordinate(0, 1),
-            Self::West => Coordinate(-1, 0),
+            Self::North => '↑',
+            Self::NorthEast => '↗',
+            Self::East => '→',
+            Self::SouthEast => '↘',
+            Self::South => '↓',
+            Self::SouthWest => '↙',
+            Self::West => '←',
+            Self::NorthWest => '↖',
         }
     }
+
+    pub const fn offset(&self) -> Coordinate {
+        Coordinate::EXTENDED_CARDINALITIES[self.index()]
+    }
+
+    /// Every direction together with its offset, clockwise from north.
+    pub fn offsets() -> impl Iterator<Item = (Self, Coordinate)> {
+        Self::ALL.into_iter().zip(Coordinate::EXTENDED_CARDINALITIES)
+    }
+
+    pub const fn rotate45(&self) -> Self {
+        Self::ALL[(self.index() + 1) % 8]
+    }
+
+    pub const fn rotate90(&self) -> Self {
+        Self::ALL[(self.index() + 2) % 8]
+    }
+
+    pub const fn rotate180(&self) -> Self {
+        Self::ALL[(self.index() + 4) % 8]
+    }
+
+    pub const fn diagonal(&self) -> bool {
+        self.index() % 2 == 1
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(direction: Direction) -> Self {
+        Self::ALL[direction.index() * 2]
+    }
+}
+
+impl From<Direction8> for Coordinate {
+    fn from(direction: Direction8) -> Self {
+        direction.offset()
+    }
 }
 
 // x, y
@@ -169,29 +388,68 @@ impl Coordinate {
         Self::WEST, // West
     ];
 
+    /// The cardinalities and the diagonals between them, clockwise from north like [`Direction8::ALL`].
+    ///
+    /// Like [`Self::CARDINALITIES`] and the rows of a grid, y grows towards the south, so north is
+    /// `(0, -1)`. These used to be listed with y growing towards the north, which went unnoticed
+    /// while only the set of offsets, not their names, was used.
     pub const EXTENDED_CARDINALITIES: [Self; 8] = [
-        Self(0, 1), // North
-        Self(1, 1), // Northeast
+        Self(0, -1), // North
+        Self(1, -1), // Northeast
         Self(1, 0), // East
-        Self(1, -1), // Southeast
-        Self(0, -1), // South
-        Self(-1, -1), // Southwest
+        Self(1, 1), // Southeast
+        Self(0, 1), // South
+        Self(-1, 1), // Southwest
         Self(-1, 0), // West
-        Self(-1, 1), // Northwest
+        Self(-1, -1), // Northwest
     ];
 
     pub const fn eigen_axis(self) -> Self {
-        let x_direction = if self.0 == 0 {
-            0
-        } else {
-            self.0/self.0.abs()
-        };
-        let y_direction = if self.1 == 0 {
-            0
-        } else {
-            self.1/self.1.abs()
+        self.signum()
+    }
+
+    /// The sign of both components, so every component is -1, 0 or 1.
+    pub const fn signum(self) -> Self {
+        Self(self.0.signum(), self.1.signum())
+    }
+
+    /// The number of steps along the cardinalities between both coordinates.
+    pub const fn manhattan(self, other: Self) -> usize {
+        self.0.abs_diff(other.0) + self.1.abs_diff(other.1)
+    }
+
+    /// The number of steps between both coordinates if diagonal steps are allowed as well.
+    pub const fn chebyshev(self, other: Self) -> usize {
+        let (x, y) = (self.0.abs_diff(other.0), self.1.abs_diff(other.1));
+        if x > y { x } else { y }
+    }
+
+    /// Turned a quarter clockwise about the origin, so north becomes east.
+    pub const fn rotate90_cw(self) -> Self {
+        Self(-self.1, self.0)
+    }
+
+    /// Turned a quarter counterclockwise about the origin, so east becomes north.
+    pub const fn rotate90_ccw(self) -> Self {
+        Self(self.1, -self.0)
+    }
+
+    /// Turned `quarter_turns` quarters clockwise about `center`, or counterclockwise if negative.
+    pub fn rotate_around(self, center: Self, quarter_turns: isize) -> Self {
+        let offset = self - center;
+        let rotated = match quarter_turns.rem_euclid(4) {
+            0 => offset,
+            1 => offset.rotate90_cw(),
+            2 => -offset,
+            _ => offset.rotate90_ccw(),
         };
-        Coordinate(x_direction, y_direction)
+        center + rotated
+    }
+
+    /// The coordinate moved into a grid of `width` by `height` tiles, with the grid repeating
+    /// beyond its edges.
+    pub const fn wrap(self, width: usize, height: usize) -> Self {
+        Self(self.0.rem_euclid(width as isize), self.1.rem_euclid(height as isize))
     }
 }
 
@@ -210,7 +468,6 @@ impl AddAssign for Coordinate {
     }
 }
 
-
 impl Sub for Coordinate {
     type Output = Coordinate;
 
@@ -234,237 +491,928 @@ impl Mul<isize> for Coordinate {
     }
 }
 
-#[derive(Clone, Debug, Eq, PartialEq, Hash)]
-pub struct Grid {
-    char_map: Vec<char>,
-    width: usize,
-}
+impl Mul<Coordinate> for isize {
+    type Output = Coordinate;
 
-struct Node {
-    position: usize,
-    distance: usize,
+    fn mul(self, rhs: Coordinate) -> Self::Output {
+        rhs * self
+    }
 }
 
-#[allow(dead_code)]
-impl Grid {
-    pub fn width(&self) -> usize {
-        self.width
-    }
+/// Divides both components, rounding towards zero.
+impl Div<isize> for Coordinate {
+    type Output = Coordinate;
 
-    pub fn index_to_coordinate(&self, index: usize) -> Coordinate {
-        Coordinate((index % self.width) as isize, (index / self.width) as isize)
+    fn div(self, rhs: isize) -> Self::Output {
+        Self(self.0 / rhs, self.1 / rhs)
     }
+}
 
-    pub fn coordinate_to_index(&self, Coordinate(x, y): Coordinate) -> Result<usize, ()> {
-        if x < 0 || y < 0 || x >= self.width as isize {
-            return Err(())
-        }
+impl Neg for Coordinate {
+    type Output = Coordinate;
 
-        let index = x as usize + y as usize * self.width;
-        if index >= self.char_map.len() {
-            Err(())
-        } else {
-            Ok(index)
-        }
+    fn neg(self) -> Self::Output {
+        Self(-self.0, -self.1)
     }
+}
 
-    pub fn offset_index(&self, index: usize, offset: Coordinate) -> Result<usize, ()> {
-        self.coordinate_to_index(self.index_to_coordinate(index) + offset)
+/// A coordinate on a grid of `WIDTH` by `HEIGHT` tiles that wraps around like a torus, so leaving
+/// it on one side enters it again on the opposite one.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct WrappingCoordinate<const WIDTH: usize, const HEIGHT: usize>(Coordinate);
+
+impl<const WIDTH: usize, const HEIGHT: usize> Display for WrappingCoordinate<WIDTH, HEIGHT> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
     }
+}
 
-    pub fn as_slice(&self) -> &[char] {
-        &self.char_map
+impl<const WIDTH: usize, const HEIGHT: usize> WrappingCoordinate<WIDTH, HEIGHT> {
+    pub const fn new(coordinate: Coordinate) -> Self {
+        Self(coordinate.wrap(WIDTH, HEIGHT))
     }
 
-    pub fn display<F: Fn(char, usize) -> String>(&self, postprocess: F) -> GridDisplay<F> {
-        GridDisplay {
-            grid: self,
-            postprocess,
-        }
+    pub const fn coordinate(self) -> Coordinate {
+        self.0
     }
 
-    pub fn row(&self, index: usize) -> impl Iterator<Item = &char> {
-        self.char_map[index * self.width..(index + 1)*self.width()].iter()
+    pub const fn x(self) -> usize {
+        self.0.0 as usize
     }
 
-    pub fn col(&self, index: usize) -> impl Iterator<Item = &char> {
-        if index > self.width {
-            panic!();
-        }
-        self.char_map.iter().skip(index).step_by(self.width)
+    pub const fn y(self) -> usize {
+        self.0.1 as usize
     }
 
-    pub fn flood(&self, start: usize, is_wall: impl Fn(char) -> bool) -> Vec<usize> {
-        let mut to_visit = VecDeque::from([Node { position: start, distance: 0 }]);
-        let mut distances = vec![usize::MAX; self.char_map.len()];
-        distances[start] = 0;
+    /// The index of the tile in a [`ParsedGrid`] of the same size.
+    pub const fn index(self) -> usize {
+        self.y() * WIDTH + self.x()
+    }
+}
 
-        while let Some(Node { position, distance }) = to_visit.pop_front() {
-            let distance = distance + 1;
-            for direction in Direction::ALL {
-                if let Ok(position) = self.offset_index(position, direction.into()) {
-                    if distance < distances[position] && !is_wall(self.char_map[position]) {
-                        distances[position] = distance;
-                        to_visit.push_back(Node { position, distance });
-                    }
-                }
-            }
-        }
+impl<const WIDTH: usize, const HEIGHT: usize> Add<Coordinate> for WrappingCoordinate<WIDTH, HEIGHT> {
+    type Output = Self;
 
-        distances
+    fn add(self, rhs: Coordinate) -> Self::Output {
+        Self::new(self.0 + rhs)
     }
 }
 
-pub struct GridDisplay<'grid, F: Fn(char, usize) -> String> {
-    grid: &'grid Grid,
-    postprocess: F,
+impl<const WIDTH: usize, const HEIGHT: usize> AddAssign<Coordinate> for WrappingCoordinate<WIDTH, HEIGHT> {
+    fn add_assign(&mut self, rhs: Coordinate) {
+        *self = *self + rhs;
+    }
 }
 
-impl<F: Fn(char, usize) -> String> Display for GridDisplay<'_, F> {
+// x, y, z
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct Coordinate3(pub isize, pub isize, pub isize);
+
+impl Display for Coordinate3 {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.grid.char_map.iter()
-            .enumerate()
-            .chunks(self.grid.width)
-            .into_iter()
-            .map(|line| line
-                .map(|(index, character)| (self.postprocess)(*character, index))
-                .collect::<String>()
-            )
-            .join("\n")
-        )
+        write!(f, "({}, {}, {})", self.0, self.1, self.2)
     }
 }
 
-impl FromStr for Grid {
-    type Err = eyre::Error;
+impl Coordinate3 {
+    pub const X: Self = Self(1, 0, 0);
+    pub const Y: Self = Self(0, 1, 0);
+    pub const Z: Self = Self(0, 0, 1);
+
+    /// The unit steps along every axis, in both directions.
+    pub const AXES: [Self; 6] = [
+        Self(1, 0, 0),
+        Self(-1, 0, 0),
+        Self(0, 1, 0),
+        Self(0, -1, 0),
+        Self(0, 0, 1),
+        Self(0, 0, -1),
+    ];
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let preprocessed = s.lines()
-            .map(|line| line.trim())
-            .filter(|line| !line.is_empty())
-            .map(|line| line.to_string())
-            .collect::<Vec<_>>();
+    /// The six coordinates sharing a face with this one.
+    pub fn neighbors6(self) -> impl Iterator<Item = Self> {
+        Self::AXES.into_iter().map(move |axis| self + axis)
+    }
 
-        let width = if let Some(line) = preprocessed.first() {
-            line.len()
-        } else {
-            return Err(anyhow!("Input is empty"));
-        };
+    /// The number of unit steps along the axes between both coordinates.
+    pub const fn manhattan(self, other: Self) -> usize {
+        self.0.abs_diff(other.0) + self.1.abs_diff(other.1) + self.2.abs_diff(other.2)
+    }
+}
 
-        let char_map = preprocessed.iter()
-            .map(|line| line.chars())
-            .flatten()
-            .collect();
+impl Add for Coordinate3 {
+    type Output = Coordinate3;
 
-        Ok(Self {
-            char_map,
-            width
-        })
+    fn add(self, rhs: Self) -> Self::Output {
+        Self(self.0 + rhs.0, self.1 + rhs.1, self.2 + rhs.2)
     }
 }
 
-#[derive(Clone, Debug, Eq, PartialEq, Hash)]
-pub struct ParsedGrid<T> {
-    map: Vec<T>,
-    width: usize,
+impl AddAssign for Coordinate3 {
+    fn add_assign(&mut self, rhs: Self) {
+        self.0 += rhs.0;
+        self.1 += rhs.1;
+        self.2 += rhs.2;
+    }
 }
 
-impl<T: Default> ParsedGrid<T> {
-    pub fn new(width: usize, height: usize) -> Self {
-        Self {
-            map: (0..width * height).map(|_| Default::default()).collect(),
-            width,
-        }
+impl Sub for Coordinate3 {
+    type Output = Coordinate3;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self(self.0 - rhs.0, self.1 - rhs.1, self.2 - rhs.2)
     }
 }
 
-impl<T> ParsedGrid<T> {
-    pub fn width(&self) -> usize {
-        self.width
+impl SubAssign for Coordinate3 {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.0 -= rhs.0;
+        self.1 -= rhs.1;
+        self.2 -= rhs.2;
     }
+}
 
-    pub fn index_to_coordinate(&self, index: usize) -> Coordinate {
-        Coordinate((index % self.width) as isize, (index / self.width) as isize)
+impl Mul<isize> for Coordinate3 {
+    type Output = Coordinate3;
+
+    fn mul(self, rhs: isize) -> Self::Output {
+        Self(self.0 * rhs, self.1 * rhs, self.2 * rhs)
     }
+}
 
-    pub fn coordinate_to_index(&self, Coordinate(x, y): Coordinate) -> Result<usize, ()> {
-        if x < 0 || y < 0 || x >= self.width as isize {
-            return Err(())
-        }
+impl Mul<Coordinate3> for isize {
+    type Output = Coordinate3;
 
-        let index = x as usize + y as usize * self.width;
-        if index >= self.map.len() {
-            Err(())
-        } else {
-            Ok(index)
+    fn mul(self, rhs: Coordinate3) -> Self::Output {
+        rhs * self
+    }
+}
+
+impl Neg for Coordinate3 {
+    type Output = Coordinate3;
+
+    fn neg(self) -> Self::Output {
+        Self(-self.0, -self.1, -self.2)
+    }
+}
+
+/// The coordinates from `min` to `max` on both axes, both included.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Bounds {
+    pub min: Coordinate,
+    pub max: Coordinate,
+}
+
+impl Bounds {
+    pub const fn new(min: Coordinate, max: Coordinate) -> Self {
+        Self {
+            min,
+            max,
         }
     }
 
-    pub fn offset_index(&self, index: usize, offset: Coordinate) -> Result<usize, ()> {
-        self.coordinate_to_index(self.index_to_coordinate(index) + offset)
+    /// The bounds of a grid of `width` by `height` with its top left corner at the origin.
+    pub const fn sized(width: usize, height: usize) -> Self {
+        Self::new(Coordinate(0, 0), Coordinate(width as isize - 1, height as isize - 1))
     }
 
-    pub fn as_slice(&self) -> &[T] {
-        &self.map
+    pub const fn is_empty(&self) -> bool {
+        self.min.0 > self.max.0 || self.min.1 > self.max.1
     }
 
-    pub fn as_mut_slice(&mut self) -> &mut [T] {
-        &mut self.map
+    pub const fn contains(&self, Coordinate(x, y): Coordinate) -> bool {
+        self.min.0 <= x && x <= self.max.0 && self.min.1 <= y && y <= self.max.1
     }
 
-    pub fn display<F: Fn(&T, usize) -> D, D: Display>(&self, postprocess: F) -> ParsedGridDisplay<T, F, D> {
-        ParsedGridDisplay {
-            grid: self,
-            postprocess,
-        }
+    /// The coordinate inside the bounds closest to `coordinate` on both axes.
+    pub fn clamp(&self, Coordinate(x, y): Coordinate) -> Coordinate {
+        Coordinate(x.clamp(self.min.0, self.max.0), y.clamp(self.min.1, self.max.1))
     }
-}
 
-impl<T: Copy> ParsedGrid<T> {
-    pub fn swap(&mut self, a: usize, b: usize) {
-        (self.map[a], self.map[b]) = (self.map[b], self.map[a])
+    /// The coordinates inside both bounds, or [`None`] if they don't overlap.
+    pub fn intersect(&self, other: &Self) -> Option<Self> {
+        let intersection = Self::new(
+            Coordinate(self.min.0.max(other.min.0), self.min.1.max(other.min.1)),
+            Coordinate(self.max.0.min(other.max.0), self.max.1.min(other.max.1)),
+        );
+        (!intersection.is_empty()).then_some(intersection)
+    }
+
+    /// Every coordinate inside the bounds, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = Coordinate> {
+        let (min, max) = (self.min, self.max);
+        (min.1..=max.1).flat_map(move |y| (min.0..=max.0).map(move |x| Coordinate(x, y)))
     }
 }
 
-pub struct ParsedGridDisplay<'grid, T, F: Fn(&T, usize) -> D, D: Display> {
-    grid: &'grid ParsedGrid<T>,
-    postprocess: F,
+/// A value expected to occur exactly once in a grid is missing or occurs several times.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub enum UniqueError<T> {
+    Missing(T),
+    /// The value and every index it occurs at
+    Multiple(T, Vec<usize>),
 }
 
-impl<T, F: Fn(&T, usize) -> D, D: Display> Display for ParsedGridDisplay<'_, T, F, D> {
+impl<T: Debug> Display for UniqueError<T> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.grid.map.iter()
-            .enumerate()
-            .chunks(self.grid.width)
-            .into_iter()
-            .map(|line| line
-                .map(|(index, character)| (self.postprocess)(character, index).to_string())
-                .collect::<String>()
-            )
-            .join("\n")
-        )
+        match self {
+            Self::Missing(value) => write!(f, "Failed to find {value:?}"),
+            Self::Multiple(value, positions) => write!(f, "Found {value:?} {} times instead of once", positions.len()),
+        }
     }
 }
 
-impl<T: TryFrom<char, Error = eyre::Error>> FromStr for ParsedGrid<T> {
-    type Err = eyre::Error;
+impl<T: Debug> Error for UniqueError<T> {}
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let preprocessed = s.lines()
-            .map(|line| line.trim())
-            .filter(|line| !line.is_empty())
-            .map(|line| line.to_string())
-            .collect::<Vec<_>>();
+/// A character expected exactly once in a grid, like the start of a maze, picked up while parsing
+/// it with [`ParsedGrid::from_str_with`].
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Marker {
+    symbol: char,
+    found: Vec<Coordinate>,
+}
 
-        let width = if let Some(line) = preprocessed.first() {
+impl Marker {
+    pub const fn new(symbol: char) -> Self {
+        Self {
+            symbol,
+            found: Vec::new(),
+        }
+    }
+
+    /// Remembers `coordinate` if `char` is the marker, returning whether it is.
+    pub fn check(&mut self, char: char, coordinate: Coordinate) -> bool {
+        let is_marker = char == self.symbol;
+        if is_marker {
+            self.found.push(coordinate);
+        }
+        is_marker
+    }
+
+    /// The index of the only tile of `grid` the marker was found on.
+    pub fn index<T>(&self, grid: &ParsedGrid<T>) -> Result<usize, UniqueError<char>> {
+        let mut positions = self.found.iter()
+            .filter_map(|&coordinate| grid.coordinate_to_index(coordinate));
+        match (positions.next(), positions.next()) {
+            (None, _) => Err(UniqueError::Missing(self.symbol)),
+            (Some(position), None) => Ok(position),
+            (Some(first), Some(second)) => Err(UniqueError::Multiple(self.symbol, [first, second].into_iter().chain(positions).collect())),
+        }
+    }
+}
+
+/// The nodes given to [`toposort`] can't be ordered, as some of them depend on each other in a
+/// cycle.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct CycleError<Node> {
+    /// The nodes that are part of, or wait on, a cycle
+    pub remaining: Vec<Node>,
+}
+
+impl<Node: Debug> Display for CycleError<Node> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Found a cycle between {:?}", self.remaining)
+    }
+}
+
+impl<Node: Debug> Error for CycleError<Node> {}
+
+/// `nodes` ordered so every `(from, to)` of `edges` points forward, with Kahn's algorithm. Edges
+/// from or to nodes that aren't in `nodes` are ignored and nodes without an order between them keep
+/// the order they were given in.
+pub fn toposort<Node: Copy + Eq + Hash>(
+    nodes: impl IntoIterator<Item = Node>,
+    edges: impl IntoIterator<Item = (Node, Node)>,
+) -> Result<Vec<Node>, CycleError<Node>> {
+    let mut indices = HashMap::new();
+    let nodes = nodes.into_iter()
+        .filter(|&node| {
+            let index = indices.len();
+            indices.insert(node, index).is_none()
+        })
+        .collect::<Vec<_>>();
+
+    let mut successors = vec![Vec::new(); nodes.len()];
+    let mut predecessor_counts = vec![0usize; nodes.len()];
+    for (from, to) in edges {
+        if let (Some(&from), Some(&to)) = (indices.get(&from), indices.get(&to)) {
+            successors[from].push(to);
+            predecessor_counts[to] += 1;
+        }
+    }
+
+    let mut to_visit = (0..nodes.len())
+        .filter(|&node| predecessor_counts[node] == 0)
+        .collect::<VecDeque<_>>();
+    let mut sorted = Vec::with_capacity(nodes.len());
+    while let Some(node) = to_visit.pop_front() {
+        sorted.push(nodes[node]);
+        for &successor in &successors[node] {
+            predecessor_counts[successor] -= 1;
+            if predecessor_counts[successor] == 0 {
+                to_visit.push_back(successor);
+            }
+        }
+    }
+
+    if sorted.len() < nodes.len() {
+        let remaining = (0..nodes.len())
+            .filter(|&node| predecessor_counts[node] > 0)
+            .map(|node| nodes[node])
+            .collect();
+        return Err(CycleError { remaining });
+    }
+    Ok(sorted)
+}
+
+fn histogram<T: Eq + Hash + Clone>(tiles: &[T]) -> HashMap<T, usize> {
+    let mut histogram = HashMap::new();
+    for tile in tiles {
+        *histogram.entry(tile.clone()).or_default() += 1;
+    }
+    histogram
+}
+
+/// A grid of characters, as most puzzle inputs are.
+pub type Grid = ParsedGrid<char>;
+
+struct Node {
+    position: usize,
+    distance: usize,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct ParsedGrid<T> {
+    map: Arc<[T]>,
+    width: usize,
+}
+
+impl<T: Default> ParsedGrid<T> {
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            map: (0..width * height).map(|_| Default::default()).collect(),
+            width,
+        }
+    }
+}
+
+impl<T> ParsedGrid<T> {
+    /// A grid of `width` by `height` with every tile computed from its coordinate, row by row.
+    pub fn from_fn(width: usize, height: usize, mut tile: impl FnMut(Coordinate) -> T) -> Self {
+        Self {
+            map: (0..width * height)
+                .map(|index| tile(Coordinate((index % width) as isize, (index / width) as isize)))
+                .collect(),
+            width,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn index_to_coordinate(&self, index: usize) -> Coordinate {
+        Coordinate((index % self.width) as isize, (index / self.width) as isize)
+    }
+
+    /// The index of `coordinate`, or [`None`] if it lies outside the grid.
+    pub fn coordinate_to_index(&self, coordinate: Coordinate) -> Option<usize> {
+        self.bounds().contains(coordinate)
+            .then(|| coordinate.0 as usize + coordinate.1 as usize * self.width)
+    }
+
+    pub fn bounds(&self) -> Bounds {
+        Bounds::sized(self.width, self.height())
+    }
+
+    /// The index in the grid of [`Self::with_border`] of the tile at `index` in this grid.
+    pub fn bordered_index(&self, index: usize) -> usize {
+        let Coordinate(x, y) = self.index_to_coordinate(index);
+        (y as usize + 1) * (self.width + 2) + x as usize + 1
+    }
+
+    /// The index in this grid of the tile at `index` in the grid of [`Self::with_border`], or
+    /// [`None`] if it is part of the border.
+    pub fn unbordered_index(&self, index: usize) -> Option<usize> {
+        let width = self.width + 2;
+        let coordinate = Coordinate((index % width) as isize - 1, (index / width) as isize - 1);
+        self.coordinate_to_index(coordinate)
+    }
+
+    /// The index `offset` away from `index`, or [`None`] if it lies outside the grid.
+    pub fn offset_index(&self, index: usize, offset: Coordinate) -> Option<usize> {
+        self.coordinate_to_index(self.index_to_coordinate(index) + offset)
+    }
+
+    /// The index `offset` away from `index`, wrapping around the edges of the grid.
+    pub fn offset_index_wrapping(&self, index: usize, offset: Coordinate) -> usize {
+        let Coordinate(x, y) = (self.index_to_coordinate(index) + offset).wrap(self.width, self.height());
+        x as usize + y as usize * self.width
+    }
+
+    /// The tile at `coordinate`, or [`None`] if it lies outside the grid.
+    pub fn get(&self, coordinate: Coordinate) -> Option<&T> {
+        self.coordinate_to_index(coordinate)
+            .map(|index| &self.map[index])
+    }
+
+    /// The tile `offset` away from `index`, or [`None`] if it lies outside the grid.
+    pub fn get_offset(&self, index: usize, offset: impl Into<Coordinate>) -> Option<&T> {
+        self.get(self.index_to_coordinate(index) + offset.into())
+    }
+
+    /// The indices of the tiles north, east, south and west of `index` inside the grid, with the
+    /// direction they lie in.
+    pub fn neighbors4(&self, index: usize) -> impl Iterator<Item = (usize, Direction)> + '_ {
+        Direction::ALL.into_iter()
+            .filter_map(move |direction| Some((self.offset_index(index, direction.into())?, direction)))
+    }
+
+    /// The indices of the tiles connected to `start` through neighbours that are the `same` as the
+    /// tile they are reached from, like the plots of a garden region. `start` comes first, the
+    /// others follow in the order they are reached.
+    pub fn region_of(&self, start: usize, same: impl Fn(&T, &T) -> bool) -> Vec<usize> {
+        let mut visited = BitGrid::new(self.width, self.height());
+        self.fill_region(start, &same, &mut visited)
+    }
+
+    fn fill_region(&self, start: usize, same: &impl Fn(&T, &T) -> bool, visited: &mut BitGrid) -> Vec<usize> {
+        visited.set(start, true);
+        let mut region = vec![start];
+        let mut next = 0;
+        while let Some(&position) = region.get(next) {
+            next += 1;
+            for (neighbour, _) in self.neighbors4(position) {
+                if !visited.get(neighbour) && same(&self.map[position], &self.map[neighbour]) {
+                    visited.set(neighbour, true);
+                    region.push(neighbour);
+                }
+            }
+        }
+        region
+    }
+
+    /// Like [`Self::neighbors4`], but including the diagonal neighbours.
+    pub fn neighbors8(&self, index: usize) -> impl Iterator<Item = (usize, Direction8)> + '_ {
+        Direction8::offsets()
+            .filter_map(move |(direction, offset)| Some((self.offset_index(index, offset)?, direction)))
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        &self.map
+    }
+
+    pub fn display<F: Fn(&T, usize) -> D, D: Display>(&self, postprocess: F) -> ParsedGridDisplay<T, F, D> {
+        ParsedGridDisplay {
+            grid: self,
+            postprocess,
+        }
+    }
+
+    /// A [`Highlighter`] with `base` rendering the grid's tiles as the base layer.
+    pub fn highlighter<'a>(&'a self, base: impl Fn(&T) -> Highlight + 'a) -> Highlighter<'a> {
+        Highlighter::new(self.width, self.map.len(), move |index| base(&self.map[index]))
+    }
+
+    pub fn row(&self, index: usize) -> impl Iterator<Item = &T> {
+        self.map[index * self.width..(index + 1)*self.width()].iter()
+    }
+
+    pub fn col(&self, index: usize) -> impl Iterator<Item = &T> {
+        if index > self.width {
+            panic!();
+        }
+        self.map.iter().skip(index).step_by(self.width)
+    }
+
+    pub fn height(&self) -> usize {
+        self.map.len() / self.width
+    }
+
+    /// Every tile with its coordinate, row by row.
+    pub fn iter_coords(&self) -> impl Iterator<Item = (Coordinate, &T)> + '_ {
+        self.map.iter()
+            .enumerate()
+            .map(|(index, tile)| (self.index_to_coordinate(index), tile))
+    }
+
+    /// The indices of every tile matching `predicate`.
+    pub fn positions<'a>(&'a self, mut predicate: impl FnMut(&T) -> bool + 'a) -> impl Iterator<Item = usize> + 'a {
+        self.map.iter()
+            .enumerate()
+            .filter(move |(_, tile)| predicate(tile))
+            .map(|(index, _)| index)
+    }
+
+    /// Like [`Self::positions`], but with the coordinates of the tiles.
+    pub fn position_coords<'a>(&'a self, predicate: impl FnMut(&T) -> bool + 'a) -> impl Iterator<Item = Coordinate> + 'a {
+        self.positions(predicate)
+            .map(|index| self.index_to_coordinate(index))
+    }
+
+    /// The index of the first tile matching `predicate`.
+    pub fn find(&self, predicate: impl FnMut(&T) -> bool) -> Option<usize> {
+        self.map.iter().position(predicate)
+    }
+
+    /// Like [`Self::find`], but with the coordinate of the tile.
+    pub fn find_coord(&self, predicate: impl FnMut(&T) -> bool) -> Option<Coordinate> {
+        self.find(predicate)
+            .map(|index| self.index_to_coordinate(index))
+    }
+
+    /// The rows from top to bottom, each with the coordinates of its tiles.
+    pub fn rows(&self) -> impl Iterator<Item = impl Iterator<Item = (Coordinate, &T)> + '_> + '_ {
+        (0..self.height()).map(move |y| self.row(y)
+            .enumerate()
+            .map(move |(x, tile)| (Coordinate(x as isize, y as isize), tile)))
+    }
+
+    /// The columns from left to right, each with the coordinates of its tiles.
+    pub fn cols(&self) -> impl Iterator<Item = impl Iterator<Item = (Coordinate, &T)> + '_> + '_ {
+        (0..self.width).map(move |x| self.col(x)
+            .enumerate()
+            .map(move |(y, tile)| (Coordinate(x as isize, y as isize), tile)))
+    }
+
+    /// A grid of the same size with every tile passed through `f`.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> ParsedGrid<U> {
+        ParsedGrid {
+            map: self.map.iter().map(f).collect(),
+            width: self.width,
+        }
+    }
+
+    /// The `width` by `height` rectangle of the grid with its top left corner at `offset`, or
+    /// [`None`] if it doesn't lie inside the grid.
+    pub fn view(&self, offset: Coordinate, width: usize, height: usize) -> Option<GridView<'_, T>> {
+        GridView::from(self).view(offset, width, height)
+    }
+
+    /// Every `width` by `height` rectangle of the grid, row by row.
+    pub fn windows(&self, width: usize, height: usize) -> impl Iterator<Item = GridView<'_, T>> + '_ {
+        GridView::from(self).windows(width, height)
+    }
+}
+
+// the tiles to look for are taken by reference or, like the characters of a `Grid`, by value
+
+impl<T: PartialEq> ParsedGrid<T> {
+    /// The indices of every tile that is `value`.
+    pub fn positions_of(&self, value: impl Borrow<T>) -> Vec<usize> {
+        let value = value.borrow();
+        self.positions(|tile| tile == value).collect()
+    }
+
+    pub fn count(&self, value: impl Borrow<T>) -> usize {
+        let value = value.borrow();
+        self.positions(|tile| tile == value).count()
+    }
+
+    /// Every group of touching equal tiles, each listed like by [`Self::region_of`] and ordered by
+    /// their first tile.
+    pub fn regions(&self) -> Vec<Vec<usize>> {
+        let mut visited = BitGrid::new(self.width, self.height());
+        (0..self.map.len())
+            .filter_map(|position| (!visited.get(position))
+                .then(|| self.fill_region(position, &T::eq, &mut visited)))
+            .collect()
+    }
+}
+
+impl<T: PartialEq + Clone> ParsedGrid<T> {
+    /// The index of the only tile that is `value`, like the start of a maze or a robot.
+    pub fn unique_position_of(&self, value: impl Borrow<T>) -> Result<usize, UniqueError<T>> {
+        let value = value.borrow();
+        match self.positions_of(value).as_slice() {
+            [] => Err(UniqueError::Missing(value.clone())),
+            [position] => Ok(*position),
+            positions => Err(UniqueError::Multiple(value.clone(), positions.to_vec())),
+        }
+    }
+}
+
+impl<T: Eq + Hash + Clone> ParsedGrid<T> {
+    /// How often every tile occurs.
+    pub fn histogram(&self) -> HashMap<T, usize> {
+        histogram(&self.map)
+    }
+}
+
+impl<T: Clone> ParsedGrid<T> {
+    /// The tiles for mutation. Clones of a grid share their tiles, so they are copied first unless
+    /// this grid is the only one holding them.
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        Arc::make_mut(&mut self.map)
+    }
+
+    /// A grid of `width` with the tile at every row and column taken from the index `source` maps
+    /// them to in this grid.
+    fn remap(&self, width: usize, source: impl Fn(usize, usize) -> usize) -> Self {
+        let map = (0..self.map.len())
+            .map(|index| self.map[source(index / width, index % width)].clone())
+            .collect();
+
+        Self {
+            map,
+            width,
+        }
+    }
+
+    /// The grid mirrored along its main diagonal, so its rows become columns.
+    pub fn transpose(&self) -> Self {
+        self.remap(self.height(), |row, col| col * self.width + row)
+    }
+
+    /// The grid rotated clockwise by 90 degrees.
+    pub fn rotate_cw(&self) -> Self {
+        let height = self.height();
+        self.remap(height, |row, col| (height - 1 - col) * self.width + row)
+    }
+
+    /// The grid rotated counterclockwise by 90 degrees.
+    pub fn rotate_ccw(&self) -> Self {
+        self.remap(self.height(), |row, col| col * self.width + self.width - 1 - row)
+    }
+
+    /// The grid with every row reversed.
+    pub fn flip_horizontal(&self) -> Self {
+        self.remap(self.width, |row, col| row * self.width + self.width - 1 - col)
+    }
+
+    /// The grid surrounded by a ring of `pad` tiles, like walls, so every tile of this grid has all
+    /// its neighbours. See [`Self::bordered_index`] to find the tiles in it.
+    pub fn with_border(&self, pad: T) -> Self {
+        let bounds = self.bounds();
+        let width = self.width + 2;
+        Self::from_fn(width, self.height() + 2, |coordinate| {
+            let inner = coordinate - Coordinate(1, 1);
+            if bounds.contains(inner) { self[inner].clone() } else { pad.clone() }
+        })
+    }
+
+    /// The diagonals running from the top left to the bottom right, starting with the one in the
+    /// bottom left corner.
+    pub fn diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T> + '_> + '_ {
+        let height = self.height();
+        (0..self.width + height - 1).map(move |diagonal| {
+            let (row, col) = if diagonal < height { (height - 1 - diagonal, 0) } else { (0, diagonal + 1 - height) };
+            self.map.iter()
+                .skip(row * self.width + col)
+                .step_by(self.width + 1)
+                .take((height - row).min(self.width - col))
+        })
+    }
+
+    /// The diagonals running from the top right to the bottom left, starting with the one in the
+    /// top left corner.
+    pub fn anti_diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T> + '_> + '_ {
+        let height = self.height();
+        (0..self.width + height - 1).map(move |diagonal| {
+            let (row, col) = if diagonal < self.width { (0, diagonal) } else { (diagonal + 1 - self.width, self.width - 1) };
+            self.map.iter()
+                .skip(row * self.width + col)
+                // the anti diagonals of a single column are single tiles, so the step doesn't matter
+                .step_by((self.width - 1).max(1))
+                .take((height - row).min(col + 1))
+        })
+    }
+}
+
+impl<T: Copy> ParsedGrid<T> {
+    pub fn swap(&mut self, a: usize, b: usize) {
+        self.as_mut_slice().swap(a, b)
+    }
+
+    pub fn flood(&self, start: usize, is_wall: impl Fn(T) -> bool) -> Vec<usize> {
+        self.flood_paths(start, is_wall).0
+    }
+
+    /// Like [`Self::flood`], but also returns the index every tile was first reached from, to
+    /// follow the shortest paths back with [`reconstruct_path`]. The start is its own predecessor.
+    pub fn flood_paths(&self, start: usize, is_wall: impl Fn(T) -> bool) -> (Vec<usize>, Vec<Option<usize>>) {
+        self.flood_sources(&[start], is_wall)
+    }
+
+    /// Like [`Self::flood`], but with the distance of every tile to the nearest of `starts`.
+    pub fn flood_multi(&self, starts: &[usize], is_wall: impl Fn(T) -> bool) -> Vec<usize> {
+        self.flood_sources(starts, is_wall).0
+    }
+
+    fn flood_sources(&self, starts: &[usize], is_wall: impl Fn(T) -> bool) -> (Vec<usize>, Vec<Option<usize>>) {
+        let mut to_visit = VecDeque::with_capacity(starts.len());
+        let mut distances = vec![usize::MAX; self.map.len()];
+        let mut predecessors = vec![None; self.map.len()];
+        for &start in starts {
+            if distances[start] != 0 {
+                distances[start] = 0;
+                predecessors[start] = Some(start);
+                to_visit.push_back(Node { position: start, distance: 0 });
+            }
+        }
+
+        while let Some(Node { position: from, distance }) = to_visit.pop_front() {
+            let distance = distance + 1;
+            for (position, _) in self.neighbors4(from) {
+                if distance < distances[position] && !is_wall(self.map[position]) {
+                    distances[position] = distance;
+                    predecessors[position] = Some(from);
+                    to_visit.push_back(Node { position, distance });
+                }
+            }
+        }
+
+        (distances, predecessors)
+    }
+}
+
+/// The path from the start of a [`ParsedGrid::flood_paths`] to `end`, both included, or [`None`] if
+/// `end` wasn't reached.
+pub fn reconstruct_path(predecessors: &[Option<usize>], end: usize) -> Option<Vec<usize>> {
+    let mut path = vec![end];
+    let mut current = end;
+    loop {
+        let previous = predecessors[current]?;
+        if previous == current {
+            break;
+        }
+        path.push(previous);
+        current = previous;
+    }
+    path.reverse();
+    Some(path)
+}
+
+impl<T> Index<Coordinate> for ParsedGrid<T> {
+    type Output = T;
+
+    fn index(&self, coordinate: Coordinate) -> &Self::Output {
+        self.get(coordinate)
+            .unwrap_or_else(|| panic!("{coordinate} lies outside the grid"))
+    }
+}
+
+impl<T: Clone> IndexMut<Coordinate> for ParsedGrid<T> {
+    fn index_mut(&mut self, coordinate: Coordinate) -> &mut Self::Output {
+        let index = self.coordinate_to_index(coordinate)
+            .unwrap_or_else(|| panic!("{coordinate} lies outside the grid"));
+        &mut self.as_mut_slice()[index]
+    }
+}
+
+/// A rectangle of a [`ParsedGrid`], read like a grid of its own. Coordinates are relative to its
+/// top left corner.
+#[derive(Debug)]
+pub struct GridView<'grid, T> {
+    grid: &'grid ParsedGrid<T>,
+    offset: Coordinate,
+    width: usize,
+    height: usize,
+}
+
+// derived, these would only be implemented for clonable tiles
+impl<T> Clone for GridView<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for GridView<'_, T> {}
+
+impl<'grid, T> From<&'grid ParsedGrid<T>> for GridView<'grid, T> {
+    fn from(grid: &'grid ParsedGrid<T>) -> Self {
+        Self {
+            grid,
+            offset: Coordinate(0, 0),
+            width: grid.width,
+            height: grid.height(),
+        }
+    }
+}
+
+impl<'grid, T> GridView<'grid, T> {
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The coordinate of the top left corner in the underlying grid.
+    pub fn offset(&self) -> Coordinate {
+        self.offset
+    }
+
+    /// The tile at `coordinate`, or [`None`] if it lies outside the view.
+    pub fn get(&self, coordinate: Coordinate) -> Option<&'grid T> {
+        if !Bounds::sized(self.width, self.height).contains(coordinate) {
+            return None;
+        }
+        self.grid.get(self.offset + coordinate)
+    }
+
+    pub fn row(&self, index: usize) -> impl Iterator<Item = &'grid T> {
+        let grid = self.grid;
+        let start = (self.offset.1 as usize + index) * grid.width + self.offset.0 as usize;
+        grid.map[start..start + self.width].iter()
+    }
+
+    pub fn col(&self, index: usize) -> impl Iterator<Item = &'grid T> {
+        let grid = self.grid;
+        grid.map.iter()
+            .skip(self.offset.1 as usize * grid.width + self.offset.0 as usize + index)
+            .step_by(grid.width)
+            .take(self.height)
+    }
+
+    /// Every tile with its coordinate, row by row.
+    pub fn iter_coords(&self) -> impl Iterator<Item = (Coordinate, &'grid T)> + 'grid {
+        let view = *self;
+        (0..self.height).flat_map(move |y| view.row(y)
+            .enumerate()
+            .map(move |(x, tile)| (Coordinate(x as isize, y as isize), tile)))
+    }
+
+    /// The `width` by `height` rectangle of the view with its top left corner at `offset`, or
+    /// [`None`] if it doesn't lie inside the view.
+    pub fn view(&self, offset: Coordinate, width: usize, height: usize) -> Option<Self> {
+        let Coordinate(x, y) = offset;
+        if x < 0 || y < 0 || x as usize + width > self.width || y as usize + height > self.height {
+            return None;
+        }
+        Some(Self {
+            grid: self.grid,
+            offset: self.offset + offset,
+            width,
+            height,
+        })
+    }
+
+    /// Every `width` by `height` rectangle of the view, row by row.
+    pub fn windows(&self, width: usize, height: usize) -> impl Iterator<Item = Self> + 'grid {
+        let view = *self;
+        let (cols, rows) = ((self.width + 1).saturating_sub(width), (self.height + 1).saturating_sub(height));
+        (0..rows)
+            .flat_map(move |y| (0..cols).map(move |x| Coordinate(x as isize, y as isize)))
+            .filter_map(move |offset| view.view(offset, width, height))
+    }
+}
+
+impl<T> Index<Coordinate> for GridView<'_, T> {
+    type Output = T;
+
+    fn index(&self, coordinate: Coordinate) -> &Self::Output {
+        self.get(coordinate)
+            .unwrap_or_else(|| panic!("{coordinate} lies outside the view"))
+    }
+}
+
+pub struct ParsedGridDisplay<'grid, T, F: Fn(&T, usize) -> D, D: Display> {
+    grid: &'grid ParsedGrid<T>,
+    postprocess: F,
+}
+
+impl<T, F: Fn(&T, usize) -> D, D: Display> Display for ParsedGridDisplay<'_, T, F, D> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.grid.map.iter()
+            .enumerate()
+            .chunks(self.grid.width)
+            .into_iter()
+            .map(|line| line
+                .map(|(index, character)| (self.postprocess)(character, index).to_string())
+                .collect::<String>()
+            )
+            .join("\n")
+        )
+    }
+}
+
+impl<T> ParsedGrid<T> {
+    /// Parses a grid like [`FromStr`], but lets `tile` turn every character into a tile knowing
+    /// where it is. That way markers like the start of a maze can be picked up while parsing and
+    /// replaced by the tile underneath them.
+    pub fn from_str_with<E>(s: &str, mut tile: impl FnMut(char, Coordinate) -> Result<T, E>) -> eyre::Result<Self>
+    where
+        eyre::Error: From<E>,
+    {
+        let preprocessed = s.lines()
+            .map(|line| line.trim())
+            .filter(|line| !line.is_empty())
+            .collect::<Vec<_>>();
+
+        let width = if let Some(line) = preprocessed.first() {
             line.len()
         } else {
             return Err(anyhow!("Input is empty"));
         };
 
         let map = preprocessed.iter()
-            .map(|line| line.chars().map(|char| char.try_into()))
-            .flatten()
+            .enumerate()
+            .flat_map(|(y, line)| line.chars()
+                .enumerate()
+                .map(move |(x, char)| (char, Coordinate(x as isize, y as isize))))
+            .map(|(char, coordinate)| tile(char, coordinate))
             .collect::<Result<_, _>>()?;
 
         Ok(Self {
@@ -473,3 +1421,395 @@ impl<T: TryFrom<char, Error = eyre::Error>> FromStr for ParsedGrid<T> {
         })
     }
 }
+
+impl<T: TryFrom<char>> FromStr for ParsedGrid<T>
+where
+    eyre::Error: From<T::Error>,
+{
+    type Err = eyre::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_str_with(s, |char, _| char.try_into())
+    }
+}
+
+/// Maps `items` on all available cores. Every thread gets its own state from `init`, e.g. a memo
+/// that doesn't need to be shared. The results are in the order of the items.
+pub fn par_map_with<'a, T: Sync, S, R: Send>(
+    items: &'a [T],
+    init: impl Fn() -> S + Sync,
+    map: impl Fn(&mut S, &'a T) -> R + Sync,
+) -> Vec<R> {
+    let threads = std::thread::available_parallelism()
+        .map(NonZeroUsize::get)
+        .unwrap_or(1);
+    let chunk_size = items.len().div_ceil(threads).max(1);
+    let (init, map) = (&init, &map);
+
+    std::thread::scope(|scope| {
+        items.chunks(chunk_size)
+            .map(|chunk| scope.spawn(move || {
+                let mut state = init();
+                chunk.iter()
+                    .map(|item| map(&mut state, item))
+                    .collect::<Vec<_>>()
+            }))
+            .collect::<Vec<_>>()
+            .into_iter()
+            .flat_map(|thread| thread.join().unwrap())
+            .collect()
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    pub fn test_lines() {
+        let lines = "3\n\n  1\n2".parse::<Lines<usize>>().unwrap();
+        assert_eq!(vec![&3, &1, &2], lines.iter().collect::<Vec<_>>());
+        assert_eq!(vec![3, 1, 2], lines.into_inner());
+        assert!("1\n-2".parse::<Lines<usize>>().is_err());
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    pub fn test_par_from_str() {
+        let raw_input = (0..1000).map(|number| format!("  {number}\n\n")).collect::<String>();
+
+        let lines = Lines::<usize>::par_from_str(&raw_input).unwrap();
+        assert_eq!(raw_input.parse::<Lines<usize>>().unwrap(), lines);
+        assert_eq!(999, lines[999]);
+        assert!(Lines::<usize>::par_from_str("1\nx\n").is_err());
+    }
+
+    #[test]
+    pub fn test_copy_on_write() {
+        let grid = ParsedGrid::<bool>::new(3, 3);
+        let mut copy = grid.clone();
+        assert!(std::ptr::eq(grid.as_slice(), copy.as_slice()));
+
+        copy.as_mut_slice()[4] = true;
+        assert!(!std::ptr::eq(grid.as_slice(), copy.as_slice()));
+        assert!(!grid.as_slice()[4]);
+        assert!(copy.as_slice()[4]);
+    }
+
+    #[test]
+    pub fn test_from_str_with() {
+        let (mut start, mut end, mut walls) = (Marker::new('S'), Marker::new('E'), Marker::new('#'));
+        let grid = Grid::from_str_with("S.#\n.#.\n#.E", |char, coordinate| {
+            walls.check(char, coordinate);
+            Ok::<_, eyre::Error>(if start.check(char, coordinate) || end.check(char, coordinate) { '.' } else { char })
+        }).unwrap();
+
+        assert_eq!(Grid::from_str("..#\n.#.\n#..").unwrap(), grid);
+        assert_eq!(Ok(0), start.index(&grid));
+        assert_eq!(Ok(8), end.index(&grid));
+        assert_eq!(Err(UniqueError::Missing('x')), Marker::new('x').index(&grid));
+        assert_eq!(Err(UniqueError::Multiple('#', vec![2, 4, 6])), walls.index(&grid));
+        assert!(Grid::from_str_with("ab", |char, _| if char == 'a' { Ok(char) } else { Err(anyhow!("Invalid tile {char}")) }).is_err());
+    }
+
+    #[test]
+    pub fn test_par_map_with() {
+        let items = (0..1000).collect::<Vec<usize>>();
+
+        let result = par_map_with(&items, Vec::new, |seen, item| {
+            seen.push(*item);
+            item * 2
+        });
+        assert_eq!(items.iter().map(|item| item * 2).collect::<Vec<_>>(), result);
+        assert!(par_map_with(&[] as &[usize], || (), |_, item| *item).is_empty());
+    }
+
+    #[test]
+    pub fn test_find_tiles() {
+        let grid: Grid = "S.#\n.#.\n#.E".parse().unwrap();
+
+        assert_eq!(vec![2, 4, 6], grid.positions_of('#'));
+        assert_eq!(3, grid.count('#'));
+        assert_eq!(0, grid.count('x'));
+        assert_eq!(Ok(0), grid.unique_position_of('S'));
+        assert_eq!(Err(UniqueError::Missing('x')), grid.unique_position_of('x'));
+        assert_eq!(Err(UniqueError::Multiple('#', vec![2, 4, 6])), grid.unique_position_of('#'));
+        assert_eq!(HashMap::from([('S', 1), ('.', 3), ('#', 3), ('E', 1)]), grid.histogram());
+        assert_eq!(vec![0, 8], grid.positions(|&tile| tile == 'S' || tile == 'E').collect::<Vec<_>>());
+        assert_eq!(vec![Coordinate(1, 1), Coordinate(0, 2)], grid.position_coords(|&tile| tile == '#').skip(1).collect::<Vec<_>>());
+        assert_eq!(Some(1), grid.find(|&tile| tile == '.'));
+        assert_eq!(Some(Coordinate(2, 2)), grid.find_coord(|&tile| tile == 'E'));
+        assert_eq!(None, grid.find(|&tile| tile == 'x'));
+    }
+
+    #[test]
+    pub fn test_coordinate_arithmetic() {
+        let (a, b) = (Coordinate(1, -2), Coordinate(-3, 4));
+
+        assert_eq!(10, a.manhattan(b));
+        assert_eq!(6, a.chebyshev(b));
+        assert_eq!(0, a.chebyshev(a));
+        assert_eq!(Coordinate(-1, 1), b.signum());
+        assert_eq!(Coordinate(-1, 2), -a);
+        assert_eq!(a * 3, 3 * a);
+        assert_eq!(Coordinate(-1, 2), b / 2);
+    }
+
+    #[test]
+    pub fn test_coordinate_rotation() {
+        assert_eq!(
+            Coordinate::CARDINALITIES.to_vec(),
+            Coordinate::CARDINALITIES.map(|direction| direction.rotate90_ccw().rotate90_cw()).to_vec(),
+        );
+        assert_eq!(Coordinate::EAST, Coordinate::NORTH.rotate90_cw());
+        assert_eq!(Coordinate::NORTH, Coordinate::EAST.rotate90_ccw());
+
+        let center = Coordinate(2, 1);
+        assert_eq!(Coordinate(1, 3), Coordinate(4, 2).rotate_around(center, 1));
+        assert_eq!(Coordinate(0, 0), Coordinate(4, 2).rotate_around(center, 2));
+        assert_eq!(Coordinate(3, -1), Coordinate(4, 2).rotate_around(center, -1));
+        assert_eq!(Coordinate(4, 2), Coordinate(4, 2).rotate_around(center, 8));
+    }
+
+    #[test]
+    pub fn test_direction_symbols() {
+        for direction in Direction::ALL {
+            assert_eq!(direction, Direction::try_from(direction.symbol()).unwrap());
+            assert_eq!(direction.symbol().to_string(), direction.to_string());
+        }
+        assert!(Direction::try_from('x').is_err());
+    }
+
+    #[test]
+    pub fn test_direction_set() {
+        let mut set = DirectionSet::from(Direction::North);
+        assert!(set.insert(Direction::South));
+        assert!(!set.insert(Direction::North));
+        assert!(set.contains(Direction::South) && !set.contains(Direction::East));
+        assert_eq!(vec![Direction::North, Direction::South], set.iter().collect::<Vec<_>>());
+        assert_eq!("│", set.to_string());
+
+        assert!(set.remove(Direction::North));
+        assert!(!set.remove(Direction::North));
+        assert_eq!(1, set.len());
+        assert_eq!("┼", Direction::ALL.into_iter().collect::<DirectionSet>().to_string());
+        assert_eq!(".", DirectionSet::EMPTY.to_string());
+    }
+
+    #[test]
+    pub fn test_direction8() {
+        for direction in Direction::ALL {
+            let coordinate: Coordinate = direction.into();
+            assert_eq!(coordinate, Direction8::from(direction).offset(), "{direction:?}");
+            assert_eq!(Direction8::from(direction.rotate90()), Direction8::from(direction).rotate90(), "{direction:?}");
+        }
+        for direction in Direction8::ALL {
+            assert_eq!(direction.rotate90(), direction.rotate45().rotate45(), "{direction:?}");
+            assert_eq!(-direction.offset(), direction.rotate180().offset(), "{direction:?}");
+        }
+        assert_eq!(Coordinate(1, -1), Direction8::NorthEast.offset());
+        let north: Coordinate = Direction8::North.into();
+        assert_eq!(Coordinate(0, -1), north);
+        assert_eq!(Coordinate::SOUTH, Coordinate::from(Direction8::South));
+        assert!(Direction8::SouthWest.diagonal());
+
+        let grid = ParsedGrid::<bool>::new(3, 3);
+        assert_eq!(vec![(1, Direction8::East), (4, Direction8::SouthEast), (3, Direction8::South)], grid.neighbors8(0).collect::<Vec<_>>());
+    }
+
+    #[test]
+    pub fn test_coordinate3() {
+        let a = Coordinate3(1, -2, 3);
+
+        assert_eq!(Coordinate3(2, -2, 3), a + Coordinate3::X);
+        assert_eq!(Coordinate3(1, -2, 2), a - Coordinate3::Z);
+        assert_eq!(Coordinate3(-2, 4, -6), -2 * a);
+        assert_eq!(a * -1, -a);
+        assert_eq!(8, a.manhattan(Coordinate3(0, 0, 0) + Coordinate3::Y * 2));
+        assert_eq!("(1, -2, 3)", a.to_string());
+
+        let neighbours = a.neighbors6().collect::<Vec<_>>();
+        assert_eq!(6, neighbours.len());
+        assert!(neighbours.iter().all(|&neighbour| neighbour.manhattan(a) == 1));
+        assert!(neighbours.contains(&Coordinate3(1, -3, 3)));
+    }
+
+    #[test]
+    pub fn test_index_by_coordinate() {
+        let mut grid: Grid = "ab\ncd".parse().unwrap();
+
+        assert_eq!('c', grid[Coordinate(0, 1)]);
+        assert_eq!(Some(&'b'), grid.get(Coordinate(1, 0)));
+        assert_eq!(None, grid.get(Coordinate(2, 0)));
+        assert_eq!(None, grid.get(Coordinate(0, -1)));
+
+        grid[Coordinate(1, 1)] = 'x';
+        assert_eq!(&['a', 'b', 'c', 'x'], grid.as_slice());
+    }
+
+    #[test]
+    pub fn test_iter_coords() {
+        let grid: Grid = "ab\ncd".parse().unwrap();
+
+        assert_eq!(
+            vec![(Coordinate(0, 0), &'a'), (Coordinate(1, 0), &'b'), (Coordinate(0, 1), &'c'), (Coordinate(1, 1), &'d')],
+            grid.iter_coords().collect::<Vec<_>>(),
+        );
+        assert_eq!(
+            vec![vec![(Coordinate(1, 0), &'b'), (Coordinate(1, 1), &'d')]],
+            grid.cols().skip(1).map(|col| col.collect::<Vec<_>>()).collect::<Vec<_>>(),
+        );
+        assert_eq!(
+            grid.iter_coords().collect::<Vec<_>>(),
+            grid.rows().flatten().collect::<Vec<_>>(),
+        );
+    }
+
+    #[test]
+    pub fn test_flood_paths() {
+        let grid: Grid = "S.#\n#..\n..E".parse().unwrap();
+        let (distances, predecessors) = grid.flood_paths(0, |tile| tile == '#');
+
+        assert_eq!(grid.flood(0, |tile| tile == '#'), distances);
+        assert_eq!(4, distances[8]);
+        // ties are broken by the order of the neighbours, east before south
+        assert_eq!(Some(vec![0, 1, 4, 5, 8]), reconstruct_path(&predecessors, 8));
+        assert_eq!(Some(vec![0]), reconstruct_path(&predecessors, 0));
+        assert_eq!(None, reconstruct_path(&predecessors, 2));
+    }
+
+    #[test]
+    pub fn test_flood_multi() {
+        let grid: Grid = "S...\n.##.\n...S".parse().unwrap();
+        let distances = grid.flood_multi(&[0, 11, 11], |tile| tile == '#');
+
+        assert_eq!(vec![0, 1, 2, 2, 1, usize::MAX, usize::MAX, 1, 2, 2, 1, 0], distances);
+        assert_eq!(grid.flood(0, |tile| tile == '#'), grid.flood_multi(&[0], |tile| tile == '#'));
+        assert!(grid.flood_multi(&[], |tile| tile == '#').iter().all(|&distance| distance == usize::MAX));
+    }
+
+    #[test]
+    pub fn test_toposort() {
+        let edges = [(97, 75), (75, 47), (47, 61), (97, 61), (13, 29)];
+        assert_eq!(Ok(vec![97, 75, 47, 61]), toposort([75, 47, 61, 97, 75], edges));
+        assert_eq!(Ok(vec![29, 13]), toposort([29, 13], [(29, 13), (13, 30)]));
+        assert_eq!(Ok(vec![13, 29]), toposort([29, 13], edges));
+
+        let error = toposort([1, 2, 3, 4], [(1, 2), (2, 3), (3, 2), (3, 4)]).unwrap_err();
+        assert_eq!(vec![2, 3, 4], error.remaining);
+        assert_eq!("Found a cycle between [2, 3, 4]", error.to_string());
+    }
+
+    #[test]
+    pub fn test_regions() {
+        let grid: Grid = "AAB\nBAB\nBBA".parse().unwrap();
+
+        assert_eq!(vec![vec![0, 1, 4], vec![2, 5], vec![3, 6, 7], vec![8]], grid.regions());
+        assert_eq!(vec![4, 1, 0], grid.region_of(4, |a, b| a == b));
+        assert_eq!(9, grid.region_of(0, |_, _| true).len());
+    }
+
+    #[test]
+    pub fn test_wrapping() {
+        let grid = ParsedGrid::<bool>::new(4, 3);
+        assert_eq!(3, grid.offset_index_wrapping(0, Coordinate(-1, 0)));
+        assert_eq!(1, grid.offset_index_wrapping(9, Coordinate(0, 1)));
+        assert_eq!(6, grid.offset_index_wrapping(6, Coordinate(-8, 9)));
+
+        let mut position = WrappingCoordinate::<4, 3>::new(Coordinate(5, -1));
+        assert_eq!(Coordinate(1, 2), position.coordinate());
+        position += Coordinate(3, 1) * 3;
+        assert_eq!((2, 2), (position.x(), position.y()));
+        assert_eq!(10, position.index());
+    }
+
+    #[test]
+    pub fn test_bounds() {
+        let bounds = Bounds::new(Coordinate(-1, 0), Coordinate(2, 1));
+
+        assert!(bounds.contains(Coordinate(-1, 1)));
+        assert!(!bounds.contains(Coordinate(3, 0)));
+        assert_eq!(Coordinate(2, 0), bounds.clamp(Coordinate(5, -3)));
+        assert_eq!(Coordinate(0, 1), bounds.clamp(Coordinate(0, 1)));
+        assert_eq!(Some(Bounds::new(Coordinate(0, 0), Coordinate(2, 1))), bounds.intersect(&Bounds::sized(4, 4)));
+        assert_eq!(None, bounds.intersect(&Bounds::new(Coordinate(3, 0), Coordinate(4, 4))));
+        assert_eq!(8, bounds.iter().count());
+        assert_eq!(Some(Coordinate(0, 0)), bounds.iter().nth(1));
+        assert!(Bounds::sized(0, 3).is_empty());
+        assert_eq!(0, Bounds::sized(0, 3).iter().count());
+    }
+
+    #[test]
+    pub fn test_grid_view() {
+        let grid: Grid = "abc\ndef\nghi".parse().unwrap();
+        let view = grid.view(Coordinate(1, 1), 2, 2).unwrap();
+
+        assert_eq!((2, 2), (view.width(), view.height()));
+        assert_eq!(Some(&'e'), view.get(Coordinate(0, 0)));
+        assert_eq!(None, view.get(Coordinate(2, 0)));
+        assert_eq!(None, view.get(Coordinate(-1, 0)));
+        assert_eq!('i', view[Coordinate(1, 1)]);
+        assert_eq!("ef", view.row(0).collect::<String>());
+        assert_eq!("fi", view.col(1).collect::<String>());
+        assert_eq!(Some(Coordinate(2, 1)), view.view(Coordinate(1, 0), 1, 2).map(|view| view.offset()));
+        assert!(grid.view(Coordinate(2, 2), 2, 1).is_none());
+        assert!(view.view(Coordinate(1, 1), 1, 2).is_none());
+
+        let windows = grid.windows(2, 2)
+            .map(|window| window.iter_coords().map(|(_, tile)| tile).collect::<String>())
+            .collect::<Vec<_>>();
+        assert_eq!(vec!["abde", "bcef", "degh", "efhi"], windows);
+        assert_eq!(4, view.windows(1, 1).count());
+        assert_eq!(0, grid.windows(4, 1).count());
+    }
+
+    #[test]
+    pub fn test_with_border() {
+        let grid: Grid = "ab\ncd\nef".parse().unwrap();
+        let bordered = grid.with_border('#');
+
+        assert_eq!((4, 5), (bordered.width(), bordered.height()));
+        assert_eq!("#####ab##cd##ef#####", bordered.as_slice().iter().collect::<String>());
+        assert_eq!(5, grid.bordered_index(0));
+        assert_eq!('f', bordered.as_slice()[grid.bordered_index(5)]);
+        assert!((0..6).all(|index| grid.unbordered_index(grid.bordered_index(index)) == Some(index)));
+        assert_eq!(None, grid.unbordered_index(4));
+        assert_eq!(None, grid.unbordered_index(19));
+    }
+
+    #[test]
+    pub fn test_from_fn_and_map() {
+        let grid = ParsedGrid::from_fn(3, 2, |Coordinate(x, y)| x + 10 * y);
+        assert_eq!((3, 2), (grid.width(), grid.height()));
+        assert_eq!(&[0, 1, 2, 10, 11, 12], grid.as_slice());
+
+        let even = grid.map(|value| value % 2 == 0);
+        assert_eq!(&[true, false, true, true, false, true], even.as_slice());
+        assert_eq!(grid.width(), even.width());
+    }
+
+    #[test]
+    pub fn test_transformations() {
+        let grid: Grid = "abc\ndef".parse().unwrap();
+        let rows = |grid: Grid| (0..grid.height())
+            .map(|row| grid.row(row).collect::<String>())
+            .collect::<Vec<_>>();
+
+        assert_eq!(vec!["ad", "be", "cf"], rows(grid.transpose()));
+        assert_eq!(vec!["da", "eb", "fc"], rows(grid.rotate_cw()));
+        assert_eq!(vec!["cf", "be", "ad"], rows(grid.rotate_ccw()));
+        assert_eq!(vec!["cba", "fed"], rows(grid.flip_horizontal()));
+        assert_eq!(grid, grid.rotate_cw().rotate_ccw());
+        assert_eq!(grid, grid.transpose().transpose());
+        assert_eq!(grid.rotate_cw(), grid.transpose().flip_horizontal());
+
+        let diagonals = grid.diagonals()
+            .map(|diagonal| diagonal.collect::<String>())
+            .collect::<Vec<_>>();
+        assert_eq!(vec!["d", "ae", "bf", "c"], diagonals);
+        let anti_diagonals = grid.anti_diagonals()
+            .map(|diagonal| diagonal.collect::<String>())
+            .collect::<Vec<_>>();
+        assert_eq!(vec!["a", "bd", "ce", "f"], anti_diagonals);
+    }
+}
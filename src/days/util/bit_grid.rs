@@ -0,0 +1,122 @@
+use std::ops::Index;
+
+use super::{Bounds, Coordinate};
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// A grid of flags, like the tiles a walk visited, packed into words. It is indexed like a
+/// [`ParsedGrid`](super::ParsedGrid) of the same size.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct BitGrid {
+    words: Vec<u64>,
+    width: usize,
+    len: usize,
+}
+
+impl BitGrid {
+    pub fn new(width: usize, height: usize) -> Self {
+        let len = width * height;
+        Self {
+            words: vec![0; len.div_ceil(WORD_BITS)],
+            width,
+            len,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.len.checked_div(self.width).unwrap_or(0)
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The index of `coordinate`, or [`None`] if it lies outside the grid.
+    pub fn coordinate_to_index(&self, coordinate: Coordinate) -> Option<usize> {
+        Bounds::sized(self.width, self.height()).contains(coordinate)
+            .then(|| coordinate.0 as usize + coordinate.1 as usize * self.width)
+    }
+
+    pub fn get(&self, index: usize) -> bool {
+        assert!(index < self.len, "{index} lies outside the grid");
+        self.words[index / WORD_BITS] & (1 << (index % WORD_BITS)) != 0
+    }
+
+    pub fn set(&mut self, index: usize, value: bool) {
+        assert!(index < self.len, "{index} lies outside the grid");
+        let word = &mut self.words[index / WORD_BITS];
+        if value {
+            *word |= 1 << (index % WORD_BITS);
+        } else {
+            *word &= !(1 << (index % WORD_BITS));
+        }
+    }
+
+    /// Unsets every flag.
+    pub fn clear(&mut self) {
+        self.words.fill(0);
+    }
+
+    /// How many flags are set.
+    pub fn count_ones(&self) -> usize {
+        self.words.iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    /// Every flag, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = bool> + '_ {
+        (0..self.len).map(|index| self.get(index))
+    }
+}
+
+impl Index<usize> for BitGrid {
+    type Output = bool;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        if self.get(index) { &true } else { &false }
+    }
+}
+
+impl Index<Coordinate> for BitGrid {
+    type Output = bool;
+
+    fn index(&self, coordinate: Coordinate) -> &Self::Output {
+        let index = self.coordinate_to_index(coordinate)
+            .unwrap_or_else(|| panic!("{coordinate} lies outside the grid"));
+        &self[index]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    pub fn test_bit_grid() {
+        let mut grid = BitGrid::new(10, 10);
+        grid.set(0, true);
+        grid.set(63, true);
+        grid.set(64, true);
+        grid.set(99, true);
+        grid.set(63, false);
+
+        assert_eq!(3, grid.count_ones());
+        assert!(grid.get(64) && !grid.get(63));
+        assert!(grid[Coordinate(9, 9)]);
+        assert!(!grid[Coordinate(1, 0)]);
+        assert_eq!(None, grid.coordinate_to_index(Coordinate(10, 0)));
+        assert_eq!(vec![0, 64, 99], grid.iter().enumerate().filter(|&(_, set)| set).map(|(index, _)| index).collect::<Vec<_>>());
+
+        grid.clear();
+        assert_eq!(0, grid.count_ones());
+    }
+}
@@ -0,0 +1,121 @@
+use std::collections::HashMap;
+use std::collections::hash_map;
+use std::hash::Hash;
+
+/// How often each value occurs, like the stones with the same engraving or the robots in each
+/// quadrant.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Counter<T: Eq + Hash> {
+    counts: HashMap<T, usize>,
+}
+
+impl<T: Eq + Hash> Default for Counter<T> {
+    fn default() -> Self {
+        Self {
+            counts: HashMap::new(),
+        }
+    }
+}
+
+impl<T: Eq + Hash> Counter<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            counts: HashMap::with_capacity(capacity),
+        }
+    }
+
+    /// Counts `value` another `count` times.
+    pub fn add(&mut self, value: T, count: usize) {
+        *self.counts.entry(value).or_default() += count;
+    }
+
+    /// How often `value` was counted, 0 if never.
+    pub fn get(&self, value: &T) -> usize {
+        self.counts.get(value).copied().unwrap_or(0)
+    }
+
+    /// The number of distinct values.
+    pub fn len(&self) -> usize {
+        self.counts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    /// The number of values counted, including repeated ones.
+    pub fn total(&self) -> usize {
+        self.counts.values().sum()
+    }
+
+    /// Every distinct value with its count, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (&T, usize)> + '_ {
+        self.counts.iter().map(|(value, &count)| (value, count))
+    }
+
+    /// Every distinct value with its count, the most common first. Values counted equally often
+    /// are in no particular order.
+    pub fn most_common(&self) -> Vec<(&T, usize)> {
+        let mut counts = self.iter().collect::<Vec<_>>();
+        counts.sort_unstable_by(|(_, a), (_, b)| b.cmp(a));
+        counts
+    }
+
+    /// Adds the counts of `other` to these.
+    pub fn merge(&mut self, other: Self) {
+        for (value, count) in other {
+            self.add(value, count);
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.counts.clear();
+    }
+}
+
+impl<T: Eq + Hash> FromIterator<T> for Counter<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut counter = Self::new();
+        for value in iter {
+            counter.add(value, 1);
+        }
+        counter
+    }
+}
+
+impl<T: Eq + Hash> IntoIterator for Counter<T> {
+    type Item = (T, usize);
+    type IntoIter = hash_map::IntoIter<T, usize>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.counts.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    pub fn test_counter() {
+        let mut counter: Counter<char> = "abracadabra".chars().collect();
+        assert_eq!(5, counter.get(&'a'));
+        assert_eq!(0, counter.get(&'z'));
+        assert_eq!(5, counter.len());
+        assert_eq!(11, counter.total());
+        assert_eq!((&'a', 5), counter.most_common()[0]);
+
+        counter.merge("zzz".chars().collect());
+        counter.add('c', 2);
+        assert_eq!(3, counter.get(&'z'));
+        assert_eq!(3, counter.get(&'c'));
+        assert_eq!(16, counter.total());
+
+        counter.clear();
+        assert!(counter.is_empty());
+    }
+}
@@ -0,0 +1,73 @@
+/// The states a simulation repeats forever: the first repeated state is reached after `start` steps
+/// and comes around again every `length` steps.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// The number of steps after which the state is the same as after `steps` steps, but reached
+    /// before the cycle comes around the first time. Makes simulating a trillion steps unnecessary.
+    pub fn equivalent_steps(&self, steps: usize) -> usize {
+        if steps < self.start {
+            steps
+        } else {
+            self.start + (steps - self.start) % self.length
+        }
+    }
+}
+
+/// The cycle the states starting from `initial_state` run into with Brent's algorithm, or [`None`]
+/// if `step` ends the simulation before. `step` has to be deterministic, as it is called more than
+/// once for most states, but doesn't remember any of them.
+pub fn detect<State: Clone + Eq>(initial_state: State, mut step: impl FnMut(&State) -> Option<State>) -> Option<Cycle> {
+    // the tortoise waits at every power of two for the hare to come around
+    let (mut power, mut length) = (1, 1);
+    let mut tortoise = initial_state.clone();
+    let mut hare = step(&initial_state)?;
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = step(&hare)?;
+        length += 1;
+    }
+
+    // with the hare a cycle ahead, both meet at the start of the cycle
+    let mut tortoise = initial_state.clone();
+    let mut hare = initial_state;
+    for _ in 0..length {
+        hare = step(&hare)?;
+    }
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise)?;
+        hare = step(&hare)?;
+        start += 1;
+    }
+
+    Some(Cycle { start, length })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    pub fn test_detect() {
+        // 3, 10, 5, 16, 8, 4, 2, 1, 4, 2, 1, ...
+        let collatz = |&n: &u64| Some(if n % 2 == 0 { n / 2 } else { 3 * n + 1 });
+        let cycle = detect(3, collatz).unwrap();
+        assert_eq!(Cycle { start: 5, length: 3 }, cycle);
+        assert_eq!(5, cycle.equivalent_steps(1_000_000_000_001));
+        assert_eq!(4, cycle.equivalent_steps(4));
+
+        assert_eq!(Some(Cycle { start: 0, length: 1 }), detect(7, |&n| Some(n)));
+        assert_eq!(Some(Cycle { start: 0, length: 4 }), detect(0, |&n| Some((n + 1) % 4)));
+        assert_eq!(None, detect(0, |&n: &u32| n.checked_sub(1)));
+        assert_eq!(None, detect(10, |&n: &u32| (n < 1000).then_some(n * 2)));
+    }
+}
@@ -0,0 +1,77 @@
+/// Partitions the elements from 0 into sets that can be joined, like the connected regions of a
+/// grid.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct DisjointSet {
+    parents: Vec<usize>,
+    ranks: Vec<u8>,
+}
+
+impl DisjointSet {
+    /// `len` elements, each in a set of its own.
+    pub fn new(len: usize) -> Self {
+        Self {
+            parents: (0..len).collect(),
+            ranks: vec![0; len],
+        }
+    }
+
+    /// The element representing the set `element` is in.
+    pub fn find(&mut self, mut element: usize) -> usize {
+        while self.parents[element] != element {
+            self.parents[element] = self.parents[self.parents[element]];
+            element = self.parents[element];
+        }
+        element
+    }
+
+    /// Joins the sets of `a` and `b`. Returns `false` if they already were the same set.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (a, b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+        let (higher, lower) = if self.ranks[a] < self.ranks[b] { (b, a) } else { (a, b) };
+        self.parents[lower] = higher;
+        if self.ranks[higher] == self.ranks[lower] {
+            self.ranks[higher] += 1;
+        }
+        true
+    }
+
+    pub fn same(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    /// Every set with its elements in ascending order, ordered by their smallest element.
+    pub fn sets(&mut self) -> Vec<Vec<usize>> {
+        let mut set_indices = vec![None; self.parents.len()];
+        let mut sets: Vec<Vec<usize>> = Vec::new();
+        for element in 0..self.parents.len() {
+            let root = self.find(element);
+            let index = *set_indices[root].get_or_insert_with(|| {
+                sets.push(Vec::new());
+                sets.len() - 1
+            });
+            sets[index].push(element);
+        }
+        sets
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    pub fn test_disjoint_set() {
+        let mut set = DisjointSet::new(6);
+
+        assert!(set.union(0, 3));
+        assert!(set.union(4, 3));
+        assert!(set.union(1, 5));
+        assert!(!set.union(0, 4));
+        assert!(set.same(4, 0));
+        assert!(!set.same(1, 2));
+        assert_eq!(vec![vec![0, 3, 4], vec![1, 5], vec![2]], set.sets());
+    }
+}
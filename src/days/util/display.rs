@@ -0,0 +1,279 @@
+use std::fmt::{Display, Formatter};
+
+use owo_colors::{AnsiColors, Style};
+
+use super::ParsedGrid;
+
+/// The appearance of a single cell, where unset attributes are left to the layers below.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct Highlight {
+    pub symbol: Option<char>,
+    pub foreground: Option<AnsiColors>,
+    pub background: Option<AnsiColors>,
+    pub bold: bool,
+    pub dimmed: bool,
+}
+
+impl Highlight {
+    pub const fn new() -> Self {
+        Self {
+            symbol: None,
+            foreground: None,
+            background: None,
+            bold: false,
+            dimmed: false,
+        }
+    }
+
+    pub const fn symbol(mut self, symbol: char) -> Self {
+        self.symbol = Some(symbol);
+        self
+    }
+
+    pub const fn foreground(mut self, color: AnsiColors) -> Self {
+        self.foreground = Some(color);
+        self
+    }
+
+    pub const fn background(mut self, color: AnsiColors) -> Self {
+        self.background = Some(color);
+        self
+    }
+
+    pub const fn bold(mut self) -> Self {
+        self.bold = true;
+        self
+    }
+
+    pub const fn dimmed(mut self) -> Self {
+        self.dimmed = true;
+        self
+    }
+
+    /// Combines two highlights, with the attributes of `self` taking precedence.
+    pub fn over(self, below: Self) -> Self {
+        Self {
+            symbol: self.symbol.or(below.symbol),
+            foreground: self.foreground.or(below.foreground),
+            background: self.background.or(below.background),
+            bold: self.bold || below.bold,
+            dimmed: self.dimmed || below.dimmed,
+        }
+    }
+
+    pub fn style(&self) -> Style {
+        let mut style = Style::new();
+        if let Some(foreground) = self.foreground {
+            style = style.color(foreground);
+        }
+        if let Some(background) = self.background {
+            style = style.on_color(background);
+        }
+        if self.bold {
+            style = style.bold();
+        }
+        if self.dimmed {
+            style = style.dimmed();
+        }
+        style
+    }
+}
+
+impl Display for Highlight {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.style().style(self.symbol.unwrap_or(' ')))
+    }
+}
+
+/// How a layer is combined with the layers below it.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Blend {
+    /// Overrides the attributes the layer sets and keeps all others
+    Over,
+    /// Only fills in attributes no layer below has set
+    Under,
+    /// Discards everything below
+    Replace,
+}
+
+struct Layer<'a> {
+    z: i32,
+    blend: Blend,
+    highlight: Box<dyn Fn(usize) -> Option<Highlight> + 'a>,
+}
+
+/// A grid rendering composed of stacked highlight layers.
+///
+/// Layers are applied from the lowest to the highest `z`, layers with the same `z` in the order they
+/// were added. Each layer decides per cell index whether it contributes anything at all.
+pub struct Highlighter<'a> {
+    pub(super) width: usize,
+    pub(super) len: usize,
+    layers: Vec<Layer<'a>>,
+}
+
+impl<'a> Highlighter<'a> {
+    pub fn new(width: usize, len: usize, base: impl Fn(usize) -> Highlight + 'a) -> Self {
+        Self {
+            width,
+            len,
+            layers: vec![Layer {
+                z: i32::MIN,
+                blend: Blend::Replace,
+                highlight: Box::new(move |index| Some(base(index))),
+            }],
+        }
+    }
+
+    pub fn layer(mut self, z: i32, blend: Blend, highlight: impl Fn(usize) -> Option<Highlight> + 'a) -> Self {
+        let position = self.layers.partition_point(|layer| layer.z <= z);
+        self.layers.insert(position, Layer {
+            z,
+            blend,
+            highlight: Box::new(highlight),
+        });
+        self
+    }
+
+    /// A layer highlighting every cell `mask` is set for.
+    pub fn mask(self, z: i32, blend: Blend, mask: &'a [bool], highlight: Highlight) -> Self {
+        self.layer(z, blend, move |index| mask[index].then_some(highlight))
+    }
+
+    pub fn highlight(&self, index: usize) -> Highlight {
+        self.layers.iter()
+            .fold(Highlight::new(), |below, layer| match (layer.highlight)(index) {
+                Some(highlight) => match layer.blend {
+                    Blend::Over => highlight.over(below),
+                    Blend::Under => below.over(highlight),
+                    Blend::Replace => highlight,
+                },
+                None => below,
+            })
+    }
+}
+
+impl Display for Highlighter<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for row in 0..self.len.div_ceil(self.width) {
+            if row != 0 {
+                writeln!(f)?;
+            }
+            for index in row * self.width..((row + 1) * self.width).min(self.len) {
+                write!(f, "{}", self.highlight(index))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// `after` with the cells that differ from `before` highlighted, like the boxes a robot pushed or a
+/// newly placed obstacle. Both grids need the same size.
+pub fn diff<'a, T>(before: &'a ParsedGrid<T>, after: &'a ParsedGrid<T>) -> Highlighter<'a>
+where
+    T: Copy + PartialEq + Into<char>,
+{
+    assert_eq!((before.width(), before.height()), (after.width(), after.height()), "Only grids of the same size can be compared");
+    after.highlighter(|&tile| Highlight::new().symbol(tile.into()).dimmed())
+        .layer(0, Blend::Replace, move |index| (before.as_slice()[index] != after.as_slice()[index])
+            .then(|| Highlight::new().symbol(after.as_slice()[index].into()).foreground(AnsiColors::Black).background(AnsiColors::BrightYellow).bold()))
+}
+
+/// Numbers like the distances of a flood laid out as a grid of `width` columns, right aligned to
+/// the widest one. Cells that were never reached, i.e. are [`usize::MAX`], show as `#`. `highlight`
+/// can style every cell by its index.
+pub fn heatmap<'a>(values: &'a [usize], width: usize, highlight: impl Fn(usize) -> Option<Style> + 'a) -> Heatmap<'a> {
+    Heatmap {
+        values,
+        width,
+        highlight: Box::new(highlight),
+    }
+}
+
+/// See [`heatmap`].
+pub struct Heatmap<'a> {
+    values: &'a [usize],
+    width: usize,
+    highlight: Box<dyn Fn(usize) -> Option<Style> + 'a>,
+}
+
+impl Heatmap<'_> {
+    fn cell(&self, index: usize) -> String {
+        match self.values[index] {
+            usize::MAX => "#".to_string(),
+            value => value.to_string(),
+        }
+    }
+}
+
+impl Display for Heatmap<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let cell_width = (0..self.values.len())
+            .map(|index| self.cell(index).len())
+            .max()
+            .unwrap_or(0);
+
+        for index in 0..self.values.len() {
+            if index % self.width != 0 {
+                write!(f, " ")?;
+            } else if index != 0 {
+                writeln!(f)?;
+            }
+            let cell = self.cell(index);
+            write!(f, "{}", " ".repeat(cell_width - cell.len()))?;
+            match (self.highlight)(index) {
+                Some(style) => write!(f, "{}", style.style(cell))?,
+                None => write!(f, "{cell}")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    pub fn test_blend() {
+        let walls = [true, false, false, true];
+        let path = [false, true, true, false];
+        let highlighter = Highlighter::new(2, 4, |_| Highlight::new().symbol('.').dimmed())
+            .mask(0, Blend::Replace, &walls, Highlight::new().symbol('#'))
+            .layer(2, Blend::Over, |index| (index == 2).then_some(Highlight::new().symbol('E')))
+            .mask(1, Blend::Over, &path, Highlight::new().symbol('O').foreground(AnsiColors::Green))
+            .layer(3, Blend::Under, |_| Some(Highlight::new().symbol('?').background(AnsiColors::Black)));
+
+        assert_eq!(Highlight::new().symbol('#').background(AnsiColors::Black), highlighter.highlight(0));
+        assert_eq!(Highlight::new().symbol('O').foreground(AnsiColors::Green).background(AnsiColors::Black).dimmed(), highlighter.highlight(1));
+        assert_eq!(Highlight::new().symbol('E').foreground(AnsiColors::Green).background(AnsiColors::Black).dimmed(), highlighter.highlight(2));
+    }
+
+    #[test]
+    pub fn test_display() {
+        let highlighter = Highlighter::new(3, 5, |index| Highlight::new().symbol(char::from(b'a' + index as u8)));
+
+        assert_eq!("abc\nde", highlighter.to_string());
+    }
+
+    #[test]
+    pub fn test_diff() {
+        let before: ParsedGrid<char> = "#.O\n.@.".parse().unwrap();
+        let after: ParsedGrid<char> = "#..\n.@O".parse().unwrap();
+        let highlighter = diff(&before, &after);
+
+        assert_eq!(Highlight::new().symbol('#').dimmed(), highlighter.highlight(0));
+        assert_eq!(Highlight::new().symbol('.').foreground(AnsiColors::Black).background(AnsiColors::BrightYellow).bold(), highlighter.highlight(2));
+        assert_eq!(Highlight::new().symbol('O').foreground(AnsiColors::Black).background(AnsiColors::BrightYellow).bold(), highlighter.highlight(5));
+        assert_eq!(6, highlighter.to_string().matches(|character| "#.O@".contains(character)).count());
+    }
+
+    #[test]
+    pub fn test_heatmap() {
+        let distances = [0, 1, usize::MAX, 12, 2, 3];
+
+        assert_eq!(" 0  1  #\n12  2  3", heatmap(&distances, 3, |_| None).to_string());
+        let highlighted = heatmap(&distances, 3, |index| (index == 2).then(|| Style::new().bold())).to_string();
+        assert_eq!(format!(" 0  1  {}\n12  2  3", Style::new().bold().style("#")), highlighted);
+    }
+}
@@ -0,0 +1,178 @@
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, VecDeque};
+
+use super::disjoint_set::DisjointSet;
+
+/// The id of a node in a [`Graph`], given in the order the nodes were added from 0.
+pub type NodeId = u32;
+
+/// A directed graph with weighted edges, kept as the list of outgoing edges of every node. Every
+/// node carries a `N`, like the tile and facing of a state in a maze.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Graph<N> {
+    nodes: Vec<N>,
+    edges: Vec<Vec<(NodeId, usize)>>,
+}
+
+impl<N> Default for Graph<N> {
+    fn default() -> Self {
+        Self {
+            nodes: Vec::new(),
+            edges: Vec::new(),
+        }
+    }
+}
+
+impl<N> Graph<N> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.edges.iter().map(Vec::len).sum()
+    }
+
+    pub fn add_node(&mut self, node: N) -> NodeId {
+        self.nodes.push(node);
+        self.edges.push(Vec::new());
+        (self.nodes.len() - 1) as NodeId
+    }
+
+    pub fn node(&self, id: NodeId) -> &N {
+        &self.nodes[id as usize]
+    }
+
+    /// An edge from `from` to `to` that costs `weight` to walk.
+    pub fn add_edge(&mut self, from: NodeId, to: NodeId, weight: usize) {
+        self.edges[from as usize].push((to, weight));
+    }
+
+    /// Edges in both directions between `a` and `b`.
+    pub fn add_undirected_edge(&mut self, a: NodeId, b: NodeId, weight: usize) {
+        self.add_edge(a, b, weight);
+        self.add_edge(b, a, weight);
+    }
+
+    /// The nodes the edges of `id` lead to, with their weights.
+    pub fn neighbours(&self, id: NodeId) -> &[(NodeId, usize)] {
+        &self.edges[id as usize]
+    }
+
+    /// The cost of the cheapest path from `source` to every node, [`usize::MAX`] for the ones that
+    /// can't be reached.
+    pub fn dijkstra(&self, source: NodeId) -> Vec<usize> {
+        let mut costs = vec![usize::MAX; self.nodes.len()];
+        costs[source as usize] = 0;
+        let mut to_visit = BinaryHeap::from([Reverse((0, source))]);
+
+        while let Some(Reverse((cost, node))) = to_visit.pop() {
+            if cost > costs[node as usize] {
+                continue;
+            }
+            for &(neighbour, weight) in self.neighbours(node) {
+                let cost = cost + weight;
+                if cost < costs[neighbour as usize] {
+                    costs[neighbour as usize] = cost;
+                    to_visit.push(Reverse((cost, neighbour)));
+                }
+            }
+        }
+
+        costs
+    }
+
+    /// Like [`Self::dijkstra`], but also finds the nodes every node is reached from on its cheapest
+    /// paths, so all of them can be walked back to `source`.
+    pub fn dijkstra_predecessors(&self, source: NodeId) -> (Vec<usize>, Vec<Vec<NodeId>>) {
+        let mut costs = vec![usize::MAX; self.nodes.len()];
+        let mut predecessors = vec![Vec::new(); self.nodes.len()];
+        costs[source as usize] = 0;
+        let mut to_visit = BinaryHeap::from([Reverse((0, source))]);
+
+        while let Some(Reverse((cost, node))) = to_visit.pop() {
+            if cost > costs[node as usize] {
+                continue;
+            }
+            for &(neighbour, weight) in self.neighbours(node) {
+                let cost = cost + weight;
+                match cost.cmp(&costs[neighbour as usize]) {
+                    Ordering::Less => {
+                        costs[neighbour as usize] = cost;
+                        predecessors[neighbour as usize] = vec![node];
+                        to_visit.push(Reverse((cost, neighbour)));
+                    }
+                    Ordering::Equal => predecessors[neighbour as usize].push(node),
+                    Ordering::Greater => (),
+                }
+            }
+        }
+
+        (costs, predecessors)
+    }
+
+    /// The number of edges on the shortest path from `source` to every node, ignoring the weights,
+    /// [`usize::MAX`] for the ones that can't be reached.
+    pub fn bfs(&self, source: NodeId) -> Vec<usize> {
+        let mut distances = vec![usize::MAX; self.nodes.len()];
+        distances[source as usize] = 0;
+        let mut to_visit = VecDeque::from([source]);
+
+        while let Some(node) = to_visit.pop_front() {
+            let distance = distances[node as usize] + 1;
+            for &(neighbour, _) in self.neighbours(node) {
+                if distances[neighbour as usize] == usize::MAX {
+                    distances[neighbour as usize] = distance;
+                    to_visit.push_back(neighbour);
+                }
+            }
+        }
+
+        distances
+    }
+
+    /// The groups of nodes connected by edges in either direction, each in ascending order and
+    /// ordered by their smallest node.
+    pub fn connected_components(&self) -> Vec<Vec<NodeId>> {
+        let mut components = DisjointSet::new(self.nodes.len());
+        for (from, edges) in self.edges.iter().enumerate() {
+            for &(to, _) in edges {
+                components.union(from, to as usize);
+            }
+        }
+        components.sets().into_iter()
+            .map(|component| component.into_iter().map(|node| node as NodeId).collect())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    pub fn test_graph() {
+        let mut graph = Graph::new();
+        let nodes = ["a", "b", "c", "d", "e", "f"].map(|name| graph.add_node(name));
+        graph.add_edge(nodes[0], nodes[1], 4);
+        graph.add_edge(nodes[0], nodes[2], 1);
+        graph.add_edge(nodes[2], nodes[1], 2);
+        graph.add_edge(nodes[1], nodes[3], 1);
+        graph.add_edge(nodes[4], nodes[0], 1);
+        graph.add_undirected_edge(nodes[5], nodes[5], 3);
+
+        assert_eq!(7, graph.edge_count());
+        assert_eq!("c", *graph.node(nodes[2]));
+        assert_eq!(vec![0, 3, 1, 4, usize::MAX, usize::MAX], graph.dijkstra(nodes[0]));
+        assert_eq!(vec![0, 1, 1, 2, usize::MAX, usize::MAX], graph.bfs(nodes[0]));
+        graph.add_edge(nodes[0], nodes[1], 3);
+        let (costs, predecessors) = graph.dijkstra_predecessors(nodes[0]);
+        assert_eq!(graph.dijkstra(nodes[0]), costs);
+        assert_eq!(vec![nodes[0], nodes[2]], predecessors[nodes[1] as usize]);
+        assert!(predecessors[nodes[0] as usize].is_empty());
+        assert_eq!(vec![vec![0, 1, 2, 3, 4], vec![5]], graph.connected_components());
+    }
+}
@@ -0,0 +1,171 @@
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+use super::Coordinate3;
+
+/// A tile of a hexagonal grid with pointy tops, in axial coordinates: `q` grows to the east and `r`
+/// to the south east. The third cube coordinate `s` is implied, as all three sum up to 0.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct HexCoordinate(pub isize, pub isize);
+
+impl Display for HexCoordinate {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.0, self.1)
+    }
+}
+
+impl HexCoordinate {
+    pub const EAST: Self = Self(1, 0);
+    pub const NORTH_EAST: Self = Self(1, -1);
+    pub const NORTH_WEST: Self = Self(0, -1);
+    pub const WEST: Self = Self(-1, 0);
+    pub const SOUTH_WEST: Self = Self(-1, 1);
+    pub const SOUTH_EAST: Self = Self(0, 1);
+
+    /// The six neighbouring directions, counterclockwise from east.
+    pub const DIRECTIONS: [Self; 6] = [
+        Self::EAST,
+        Self::NORTH_EAST,
+        Self::NORTH_WEST,
+        Self::WEST,
+        Self::SOUTH_WEST,
+        Self::SOUTH_EAST,
+    ];
+
+    pub const fn q(self) -> isize {
+        self.0
+    }
+
+    pub const fn r(self) -> isize {
+        self.1
+    }
+
+    pub const fn s(self) -> isize {
+        -self.0 - self.1
+    }
+
+    /// The cube coordinates `q`, `r` and `s`.
+    pub const fn to_cube(self) -> Coordinate3 {
+        Coordinate3(self.q(), self.r(), self.s())
+    }
+
+    /// The tile of the cube coordinates `q`, `r` and `s`, or [`None`] if they don't sum up to 0.
+    pub const fn from_cube(Coordinate3(q, r, s): Coordinate3) -> Option<Self> {
+        if q + r + s == 0 {
+            Some(Self(q, r))
+        } else {
+            None
+        }
+    }
+
+    /// The number of steps between both tiles.
+    pub const fn distance(self, other: Self) -> usize {
+        (self.0.abs_diff(other.0) + self.1.abs_diff(other.1) + self.s().abs_diff(other.s())) / 2
+    }
+
+    /// The six surrounding tiles, in the order of [`Self::DIRECTIONS`].
+    pub fn neighbours(self) -> impl Iterator<Item = Self> {
+        Self::DIRECTIONS.into_iter().map(move |direction| self + direction)
+    }
+
+    /// The tiles exactly `radius` steps away, counterclockwise starting south west. A ring of radius
+    /// 0 is just this tile.
+    pub fn ring(self, radius: usize) -> impl Iterator<Item = Self> {
+        let start = self + Self::SOUTH_WEST * radius as isize;
+        let steps = Self::DIRECTIONS.into_iter()
+            .flat_map(move |direction| std::iter::repeat_n(direction, radius));
+        std::iter::once(start)
+            .chain(steps.scan(start, |position, direction| {
+                *position += direction;
+                Some(*position)
+            }))
+            .take((6 * radius).max(1))
+    }
+
+    /// This tile and the rings around it, up to `radius` steps away.
+    pub fn spiral(self, radius: usize) -> impl Iterator<Item = Self> {
+        (0..=radius).flat_map(move |radius| self.ring(radius))
+    }
+}
+
+impl Add for HexCoordinate {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self(self.0 + rhs.0, self.1 + rhs.1)
+    }
+}
+
+impl AddAssign for HexCoordinate {
+    fn add_assign(&mut self, rhs: Self) {
+        self.0 += rhs.0;
+        self.1 += rhs.1;
+    }
+}
+
+impl Sub for HexCoordinate {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self(self.0 - rhs.0, self.1 - rhs.1)
+    }
+}
+
+impl SubAssign for HexCoordinate {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.0 -= rhs.0;
+        self.1 -= rhs.1;
+    }
+}
+
+impl Mul<isize> for HexCoordinate {
+    type Output = Self;
+
+    fn mul(self, rhs: isize) -> Self::Output {
+        Self(self.0 * rhs, self.1 * rhs)
+    }
+}
+
+impl Neg for HexCoordinate {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self(-self.0, -self.1)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+    use super::*;
+
+    #[test]
+    pub fn test_distance() {
+        let (a, b) = (HexCoordinate(1, -2), HexCoordinate(-2, 3));
+
+        assert_eq!(5, a.distance(b));
+        assert_eq!(0, a.distance(a));
+        assert!(HexCoordinate::DIRECTIONS.iter().all(|&direction| HexCoordinate::default().distance(direction) == 1));
+        assert_eq!(Coordinate3(1, -2, 1), a.to_cube());
+        assert_eq!(Some(a), HexCoordinate::from_cube(a.to_cube()));
+        assert_eq!(None, HexCoordinate::from_cube(Coordinate3(1, 1, 1)));
+        assert_eq!(HexCoordinate(0, 0), HexCoordinate::EAST + HexCoordinate::WEST);
+    }
+
+    #[test]
+    pub fn test_rings() {
+        let center = HexCoordinate(2, -1);
+
+        assert_eq!(vec![center], center.ring(0).collect::<Vec<_>>());
+        assert_eq!(center.neighbours().collect::<HashSet<_>>(), center.ring(1).collect::<HashSet<_>>());
+        for radius in 1..5 {
+            let ring = center.ring(radius).collect::<Vec<_>>();
+            assert_eq!(6 * radius, ring.iter().collect::<HashSet<_>>().len());
+            assert!(ring.iter().all(|tile| tile.distance(center) == radius));
+        }
+
+        let spiral = center.spiral(3).collect::<HashSet<_>>();
+        assert_eq!(1 + 3 * 3 * 4, spiral.len());
+        assert!(spiral.iter().all(|tile| tile.distance(center) <= 3));
+    }
+}
@@ -0,0 +1,162 @@
+use std::iter::Sum;
+use std::ops::{Range, Sub};
+
+/// A set of values kept as the sorted, disjoint ranges they make up, like the free space on a disk.
+/// Touching ranges are merged, so every gap between two ranges holds at least one value.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct IntervalSet<T> {
+    ranges: Vec<Range<T>>,
+}
+
+impl<T> Default for IntervalSet<T> {
+    fn default() -> Self {
+        Self {
+            ranges: Vec::new(),
+        }
+    }
+}
+
+impl<T: Copy + Ord> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The ranges of the set, in ascending order.
+    pub fn ranges(&self) -> &[Range<T>] {
+        &self.ranges
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// The number of values in the set.
+    pub fn len(&self) -> T
+    where
+        T: Sub<Output = T> + Sum,
+    {
+        self.ranges.iter()
+            .map(|range| range.end - range.start)
+            .sum()
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        let index = self.ranges.partition_point(|range| range.end <= value);
+        self.ranges.get(index).is_some_and(|range| range.start <= value)
+    }
+
+    /// Adds the values of `range`, merging it with the ranges it overlaps or touches.
+    pub fn insert(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+        let first = self.ranges.partition_point(|other| other.end < range.start);
+        let last = self.ranges.partition_point(|other| other.start <= range.end);
+        let merged = if first < last {
+            range.start.min(self.ranges[first].start)..range.end.max(self.ranges[last - 1].end)
+        } else {
+            range
+        };
+        self.ranges.splice(first..last, [merged]);
+    }
+
+    /// Removes the values of `range`, cutting the ranges it overlaps.
+    pub fn remove(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+        let first = self.ranges.partition_point(|other| other.end <= range.start);
+        let last = self.ranges.partition_point(|other| other.start < range.end);
+        if first >= last {
+            return;
+        }
+
+        let (start, end) = (self.ranges[first].start, self.ranges[last - 1].end);
+        let rest = [start..range.start, range.end..end].into_iter()
+            .filter(|rest| !rest.is_empty());
+        self.ranges.splice(first..last, rest);
+    }
+
+    /// The values in either set.
+    pub fn union(&self, other: &Self) -> Self {
+        let mut union = self.clone();
+        for range in &other.ranges {
+            union.insert(range.clone());
+        }
+        union
+    }
+
+    /// The values in this set but not in `other`.
+    pub fn subtract(&self, other: &Self) -> Self {
+        let mut difference = self.clone();
+        for range in &other.ranges {
+            difference.remove(range.clone());
+        }
+        difference
+    }
+
+    /// The values in both sets.
+    pub fn intersect(&self, other: &Self) -> Self {
+        let (mut these, mut others) = (self.ranges.iter().peekable(), other.ranges.iter().peekable());
+        let mut ranges = Vec::new();
+        while let (Some(this), Some(other)) = (these.peek(), others.peek()) {
+            let (start, end) = (this.start.max(other.start), this.end.min(other.end));
+            if start < end {
+                ranges.push(start..end);
+            }
+            if this.end < other.end {
+                these.next();
+            } else {
+                others.next();
+            }
+        }
+        Self {
+            ranges,
+        }
+    }
+}
+
+impl<T: Copy + Ord> FromIterator<Range<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> Self {
+        let mut set = Self::new();
+        for range in iter {
+            set.insert(range);
+        }
+        set
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    pub fn test_insert_and_remove() {
+        let mut set = IntervalSet::from_iter([5..8, 0..2, 10..12]);
+        assert_eq!(&[0..2, 5..8, 10..12], set.ranges());
+        assert_eq!(7, set.len());
+
+        set.insert(2..3);
+        set.insert(7..10);
+        set.insert(20..20);
+        assert_eq!(&[0..3, 5..12], set.ranges());
+
+        set.remove(1..6);
+        set.remove(8..9);
+        set.remove(30..40);
+        assert_eq!(&[0..1, 6..8, 9..12], set.ranges());
+        assert!(set.contains(0) && set.contains(11));
+        assert!(!set.contains(8) && !set.contains(12) && !set.contains(-1));
+    }
+
+    #[test]
+    pub fn test_set_operations() {
+        let a = IntervalSet::from_iter([0..5, 10..15]);
+        let b = IntervalSet::from_iter([3..12, 14..20]);
+
+        assert_eq!(&[0..20], a.union(&b).ranges());
+        assert_eq!(&[0..3, 12..14], a.subtract(&b).ranges());
+        assert_eq!(&[3..5, 10..12, 14..15], a.intersect(&b).ranges());
+        assert!(a.intersect(&IntervalSet::new()).is_empty());
+    }
+}
@@ -0,0 +1,106 @@
+/// The greatest common divisor of `a` and `b`, which is never negative.
+pub fn gcd(mut a: i128, mut b: i128) -> i128 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a.abs()
+}
+
+/// The least common multiple of `a` and `b`, which is never negative.
+pub fn lcm(a: i128, b: i128) -> i128 {
+    if a == 0 || b == 0 {
+        return 0;
+    }
+    (a / gcd(a, b) * b).abs()
+}
+
+/// The greatest common divisor `g` of `a` and `b` together with the `x` and `y` for which
+/// `a * x + b * y == g`.
+pub fn egcd(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_remainder, mut remainder) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+    while remainder != 0 {
+        let quotient = old_remainder / remainder;
+        (old_remainder, remainder) = (remainder, old_remainder - quotient * remainder);
+        (old_x, x) = (x, old_x - quotient * x);
+        (old_y, y) = (y, old_y - quotient * y);
+    }
+
+    if old_remainder < 0 {
+        (-old_remainder, -old_x, -old_y)
+    } else {
+        (old_remainder, old_x, old_y)
+    }
+}
+
+/// The smallest non-negative number that leaves every remainder of the `(remainder, modulus)`
+/// `congruences` when divided by its modulus, together with the modulus the solutions repeat in.
+/// The moduli don't have to be coprime, so there is no solution if two of them contradict each
+/// other.
+pub fn crt(congruences: &[(i128, i128)]) -> Option<(i128, i128)> {
+    congruences.iter().try_fold((0, 1), |(remainder, modulus), &(other_remainder, other_modulus)| {
+        let (divisor, inverse, _) = egcd(modulus, other_modulus);
+        let difference = other_remainder - remainder;
+        if difference % divisor != 0 {
+            return None;
+        }
+
+        // the solutions so far are `remainder + modulus * steps`, the steps have to make up for
+        // the difference to the other remainder
+        let combined = modulus / divisor * other_modulus;
+        let steps = (difference / divisor * inverse).rem_euclid(other_modulus / divisor);
+        Some(((remainder + modulus * steps).rem_euclid(combined), combined))
+    })
+}
+
+/// `base` to the power of `exponent`, modulo `modulus`. The intermediate products have to fit an
+/// [`i128`], so `modulus` should fit an [`i64`].
+pub fn mod_pow(base: i128, mut exponent: u64, modulus: i128) -> i128 {
+    let mut result = 1 % modulus;
+    let mut base = base.rem_euclid(modulus);
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = result * base % modulus;
+        }
+        base = base * base % modulus;
+        exponent >>= 1;
+    }
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    pub fn test_gcd() {
+        assert_eq!(6, gcd(48, 18));
+        assert_eq!(6, gcd(-48, 18));
+        assert_eq!(5, gcd(0, 5));
+        assert_eq!(144, lcm(48, -18));
+        assert_eq!(0, lcm(0, 7));
+
+        for (a, b) in [(240, 46), (-7, 3), (0, 9), (17, 0)] {
+            let (g, x, y) = egcd(a, b);
+            assert_eq!(gcd(a, b), g, "{a}, {b}");
+            assert_eq!(g, a * x + b * y, "{a}, {b}");
+        }
+    }
+
+    #[test]
+    pub fn test_crt() {
+        assert_eq!(Some((23, 105)), crt(&[(2, 3), (3, 5), (2, 7)]));
+        assert_eq!(Some((10, 12)), crt(&[(-2, 4), (4, 6)]));
+        assert_eq!(None, crt(&[(1, 4), (2, 6)]));
+        assert_eq!(Some((0, 1)), crt(&[]));
+    }
+
+    #[test]
+    pub fn test_mod_pow() {
+        assert_eq!(24, mod_pow(2, 10, 1000));
+        assert_eq!(1, mod_pow(-3, 0, 7));
+        assert_eq!(6, mod_pow(-1, 3, 7));
+        assert_eq!(0, mod_pow(5, 3, 1));
+    }
+}
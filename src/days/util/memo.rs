@@ -0,0 +1,148 @@
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::RwLock;
+
+/// The results of a function for the keys it was already called with, like the arrangements of
+/// the pattern suffixes of day 19. It is passed to the recursion explicitly, so every memo belongs
+/// to one set of inputs.
+#[derive(Clone, Debug)]
+pub struct Memo<K, V> {
+    values: HashMap<K, V>,
+}
+
+impl<K, V> Default for Memo<K, V> {
+    fn default() -> Self {
+        Self {
+            values: HashMap::new(),
+        }
+    }
+}
+
+impl<K: Eq + Hash, V: Clone> Memo<K, V> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn get<Q: Eq + Hash + ?Sized>(&self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+    {
+        self.values.get(key).cloned()
+    }
+
+    pub fn insert(&mut self, key: K, value: V) {
+        self.values.insert(key, value);
+    }
+
+    /// The remembered value of `key`, or the one `compute` returns for it. `compute` gets the memo
+    /// to recurse with.
+    pub fn get_or_insert_with(&mut self, key: K, compute: impl FnOnce(&mut Self) -> V) -> V {
+        if let Some(value) = self.values.get(&key) {
+            return value.clone();
+        }
+        let value = compute(self);
+        self.values.insert(key, value.clone());
+        value
+    }
+
+    pub fn clear(&mut self) {
+        self.values.clear();
+    }
+}
+
+/// A [`Memo`] that can be shared between threads. Two threads missing the same key at once both
+/// compute it, as the lock isn't held while computing.
+#[derive(Debug)]
+pub struct SharedMemo<K, V> {
+    values: RwLock<HashMap<K, V>>,
+}
+
+impl<K, V> Default for SharedMemo<K, V> {
+    fn default() -> Self {
+        Self {
+            values: RwLock::new(HashMap::new()),
+        }
+    }
+}
+
+impl<K: Eq + Hash, V: Clone> SharedMemo<K, V> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.read().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.read().unwrap().is_empty()
+    }
+
+    pub fn get<Q: Eq + Hash + ?Sized>(&self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+    {
+        self.values.read().unwrap().get(key).cloned()
+    }
+
+    /// Like [`Memo::get_or_insert_with`].
+    pub fn get_or_insert_with(&self, key: K, compute: impl FnOnce(&Self) -> V) -> V {
+        if let Some(value) = self.get(&key) {
+            return value;
+        }
+        let value = compute(self);
+        self.values.write().unwrap().insert(key, value.clone());
+        value
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn fibonacci(n: u64, memo: &mut Memo<u64, u64>) -> u64 {
+        if n < 2 {
+            return n;
+        }
+        memo.get_or_insert_with(n, |memo| fibonacci(n - 1, memo) + fibonacci(n - 2, memo))
+    }
+
+    #[test]
+    pub fn test_memo() {
+        let mut memo = Memo::new();
+
+        assert_eq!(12586269025, fibonacci(50, &mut memo));
+        assert_eq!(49, memo.len());
+        assert_eq!(Some(55), memo.get(&10));
+        assert_eq!(None, memo.get(&51));
+
+        memo.clear();
+        assert!(memo.is_empty());
+    }
+
+    #[test]
+    pub fn test_shared_memo() {
+        let memo = SharedMemo::new();
+
+        std::thread::scope(|scope| {
+            for _ in 0..4 {
+                scope.spawn(|| {
+                    for n in 0..100u64 {
+                        assert_eq!(n * n, memo.get_or_insert_with(n, |_| n * n));
+                    }
+                });
+            }
+        });
+        assert_eq!(100, memo.len());
+        assert_eq!(Some(81), memo.get(&9));
+    }
+}
@@ -0,0 +1,104 @@
+use std::error::Error;
+use std::str::FromStr;
+
+use eyre::{eyre, WrapErr};
+
+/// The rest of `line` after `label`, like the `729` of `Register A: 729` after `Register A: `.
+pub fn labelled<'a>(line: &'a str, label: &str) -> eyre::Result<&'a str> {
+    line.strip_prefix(label)
+        .ok_or_else(|| eyre!("Expected {line:?} to start with {label:?}"))
+}
+
+/// The parts of `s` before and after the first `separator`, like the coordinates of `X=1, Y=2`.
+pub fn pair<'a>(s: &'a str, separator: &str) -> eyre::Result<(&'a str, &'a str)> {
+    s.split_once(separator)
+        .ok_or_else(|| eyre!("Expected {separator:?} in {s:?}"))
+}
+
+/// `s` parsed as a `T`, with the offending text in the error.
+pub fn parsed<T>(s: &str) -> eyre::Result<T>
+where
+    T: FromStr,
+    T::Err: Error + Send + Sync + 'static,
+{
+    s.parse().wrap_err_with(|| format!("Failed to parse {s:?}"))
+}
+
+/// The numbers written in `s`, each a run of digits. A `-` right before the digits makes the number
+/// negative, unless it follows another number like in a range `1-3`.
+fn int_tokens(s: &str) -> impl Iterator<Item = &str> + '_ {
+    let bytes = s.as_bytes();
+    let mut index = 0;
+    std::iter::from_fn(move || {
+        while index < bytes.len() {
+            let start = index;
+            let negative = bytes[index] == b'-'
+                && bytes.get(index + 1).is_some_and(u8::is_ascii_digit)
+                && (index == 0 || !bytes[index - 1].is_ascii_digit());
+            index += 1;
+            if negative || bytes[start].is_ascii_digit() {
+                while index < bytes.len() && bytes[index].is_ascii_digit() {
+                    index += 1;
+                }
+                return Some(&s[start..index]);
+            }
+        }
+        None
+    })
+}
+
+/// Every number in `s`, ignoring whatever is around them, like the labels in `p=0,4 v=3,-3`.
+/// Numbers that don't fit `T`, like negative ones for unsigned types, are skipped.
+pub fn ints<T: FromStr>(s: &str) -> Vec<T> {
+    int_tokens(s)
+        .filter_map(|token| token.parse().ok())
+        .collect()
+}
+
+/// Like [`ints`], but expecting exactly `N` numbers that all fit `T`.
+pub fn ints_fixed<T, const N: usize>(s: &str) -> eyre::Result<[T; N]>
+where
+    T: FromStr,
+    T::Err: Error + Send + Sync + 'static,
+{
+    let values = int_tokens(s)
+        .map(|token| token.parse().wrap_err_with(|| format!("Failed to parse {token:?} in {s:?}")))
+        .collect::<eyre::Result<Vec<T>>>()?;
+    let count = values.len();
+    values.try_into()
+        .map_err(|_| eyre!("Expected {N} numbers in {s:?}, found {count}"))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    pub fn test_labelled() {
+        let (x, y) = pair(labelled("Prize: X=8400, Y=5400", "Prize: ").unwrap(), ", ").unwrap();
+        assert_eq!(8400, parsed::<usize>(labelled(x, "X=").unwrap()).unwrap());
+        assert_eq!(5400, parsed::<usize>(labelled(y, "Y=").unwrap()).unwrap());
+
+        let error = labelled("Register A: 729", "Program: ").unwrap_err();
+        assert!(error.to_string().contains("Register A: 729"));
+        assert!(pair("p=0,4", " ").is_err());
+        assert!(parsed::<usize>("-3").unwrap_err().to_string().contains("-3"));
+    }
+
+    #[test]
+    pub fn test_ints() {
+        assert_eq!(vec![0, 4, 3, -3], ints::<isize>("p=0,4 v=3,-3"));
+        assert_eq!(vec![0, 4, 3], ints::<usize>("p=0,4 v=3,-3"));
+        assert_eq!(vec![94, 34], ints::<usize>("Button A: X+94, Y+34"));
+        assert_eq!(vec![1, 3, -2], ints::<i32>("1-3 --2 -"));
+        assert!(ints::<u8>("no numbers").is_empty());
+    }
+
+    #[test]
+    pub fn test_ints_fixed() {
+        assert_eq!([8400, 5400], ints_fixed::<usize, 2>("Prize: X=8400, Y=5400").unwrap());
+        assert!(ints_fixed::<usize, 2>("6,1,2").is_err());
+        assert!(ints_fixed::<usize, 2>("6,-1").is_err());
+        assert!(ints_fixed::<u8, 1>("300").is_err());
+    }
+}
@@ -0,0 +1,272 @@
+use std::fmt::{Display, Formatter};
+
+use owo_colors::AnsiColors;
+
+use super::display::{Highlight, Highlighter};
+
+/// The font size of the legend labels and the size of their swatches, in pixels.
+const LEGEND_LINE: usize = 16;
+/// The space between the grid and the legend and around the legend entries, in pixels.
+const MARGIN: usize = 8;
+/// Cells smaller than this are filled with their foreground color instead of showing their symbol.
+const MIN_SYMBOL_CELL: usize = 8;
+
+/// Where the legend of an image goes relative to the grid.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum LegendPlacement {
+    Right,
+    Below,
+}
+
+/// How [`svg`] lays out an image of a grid.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ImageOptions {
+    /// The width and height of a cell, in pixels
+    pub cell_size: usize,
+    /// Outline every cell
+    pub gridlines: bool,
+    pub legend: Option<LegendPlacement>,
+    /// The highlights shown in the legend with what they mean
+    pub legend_entries: Vec<(Highlight, String)>,
+}
+
+impl Default for ImageOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ImageOptions {
+    /// One pixel per cell without gridlines or a legend.
+    pub const fn new() -> Self {
+        Self {
+            cell_size: 1,
+            gridlines: false,
+            legend: None,
+            legend_entries: Vec::new(),
+        }
+    }
+
+    pub const fn cell_size(mut self, cell_size: usize) -> Self {
+        self.cell_size = cell_size;
+        self
+    }
+
+    pub const fn gridlines(mut self) -> Self {
+        self.gridlines = true;
+        self
+    }
+
+    pub fn legend(mut self, placement: LegendPlacement, entries: impl IntoIterator<Item = (Highlight, String)>) -> Self {
+        self.legend = Some(placement);
+        self.legend_entries = entries.into_iter().collect();
+        self
+    }
+}
+
+/// Where everything of an image goes, in pixels.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+struct Layout {
+    width: usize,
+    height: usize,
+    /// The top left corner of the legend
+    legend: Option<(usize, usize)>,
+}
+
+impl Layout {
+    fn new(columns: usize, rows: usize, options: &ImageOptions) -> Self {
+        let (grid_width, grid_height) = (columns * options.cell_size, rows * options.cell_size);
+        let legend_size = || {
+            let label = options.legend_entries.iter()
+                .map(|(_, label)| label.chars().count())
+                .max()
+                .unwrap_or(0);
+            // labels are estimated at 0.6em per character, most fonts stay below that
+            let width = LEGEND_LINE + MARGIN + (label * LEGEND_LINE * 3).div_ceil(5);
+            let height = options.legend_entries.len() * (LEGEND_LINE + MARGIN);
+            (width, height)
+        };
+
+        match options.legend.filter(|_| !options.legend_entries.is_empty()) {
+            Some(LegendPlacement::Right) => {
+                let (width, height) = legend_size();
+                Self {
+                    width: grid_width + MARGIN + width,
+                    height: grid_height.max(height),
+                    legend: Some((grid_width + MARGIN, 0)),
+                }
+            }
+            Some(LegendPlacement::Below) => {
+                let (width, height) = legend_size();
+                Self {
+                    width: grid_width.max(width),
+                    height: grid_height + MARGIN + height,
+                    legend: Some((0, grid_height + MARGIN)),
+                }
+            }
+            None => Self {
+                width: grid_width,
+                height: grid_height,
+                legend: None,
+            },
+        }
+    }
+}
+
+/// The hex code of a terminal color as common terminals show it, [`None`] for the default color.
+fn hex(color: AnsiColors) -> Option<&'static str> {
+    Some(match color {
+        AnsiColors::Black => "#000000",
+        AnsiColors::Red => "#cd0000",
+        AnsiColors::Green => "#00cd00",
+        AnsiColors::Yellow => "#cdcd00",
+        AnsiColors::Blue => "#0000ee",
+        AnsiColors::Magenta => "#cd00cd",
+        AnsiColors::Cyan => "#00cdcd",
+        AnsiColors::White => "#e5e5e5",
+        AnsiColors::Default => return None,
+        AnsiColors::BrightBlack => "#7f7f7f",
+        AnsiColors::BrightRed => "#ff0000",
+        AnsiColors::BrightGreen => "#00ff00",
+        AnsiColors::BrightYellow => "#ffff00",
+        AnsiColors::BrightBlue => "#5c5cff",
+        AnsiColors::BrightMagenta => "#ff00ff",
+        AnsiColors::BrightCyan => "#00ffff",
+        AnsiColors::BrightWhite => "#ffffff",
+    })
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// An SVG image of the cells of `highlighter`, e.g. to export a day's grid for presentation.
+///
+/// A cell is filled with its background color and shows its symbol in its foreground color, unless
+/// the cells are too small to read a symbol, then it is filled with its foreground color instead.
+pub fn svg<'a>(highlighter: &'a Highlighter<'a>, options: &'a ImageOptions) -> Svg<'a> {
+    Svg {
+        highlighter,
+        options,
+    }
+}
+
+/// See [`svg`].
+pub struct Svg<'a> {
+    highlighter: &'a Highlighter<'a>,
+    options: &'a ImageOptions,
+}
+
+impl Svg<'_> {
+    fn layout(&self) -> Layout {
+        let columns = self.highlighter.width;
+        Layout::new(columns, self.highlighter.len.div_ceil(columns), self.options)
+    }
+
+    fn cell(&self, f: &mut Formatter<'_>, x: usize, y: usize, highlight: Highlight) -> std::fmt::Result {
+        let size = self.options.cell_size;
+        let symbol = highlight.symbol.filter(|symbol| !symbol.is_whitespace() && size >= MIN_SYMBOL_CELL);
+        let fill = match symbol {
+            Some(_) => highlight.background,
+            None => highlight.background.or(highlight.foreground),
+        };
+        if let Some(fill) = fill.and_then(hex) {
+            write!(f, r#"<rect x="{x}" y="{y}" width="{size}" height="{size}" fill="{fill}""#)?;
+            if highlight.dimmed {
+                write!(f, r#" fill-opacity="0.5""#)?;
+            }
+            writeln!(f, "/>")?;
+        }
+        if let Some(symbol) = symbol {
+            let color = highlight.foreground.and_then(hex).unwrap_or("#000000");
+            write!(f, r#"<text x="{}" y="{}" font-size="{size}" text-anchor="middle" dominant-baseline="central" fill="{color}""#, x + size / 2, y + size / 2)?;
+            if highlight.bold {
+                write!(f, r#" font-weight="bold""#)?;
+            }
+            if highlight.dimmed {
+                write!(f, r#" fill-opacity="0.5""#)?;
+            }
+            writeln!(f, ">{}</text>", escape(&symbol.to_string()))?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for Svg<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let layout = self.layout();
+        let (columns, size) = (self.highlighter.width, self.options.cell_size);
+        let rows = self.highlighter.len.div_ceil(columns);
+        writeln!(f, r#"<svg xmlns="http://www.w3.org/2000/svg" width="{0}" height="{1}" viewBox="0 0 {0} {1}" font-family="monospace">"#, layout.width, layout.height)?;
+        writeln!(f, r##"<rect width="100%" height="100%" fill="#ffffff"/>"##)?;
+
+        for index in 0..self.highlighter.len {
+            self.cell(f, index % columns * size, index / columns * size, self.highlighter.highlight(index))?;
+        }
+
+        if self.options.gridlines {
+            let (width, height) = (columns * size, rows * size);
+            write!(f, r##"<path stroke="#7f7f7f" stroke-width="1" fill="none" d=""##)?;
+            for column in 0..=columns {
+                write!(f, "M{0} 0V{height}", column * size)?;
+            }
+            for row in 0..=rows {
+                write!(f, "M0 {0}H{width}", row * size)?;
+            }
+            writeln!(f, r#""/>"#)?;
+        }
+
+        if let Some((x, y)) = layout.legend {
+            for (entry, (highlight, label)) in self.options.legend_entries.iter().enumerate() {
+                let y = y + entry * (LEGEND_LINE + MARGIN);
+                let fill = highlight.background.or(highlight.foreground).and_then(hex).unwrap_or("#ffffff");
+                writeln!(f, r##"<rect x="{x}" y="{y}" width="{LEGEND_LINE}" height="{LEGEND_LINE}" fill="{fill}" stroke="#7f7f7f"/>"##)?;
+                writeln!(f, r#"<text x="{}" y="{}" font-size="{LEGEND_LINE}" dominant-baseline="central">{}</text>"#, x + LEGEND_LINE + MARGIN, y + LEGEND_LINE / 2, escape(label))?;
+            }
+        }
+
+        write!(f, "</svg>")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    pub fn test_layout() {
+        let entries = [(Highlight::new(), "wall".to_string()), (Highlight::new(), "path".to_string())];
+
+        assert_eq!(Layout { width: 10, height: 5, legend: None }, Layout::new(10, 5, &ImageOptions::new()));
+        let right = ImageOptions::new().cell_size(4).legend(LegendPlacement::Right, entries.clone());
+        assert_eq!(Layout { width: 40 + 8 + 16 + 8 + 39, height: 48, legend: Some((48, 0)) }, Layout::new(10, 2, &right));
+        let below = ImageOptions::new().cell_size(4).legend(LegendPlacement::Below, entries);
+        assert_eq!(Layout { width: 63, height: 8 + 8 + 48, legend: Some((0, 16)) }, Layout::new(10, 2, &below));
+        let empty = ImageOptions::new().legend(LegendPlacement::Right, []);
+        assert_eq!(Layout { width: 10, height: 2, legend: None }, Layout::new(10, 2, &empty));
+    }
+
+    #[test]
+    pub fn test_svg() {
+        let wall = Highlight::new().symbol('#').background(AnsiColors::BrightBlack);
+        let highlighter = Highlighter::new(2, 3, |index| if index == 0 { wall } else { Highlight::new().symbol('.') });
+
+        let tiny = svg(&highlighter, &ImageOptions::new()).to_string();
+        assert!(tiny.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" width="2" height="2""#));
+        assert_eq!(1, tiny.matches("<rect x=").count());
+        assert!(!tiny.contains("<text"));
+
+        let options = ImageOptions::new()
+            .cell_size(10)
+            .gridlines()
+            .legend(LegendPlacement::Below, [(wall, "<wall>".to_string())]);
+        let image = svg(&highlighter, &options).to_string();
+        assert_eq!(4, image.matches("</text>").count());
+        assert!(image.contains("M20 0V20"));
+        assert!(image.contains("&lt;wall&gt;</text>"));
+        assert!(image.ends_with("</svg>"));
+    }
+}
@@ -0,0 +1,317 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+
+use super::ParsedGrid;
+use super::disjoint_set::DisjointSet;
+
+/// Which nodes of an unweighted graph are connected, the nodes indexed from 0 like the tiles of a
+/// grid. Unlike [`Graph`](super::graph::Graph), it only needs to know the neighbours of a node.
+pub trait Connectivity {
+    fn node_count(&self) -> usize;
+
+    fn neighbours(&self, node: usize) -> impl Iterator<Item = usize> + '_;
+}
+
+impl<T> Connectivity for ParsedGrid<T> {
+    fn node_count(&self) -> usize {
+        self.as_slice().len()
+    }
+
+    fn neighbours(&self, node: usize) -> impl Iterator<Item = usize> + '_ {
+        self.neighbors4(node).map(|(neighbour, _)| neighbour)
+    }
+}
+
+/// The cheapest path from `start` to a state satisfying `is_goal`, moving between states as
+/// `neighbours` allows at the cost it gives each step, together with its cost. Returns [`None`] if
+/// no goal can be reached.
+pub fn dijkstra<State, Neighbours>(
+    start: State,
+    neighbours: impl FnMut(&State) -> Neighbours,
+    is_goal: impl FnMut(&State) -> bool,
+) -> Option<(usize, Vec<State>)>
+where
+    State: Clone + Eq + Hash,
+    Neighbours: IntoIterator<Item = (State, usize)>,
+{
+    astar(start, neighbours, |_| 0, is_goal)
+}
+
+/// Like [`dijkstra`], but visits the states by their cost plus the `heuristic` estimate of the
+/// cost left to a goal. The path is only guaranteed to be the cheapest if the estimates never
+/// exceed the actual costs.
+pub fn astar<State, Neighbours>(
+    start: State,
+    mut neighbours: impl FnMut(&State) -> Neighbours,
+    mut heuristic: impl FnMut(&State) -> usize,
+    mut is_goal: impl FnMut(&State) -> bool,
+) -> Option<(usize, Vec<State>)>
+where
+    State: Clone + Eq + Hash,
+    Neighbours: IntoIterator<Item = (State, usize)>,
+{
+    // the states are numbered in the order they are found, so only the numbers go on the heap
+    let mut to_visit = BinaryHeap::from([Reverse((heuristic(&start), 0, 0))]);
+    let mut states = vec![start.clone()];
+    let mut numbers = HashMap::from([(start, 0)]);
+    let mut costs = vec![0];
+    let mut previous = vec![None];
+
+    while let Some(Reverse((_, cost, state))) = to_visit.pop() {
+        if cost > costs[state] {
+            continue;
+        }
+        if is_goal(&states[state]) {
+            let mut path = vec![states[state].clone()];
+            let mut current = state;
+            while let Some(before) = previous[current] {
+                path.push(states[before].clone());
+                current = before;
+            }
+            path.reverse();
+            return Some((cost, path));
+        }
+
+        for (neighbour, step) in neighbours(&states[state]) {
+            let cost = cost + step;
+            let estimate = cost + heuristic(&neighbour);
+            let neighbour = match numbers.get(&neighbour) {
+                Some(&number) if cost >= costs[number] => continue,
+                Some(&number) => number,
+                None => {
+                    numbers.insert(neighbour.clone(), states.len());
+                    states.push(neighbour);
+                    costs.push(usize::MAX);
+                    previous.push(None);
+                    states.len() - 1
+                }
+            };
+            costs[neighbour] = cost;
+            previous[neighbour] = Some(state);
+            to_visit.push(Reverse((estimate, cost, neighbour)));
+        }
+    }
+
+    None
+}
+
+/// How [`first_breaking_change`] searches for the change.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Strategy {
+    /// Apply the changes one after another, only searching for a new path when the current one
+    /// was cut.
+    Incremental,
+    /// Apply all changes and undo them from the last one, joining the reachable regions until they
+    /// connect again.
+    ReverseUnionFind,
+    /// Bisect the number of applied changes, searching for a path every time.
+    BinarySearch,
+}
+
+/// Finds the first of the `changes`, each removing a node from `graph`, after which `target` can't
+/// be reached from `source` anymore. Returns its index, or [`None`] if the nodes stay connected or
+/// are not connected to begin with.
+pub fn first_breaking_change(graph: &impl Connectivity, changes: &[usize], source: usize, target: usize, strategy: Strategy) -> Option<usize> {
+    match strategy {
+        Strategy::Incremental => incremental(graph, changes, source, target),
+        Strategy::ReverseUnionFind => reverse_union_find(graph, changes, source, target),
+        Strategy::BinarySearch => binary_search(graph, changes, source, target),
+    }
+}
+
+/// The nodes on a shortest path from `source` to `target` that avoids the `removed` ones, listed
+/// from `target` back to `source`.
+fn find_path(graph: &impl Connectivity, removed: &[bool], source: usize, target: usize) -> Option<Vec<usize>> {
+    if removed[source] || removed[target] {
+        return None;
+    }
+    if source == target {
+        return Some(vec![source]);
+    }
+
+    let mut previous = vec![None; graph.node_count()];
+    previous[source] = Some(source);
+    let mut to_visit = VecDeque::from([source]);
+    while let Some(node) = to_visit.pop_front() {
+        if node == target {
+            let mut path = vec![target];
+            while let Some(node) = previous[*path.last().unwrap()].filter(|&node| node != source) {
+                path.push(node);
+            }
+            path.push(source);
+            return Some(path);
+        }
+
+        for neighbour in graph.neighbours(node) {
+            if !removed[neighbour] && previous[neighbour].is_none() {
+                previous[neighbour] = Some(node);
+                to_visit.push_back(neighbour);
+            }
+        }
+    }
+
+    None
+}
+
+fn incremental(graph: &impl Connectivity, changes: &[usize], source: usize, target: usize) -> Option<usize> {
+    let mut removed = vec![false; graph.node_count()];
+    let mut on_path = vec![false; graph.node_count()];
+    for node in find_path(graph, &removed, source, target)? {
+        on_path[node] = true;
+    }
+
+    for (index, &node) in changes.iter().enumerate() {
+        removed[node] = true;
+        if on_path[node] {
+            let path = match find_path(graph, &removed, source, target) {
+                Some(path) => path,
+                None => return Some(index),
+            };
+            on_path.fill(false);
+            for node in path {
+                on_path[node] = true;
+            }
+        }
+    }
+
+    None
+}
+
+fn binary_search(graph: &impl Connectivity, changes: &[usize], source: usize, target: usize) -> Option<usize> {
+    let connected = |applied: usize| {
+        let mut removed = vec![false; graph.node_count()];
+        for &node in &changes[..applied] {
+            removed[node] = true;
+        }
+        find_path(graph, &removed, source, target).is_some()
+    };
+    if !connected(0) || connected(changes.len()) {
+        return None;
+    }
+
+    // the nodes are connected with `connected_until` changes applied, but not with `broken_from`
+    let (mut connected_until, mut broken_from) = (0, changes.len());
+    while broken_from - connected_until > 1 {
+        let middle = (connected_until + broken_from) / 2;
+        if connected(middle) {
+            connected_until = middle;
+        } else {
+            broken_from = middle;
+        }
+    }
+
+    Some(broken_from - 1)
+}
+
+fn reverse_union_find(graph: &impl Connectivity, changes: &[usize], source: usize, target: usize) -> Option<usize> {
+    // a node removed more than once only returns when the first of its removals is undone
+    let mut removals = vec![0usize; graph.node_count()];
+    for &node in changes {
+        removals[node] += 1;
+    }
+
+    let mut regions = DisjointSet::new(graph.node_count());
+    let restore = |regions: &mut DisjointSet, removals: &[usize], node: usize| {
+        for neighbour in graph.neighbours(node) {
+            if removals[neighbour] == 0 {
+                regions.union(node, neighbour);
+            }
+        }
+    };
+    for node in (0..graph.node_count()).filter(|&node| removals[node] == 0) {
+        restore(&mut regions, &removals, node);
+    }
+    let connected = |regions: &mut DisjointSet, removals: &[usize]| {
+        removals[source] == 0 && removals[target] == 0 && regions.same(source, target)
+    };
+    if connected(&mut regions, &removals) {
+        return None;
+    }
+
+    for (index, &node) in changes.iter().enumerate().rev() {
+        removals[node] -= 1;
+        if removals[node] == 0 {
+            restore(&mut regions, &removals, node);
+        }
+        if connected(&mut regions, &removals) {
+            return Some(index);
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod test {
+    use rand::{Rng, SeedableRng};
+    use rand::rngs::StdRng;
+    use super::*;
+
+    const STRATEGIES: [Strategy; 3] = [Strategy::Incremental, Strategy::ReverseUnionFind, Strategy::BinarySearch];
+
+    #[test]
+    pub fn test_dijkstra() {
+        let edges = [(0, 1, 4), (0, 2, 1), (2, 1, 2), (1, 3, 1), (2, 3, 5), (4, 0, 1)];
+        let neighbours = |&node: &usize| edges.iter()
+            .filter(move |&&(from, _, _)| from == node)
+            .map(|&(_, to, cost)| (to, cost))
+            .collect::<Vec<_>>();
+
+        assert_eq!(Some((4, vec![0, 2, 1, 3])), dijkstra(0, neighbours, |&node| node == 3));
+        assert_eq!(Some((0, vec![0])), dijkstra(0, neighbours, |&node| node == 0));
+        assert_eq!(None, dijkstra(0, neighbours, |&node| node == 4));
+    }
+
+    #[test]
+    pub fn test_astar_like_dijkstra() {
+        let mut rng = StdRng::seed_from_u64(18);
+        for _ in 0..200 {
+            let (width, height) = (rng.gen_range(2..12), rng.gen_range(2..12));
+            let mut grid = ParsedGrid::from_fn(width, height, |_| rng.gen_bool(0.3));
+            let (start, end) = (0, width * height - 1);
+            grid.as_mut_slice()[start] = false;
+            grid.as_mut_slice()[end] = false;
+
+            let grid = &grid;
+            let neighbours = |&node: &usize| Connectivity::neighbours(grid, node)
+                .filter(move |&neighbour| !grid.as_slice()[neighbour])
+                .map(|neighbour| (neighbour, 1))
+                .collect::<Vec<_>>();
+            let end_coordinate = grid.index_to_coordinate(end);
+            let manhattan = |&node: &usize| grid.index_to_coordinate(node).manhattan(end_coordinate);
+
+            let expected = dijkstra(start, neighbours, |&node| node == end).map(|(cost, _)| cost);
+            assert_eq!(expected, astar(start, neighbours, manhattan, |&node| node == end).map(|(cost, _)| cost));
+            assert_eq!(expected, astar(start, neighbours, |_| 0, |&node| node == end).map(|(cost, _)| cost));
+        }
+    }
+
+    #[test]
+    pub fn test_find_path() {
+        let grid = ParsedGrid::<bool>::new(3, 3);
+        let mut removed = vec![false; 9];
+
+        assert_eq!(Some(vec![4]), find_path(&grid, &removed, 4, 4));
+        assert_eq!(Some(vec![2, 1, 0]), find_path(&grid, &removed, 0, 2));
+        removed[1] = true;
+        assert_eq!(Some(5), find_path(&grid, &removed, 0, 2).map(|path| path.len()));
+        removed[4] = true;
+        removed[7] = true;
+        assert_eq!(None, find_path(&grid, &removed, 0, 2));
+    }
+
+    #[test]
+    pub fn test_first_breaking_change() {
+        let grid = ParsedGrid::<bool>::new(3, 3);
+
+        for strategy in STRATEGIES {
+            // walls off the middle column
+            assert_eq!(Some(3), first_breaking_change(&grid, &[1, 4, 2, 7], 0, 8, strategy), "{strategy:?}");
+            assert_eq!(Some(3), first_breaking_change(&grid, &[4, 4, 1, 7], 0, 8, strategy), "{strategy:?}");
+            assert_eq!(Some(0), first_breaking_change(&grid, &[8, 1], 0, 8, strategy), "{strategy:?}");
+            assert_eq!(None, first_breaking_change(&grid, &[1, 4], 0, 8, strategy), "{strategy:?}");
+            assert_eq!(None, first_breaking_change(&grid, &[], 0, 8, strategy), "{strategy:?}");
+        }
+    }
+}
@@ -0,0 +1,89 @@
+use super::Direction;
+
+/// Records when a walk over a grid visited every position, separately for every direction it faced
+/// there. Every recorded state advances the time by one, starting at 0.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct TimedWalk {
+    times: Vec<[Option<usize>; 4]>,
+    time: usize,
+}
+
+impl TimedWalk {
+    pub fn new(len: usize) -> Self {
+        Self {
+            times: vec![[None; 4]; len],
+            time: 0,
+        }
+    }
+
+    /// Records being at `position` facing `direction` as the next state of the walk. Returns `false`
+    /// if the walk was already in that state, which means it loops.
+    pub fn visit(&mut self, position: usize, direction: Direction) -> bool {
+        let time = &mut self.times[position][direction.index()];
+        if time.is_some() {
+            return false;
+        }
+        *time = Some(self.time);
+        self.time += 1;
+        true
+    }
+
+    /// When the walk was at `position` facing `direction`.
+    pub fn time(&self, position: usize, direction: Direction) -> Option<usize> {
+        self.times[position][direction.index()]
+    }
+
+    /// When and facing which direction the walk first reached `position`.
+    pub fn first_visit(&self, position: usize) -> Option<(usize, Direction)> {
+        Direction::ALL.into_iter()
+            .filter_map(|direction| Some((self.time(position, direction)?, direction)))
+            .min()
+    }
+
+    /// When the walk was last at `position`.
+    pub fn last_visit(&self, position: usize) -> Option<usize> {
+        self.times[position].iter()
+            .flatten()
+            .max()
+            .copied()
+    }
+
+    pub fn visited(&self, position: usize) -> bool {
+        self.times[position].iter().any(Option::is_some)
+    }
+
+    /// The number of recorded states.
+    pub fn len(&self) -> usize {
+        self.time
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.time == 0
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    pub fn test_timed_walk() {
+        let mut walk = TimedWalk::new(4);
+        assert!(walk.visit(0, Direction::East));
+        assert!(walk.visit(1, Direction::East));
+        assert!(walk.visit(1, Direction::South));
+        assert!(walk.visit(3, Direction::South));
+        assert!(walk.visit(3, Direction::West));
+        assert!(walk.visit(2, Direction::West));
+        assert!(walk.visit(2, Direction::North));
+        assert!(walk.visit(0, Direction::North));
+        assert!(!walk.visit(1, Direction::East));
+
+        assert_eq!(8, walk.len());
+        assert_eq!(Some(2), walk.time(1, Direction::South));
+        assert_eq!(None, walk.time(1, Direction::North));
+        assert_eq!(Some((3, Direction::South)), walk.first_visit(3));
+        assert_eq!(Some(7), walk.last_visit(0));
+        assert!(walk.visited(2));
+    }
+}
@@ -1 +1,33 @@
+use eyre::eyre;
+
+use days::Day;
+use days::solver::{Answer, Part};
+
 pub mod days;
+
+/// Solves `part` of `day` for `input` with the registered solution of the day.
+pub fn solve(day: Day, part: Part, input: &str) -> eyre::Result<Answer> {
+    let registration = days::registration(day)
+        .ok_or(eyre!("{day} is not implemented"))?;
+    let input = (registration.parse)(input)?;
+    match part {
+        Part::One => input.part1(),
+        Part::Two => input.part2(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[cfg(feature = "day1")]
+    #[test]
+    pub fn test_solve() {
+        let day = Day::try_from(1).unwrap();
+        let input = "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n";
+
+        assert_eq!(Answer::Unsigned(11), solve(day, Part::One, input).unwrap());
+        assert_eq!(Answer::Unsigned(31), solve(day, Part::Two, input).unwrap());
+        assert!(solve(Day::try_from(24).unwrap(), Part::One, input).is_err());
+    }
+}
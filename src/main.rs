@@ -1,74 +1,316 @@
-use tokio::join;
+use std::collections::BTreeMap;
+use std::io::BufReader;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::SystemTime;
 
-use aoc_2024::days;
+use clap::{Args, CommandFactory, Parser, Subcommand};
+use clap_complete::Shell;
+use eyre::{eyre, WrapErr};
+use tokio::sync::{mpsc, Semaphore};
+use tokio::task::JoinSet;
+
+use aoc_2024::days::{self, Day};
+use aoc_2024::days::answer_cache::AnswerCache;
+use aoc_2024::days::approach::ApproachHistory;
+use aoc_2024::days::config::Config;
+use aoc_2024::days::harness::DayResult;
+use aoc_2024::days::manifest::{Manifest, Verification};
+use aoc_2024::days::report::RunReport;
 mod util;
 
+/// Advent of Code 2024 solutions
+#[derive(Debug, Parser)]
+#[command(version, about)]
+struct Cli {
+    /// The session profile from the config file to use instead of the default session
+    #[arg(long, global = true)]
+    profile: Option<String>,
+    /// Check whether a newer release is available
+    #[arg(long, global = true)]
+    check_update: bool,
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Debug, Default, Args)]
+struct RunArgs {
+    /// Only run these days, during the event only today's by default
+    #[arg(long, conflicts_with = "all")]
+    day: Vec<Day>,
+    /// Run all days, even during the event
+    #[arg(long)]
+    all: bool,
+    /// Run on the examples from the puzzle descriptions instead of the personal inputs
+    #[arg(long)]
+    example: bool,
+    /// Download the inputs again instead of using the cached ones
+    #[arg(long)]
+    refresh_input: bool,
+    /// Wait for puzzles that are still locked and fetch their inputs as soon as they unlock
+    #[arg(long)]
+    wait: bool,
+    /// Solve the days that implement it with this approach instead of their active one
+    #[arg(long)]
+    strategy: Option<String>,
+    /// How many days to run at once, the number of available cores by default
+    #[arg(long, short, value_parser = clap::value_parser!(usize).range(1..))]
+    jobs: Option<usize>,
+    /// Solve every day again instead of printing the cached answers of unchanged inputs
+    #[arg(long)]
+    force: bool,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Run today's day during the event and all days otherwise (the default)
+    Run(RunArgs),
+    /// List the days with their status and the approaches of those solved in more than one way
+    List,
+    /// Time every approach on the personal inputs and use the fastest ones from now on
+    Calibrate {
+        /// How many times to solve with every approach, keeping the fastest run
+        #[arg(long, default_value_t = 3)]
+        runs: usize,
+    },
+    /// Print the puzzle description of a day
+    Puzzle {
+        #[arg(long)]
+        day: Day,
+    },
+    /// Submit an answer, unless it is known to be wrong from previous guesses
+    Submit {
+        #[arg(long)]
+        day: Day,
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+        answer: String,
+    },
+    /// Print the salted hash of an accepted answer to register it as one of a day's `my_answers`
+    HashAnswer {
+        #[arg(long)]
+        day: Day,
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+        answer: String,
+    },
+    /// Print the stars and local scores of a private leaderboard
+    Leaderboard {
+        /// The id of the leaderboard, which is the user id of its owner
+        id: u64,
+    },
+    /// Check that the cached inputs are the ones recorded in `input.manifest.toml`
+    VerifyInputs {
+        /// Record the hashes of the cached inputs in the manifest instead
+        #[arg(long)]
+        record: bool,
+    },
+    /// Print the completions for a shell
+    Completions {
+        shell: Shell,
+    },
+    /// Print the man page
+    Mangen,
+    /// Aggregate the span timings of a log captured with `AOC_LOG_FORMAT=json`
+    Report {
+        log: PathBuf,
+        /// Print the report as JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
 #[tokio::main]
 pub async fn main() -> eyre::Result<()> {
     util::setup()?;
 
-    let (
-        day1,
-        day2,
-        day3,
-        day4,
-        day5,
-        day6,
-        day7,
-        day8,
-        day9,
-        day10,
-        day11,
-        day12,
-        day13,
-        day14,
-        day15,
-        day16,
-        day17,
-        day18,
-        day19,
-        day20,
-    ) = join!(
-        tokio::spawn(days::day1::run()),
-        tokio::spawn(days::day2::run()),
-        tokio::spawn(days::day3::run()),
-        tokio::spawn(days::day4::run()),
-        tokio::spawn(days::day5::run()),
-        tokio::spawn(days::day6::run()),
-        tokio::spawn(days::day7::run()),
-        tokio::spawn(days::day8::run()),
-        tokio::spawn(days::day9::run()),
-        tokio::spawn(days::day10::run()),
-        tokio::spawn(days::day11::run()),
-        tokio::spawn(days::day12::run()),
-        tokio::spawn(days::day13::run()),
-        tokio::spawn(days::day14::run()),
-        tokio::spawn(days::day15::run()),
-        tokio::spawn(days::day16::run()),
-        tokio::spawn(days::day17::run()),
-        tokio::spawn(days::day18::run()),
-        tokio::spawn(days::day19::run()),
-        tokio::spawn(days::day20::run()),
-    );
-
-    day1?
-        .and(day2?)
-        .and(day3?)
-        .and(day4?)
-        .and(day5?)
-        .and(day6?)
-        .and(day7?)
-        .and(day8?)
-        .and(day9?)
-        .and(day10?)
-        .and(day11?)
-        .and(day12?)
-        .and(day13?)
-        .and(day14?)
-        .and(day15?)
-        .and(day16?)
-        .and(day17?)
-        .and(day18?)
-        .and(day19?)
-        .and(day20?)
+    let cli = Cli::parse();
+    if let Some(profile) = cli.profile {
+        days::config::use_profile(profile);
+    }
+    if cli.check_update {
+        match days::update::check_update().await {
+            Ok(Some(release)) => eprintln!("A newer version {} is available at {}", release.tag_name, release.html_url),
+            Ok(None) => eprintln!("{} is the latest version", env!("CARGO_PKG_VERSION")),
+            Err(error) => eprintln!("Failed to check for updates: {error}"),
+        }
+    }
+
+    match cli.command.unwrap_or(Command::Run(RunArgs::default())) {
+        Command::Run(RunArgs { mut day, all, example, refresh_input, wait, strategy, jobs, force }) => {
+            days::approach::use_calibration(Config::load()?.strategies);
+            days::use_examples(example);
+            days::refresh_inputs(refresh_input);
+            days::wait_for_unlock(wait);
+            if let Some(strategy) = strategy {
+                if !approach_histories().any(|(_, history)| history.get(&strategy).is_some()) {
+                    return Err(eyre!("No day implements the {strategy} approach"));
+                }
+                days::approach::use_strategy(strategy);
+            }
+            let jobs = match jobs {
+                Some(jobs) => jobs,
+                None => std::thread::available_parallelism()
+                    .map(usize::from)
+                    .unwrap_or(1),
+            };
+            if day.is_empty() && !all {
+                // during the event only today's puzzle runs, unless it isn't solved yet
+                match Day::today(SystemTime::now(), days::YEAR) {
+                    Some(today) if days::registration(today).is_some() => day.push(today),
+                    Some(today) => eprintln!("{today} is not implemented yet, running all days instead"),
+                    None => (),
+                }
+            }
+            days::answer_cache::use_cache(AnswerCache::load()?);
+            days::answer_cache::force(force);
+            let result = run_all(&day, jobs).await;
+            days::answer_cache::save()?;
+            result
+        }
+        Command::List => {
+            for day in Day::ALL {
+                println!("{day}: {}", days::status(day));
+                if let Some(history) = days::approach_history(day) {
+                    println!("{history}");
+                }
+            }
+            Ok(())
+        }
+        Command::Calibrate { runs } => {
+            let mut config = Config::load()?;
+            for (day, history) in approach_histories() {
+                let input = days::get_input(days::YEAR, day).await?;
+                let timings = history.calibrate(&input.body, runs)?;
+                println!("{day}:");
+                for timing in &timings {
+                    println!("  {}: {:?}", timing.approach.name, timing.duration);
+                }
+                if let Some(fastest) = timings.first() {
+                    config.strategies.insert((*day).to_string(), fastest.approach.name.to_string());
+                }
+            }
+            config.save()
+        }
+        Command::Puzzle { day } => {
+            println!("{}", days::puzzle::get_puzzle(day).await?);
+            Ok(())
+        }
+        Command::Submit { day, part, answer } => {
+            println!("{}", days::submit::submit(day, part, &answer).await?);
+            Ok(())
+        }
+        Command::HashAnswer { day, part, answer } => {
+            println!("{}", days::solver::answer_hash(day, part, &answer));
+            Ok(())
+        }
+        Command::Leaderboard { id } => {
+            println!("{}", days::leaderboard::get_leaderboard(id).await?);
+            Ok(())
+        }
+        Command::VerifyInputs { record } => {
+            let mut manifest = Manifest::load()?;
+            let mut mismatches = 0;
+            for day in Day::ALL {
+                let Some(input) = days::cached_input(days::YEAR, day)? else {
+                    continue;
+                };
+                if record {
+                    manifest.record(days::YEAR, day, days::sha256(&input));
+                    continue;
+                }
+                let verification = manifest.verify(days::YEAR, day, Some(&input));
+                if matches!(verification, Verification::Differs { .. }) {
+                    mismatches += 1;
+                }
+                println!("{day}: {verification}");
+            }
+
+            if record {
+                manifest.save()
+            } else if mismatches > 0 {
+                Err(eyre!("{mismatches} inputs differ from the manifest, their recorded answers won't match"))
+            } else {
+                Ok(())
+            }
+        }
+        Command::Completions { shell } => {
+            clap_complete::generate(shell, &mut Cli::command(), env!("CARGO_BIN_NAME"), &mut std::io::stdout());
+            Ok(())
+        }
+        Command::Mangen => {
+            clap_mangen::Man::new(Cli::command())
+                .render(&mut std::io::stdout())
+                .wrap_err("Failed to render the man page")
+        }
+        Command::Report { log, json } => {
+            let file = std::fs::File::open(&log)
+                .wrap_err(format!("Failed to open {}", log.display()))?;
+            let report = RunReport::from_log(BufReader::new(file))?;
+            if json {
+                println!("{}", serde_json::to_string_pretty(&report)?);
+            } else {
+                println!("{report}");
+            }
+            Ok(())
+        }
+    }
+}
+
+fn approach_histories() -> impl Iterator<Item = (Day, ApproachHistory)> {
+    days::all().into_iter()
+        .filter_map(|registration| registration.approach_history.map(|history| (registration.day, history)))
+}
+
+/// Runs the registered `days`, or all of them if there are none, at most `jobs` at once, and prints
+/// their results in day order as soon as all earlier days have finished.
+async fn run_all(days: &[Day], jobs: usize) -> eyre::Result<()> {
+    if let Some(day) = days.iter().find(|&&day| days::registration(day).is_none()) {
+        return Err(eyre!("{day} is not implemented, pick another one with --day or run all days with --all"));
+    }
+
+    let registrations = days::all().into_iter()
+        .filter(|registration| days.is_empty() || days.contains(&registration.day))
+        .collect::<Vec<_>>();
+
+    let permits = Arc::new(Semaphore::new(jobs));
+    let (sender, mut receiver) = mpsc::unbounded_channel();
+    let mut tasks = JoinSet::new();
+    for &registration in &registrations {
+        let permits = permits.clone();
+        let sender = sender.clone();
+        tasks.spawn(async move {
+            let _permit = permits.acquire_owned().await?;
+            let result = (registration.run)().await;
+            // the receiver only goes away once every day has been printed
+            let _ = sender.send((registration.day, result));
+            eyre::Ok(())
+        });
+    }
+    drop(sender);
+
+    let mut result = Ok(());
+    let mut print = |day_result: eyre::Result<DayResult>| match day_result {
+        Ok(day_result) => println!("{day_result}"),
+        Err(error) => if result.is_ok() {
+            result = Err(error);
+        },
+    };
+
+    let mut next_days = registrations.iter().map(|registration| registration.day).peekable();
+    let mut finished = BTreeMap::new();
+    while let Some((day, day_result)) = receiver.recv().await {
+        finished.insert(day, day_result);
+        while let Some(day_result) = next_days.peek().and_then(|day| finished.remove(day)) {
+            next_days.next();
+            print(day_result);
+        }
+    }
+    // days after one that never finished, they are reported by the join below
+    finished.into_values().for_each(&mut print);
+
+    while let Some(task) = tasks.join_next().await {
+        task??;
+    }
+    result
 }
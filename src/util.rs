@@ -1,7 +1,21 @@
+use tracing_subscriber::EnvFilter;
+use tracing_subscriber::fmt::format::FmtSpan;
+
 pub fn setup() -> eyre::Result<()> {
     color_eyre::install()?;
 
-    tracing_subscriber::fmt::init();
+    // `AOC_LOG_FORMAT=json` writes machine-readable logs including span timings to stderr, which can
+    // be aggregated again with the `report` command
+    if std::env::var("AOC_LOG_FORMAT").is_ok_and(|format| format == "json") {
+        tracing_subscriber::fmt()
+            .json()
+            .with_span_events(FmtSpan::CLOSE)
+            .with_env_filter(EnvFilter::from_default_env())
+            .with_writer(std::io::stderr)
+            .init();
+    } else {
+        tracing_subscriber::fmt::init();
+    }
 
     Ok(())
 }
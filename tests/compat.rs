@@ -0,0 +1,94 @@
+// Golden tests locking the public behavior of the shared utilities, so refactors driven by one
+// day's solution can't silently change what the other days (and other years) rely on.
+
+use aoc_2024::days::util::{Coordinate, Direction, Grid};
+
+#[test]
+pub fn test_coordinate_arithmetic() {
+    let mut coordinate = Coordinate(3, -4);
+    assert_eq!(Coordinate(4, -6), coordinate + Coordinate(1, -2));
+    assert_eq!(Coordinate(2, -2), coordinate - Coordinate(1, -2));
+    assert_eq!(Coordinate(-9, 12), coordinate * -3);
+
+    coordinate += Coordinate::EAST;
+    assert_eq!(Coordinate(4, -4), coordinate);
+    coordinate -= Coordinate::NORTH;
+    assert_eq!(Coordinate(4, -3), coordinate);
+
+    assert_eq!(Coordinate(1, -1), Coordinate(7, -3).eigen_axis());
+    assert_eq!(Coordinate(0, 1), Coordinate(0, 12).eigen_axis());
+    assert_eq!(Coordinate(0, 0), Coordinate(0, 0).eigen_axis());
+
+    assert_eq!("(3, -4)", Coordinate(3, -4).to_string());
+}
+
+#[test]
+pub fn test_cardinalities() {
+    // y grows downwards, like the rows of a grid
+    assert_eq!([Coordinate(0, -1), Coordinate(1, 0), Coordinate(0, 1), Coordinate(-1, 0)], Coordinate::CARDINALITIES);
+    for direction in Direction::ALL {
+        let coordinate: Coordinate = direction.into();
+        assert_eq!(Coordinate::CARDINALITIES[direction.index()], coordinate, "{direction:?}");
+    }
+}
+
+#[test]
+pub fn test_direction_rotations() {
+    use Direction::*;
+
+    assert_eq!([East, South, West, North], Direction::ALL.map(|direction| direction.rotate90()));
+    assert_eq!([South, West, North, East], Direction::ALL.map(|direction| direction.rotate180()));
+    assert_eq!([West, North, East, South], Direction::ALL.map(|direction| direction.rotate270()));
+
+    for direction in Direction::ALL {
+        assert_eq!(direction.rotate180(), direction.rotate90().rotate90(), "{direction:?}");
+        assert_eq!(direction.rotate270(), direction.rotate180().rotate90(), "{direction:?}");
+        assert_eq!(direction, direction.rotate90().rotate270(), "{direction:?}");
+        assert_ne!(direction.vertical(), direction.horizontal(), "{direction:?}");
+    }
+    assert_eq!(['^', '>', 'v', '<'], Direction::ALL.map(|direction| direction.symbol()));
+}
+
+#[test]
+pub fn test_direction_masks() {
+    use Direction::*;
+
+    assert_eq!([1, 2, 4, 8], Direction::ALL.map(|direction| direction.mask()));
+    assert_eq!(vec![North, South], Direction::from_mask(North.mask() | South.mask()));
+    assert_eq!(Vec::<Direction>::new(), Direction::from_mask(0));
+    assert_eq!('│', Direction::DISPLAY[(North.mask() | South.mask()) as usize]);
+    assert_eq!('┼', Direction::DISPLAY[0b1111]);
+}
+
+#[test]
+pub fn test_grid_indices() {
+    let grid: Grid = r"abc
+                       def
+                       ".parse().unwrap();
+
+    assert_eq!(3, grid.width());
+    assert_eq!(Coordinate(2, 1), grid.index_to_coordinate(5));
+    assert_eq!(Some(4), grid.coordinate_to_index(Coordinate(1, 1)));
+    // neither wraps around to the next row nor runs past the end
+    assert_eq!(None, grid.coordinate_to_index(Coordinate(3, 0)));
+    assert_eq!(None, grid.coordinate_to_index(Coordinate(0, 2)));
+    assert_eq!(None, grid.coordinate_to_index(Coordinate(-1, 0)));
+    assert_eq!(None, grid.offset_index(2, Coordinate::EAST));
+    assert_eq!(Some(&'e'), grid.get_offset(1, Direction::South));
+}
+
+#[test]
+pub fn test_flood_distances() {
+    let grid: Grid = r"S..#
+                       .#..
+                       ...#
+                       ".parse().unwrap();
+
+    let distances = grid.flood(0, |tile| tile == '#');
+    let unreachable = usize::MAX;
+    assert_eq!(vec![
+        0, 1, 2, unreachable,
+        1, unreachable, 3, 4,
+        2, 3, 4, unreachable,
+    ], distances);
+}
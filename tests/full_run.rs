@@ -0,0 +1,66 @@
+use std::path::Path;
+
+use aoc_2024::days::*;
+
+/// Runs every day with a cached input against the answers recorded in `answers.toml`:
+///
+/// ```toml
+/// [day1]
+/// part1 = "..."
+/// part2 = "..."
+/// ```
+///
+/// Days whose cached input differs from the one in `input.manifest.toml` are reported, as their
+/// recorded answers are bound to fail.
+#[cfg_attr(not(feature = "real-inputs"), ignore)]
+#[test]
+pub fn test_full_run() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let answers: toml::Table = std::fs::read_to_string(root.join("answers.toml"))
+        .expect("Failed to read answers.toml")
+        .parse()
+        .expect("Failed to parse answers.toml");
+
+    let manifest = manifest::Manifest::load().unwrap();
+
+    for registration in all() {
+        let day = registration.day;
+        let Some(raw_input) = cached_input(YEAR, day).unwrap() else {
+            println!("{day}: skipped, no cached input");
+            continue;
+        };
+        if let verification @ manifest::Verification::Differs { .. } = manifest.verify(YEAR, day, Some(&raw_input)) {
+            println!("{day}: warning, the cached input {verification}, so the recorded answers are for a different input");
+        }
+        let Some(expected) = answers.get(&format!("day{}", *day)) else {
+            println!("{day}: skipped, no recorded answers");
+            continue;
+        };
+
+        let (result1, result2) = (registration.solve)(&raw_input).unwrap();
+        assert_eq!(expected.get("part1").and_then(|part| part.as_str()), Some(result1.to_string().as_str()), "{day} part 1");
+        assert_eq!(expected.get("part2").and_then(|part| part.as_str()), Some(result2.to_string().as_str()), "{day} part 2");
+    }
+}
+
+/// Runs every day with a cached input against the hashed answers registered in its module, which
+/// needs neither `answers.toml` nor `input.manifest.toml`.
+#[cfg_attr(not(all(feature = "real-inputs", feature = "my-answers")), ignore)]
+#[test]
+pub fn test_my_answers() {
+    for registration in all() {
+        let day = registration.day;
+        let Some(my_answers) = registration.my_answers else {
+            println!("{day}: skipped, no registered answers");
+            continue;
+        };
+        let Some(raw_input) = cached_input(YEAR, day).unwrap() else {
+            println!("{day}: skipped, no cached input");
+            continue;
+        };
+
+        let (result1, result2) = (registration.solve)(&raw_input).unwrap();
+        assert_ne!(Some(false), my_answers.verify(day, 1, &result1), "{day} part 1 answered {result1}");
+        assert_ne!(Some(false), my_answers.verify(day, 2, &result2), "{day} part 2 answered {result2}");
+    }
+}